@@ -1,7 +1,7 @@
 #! /bin/sh
 cargo fmt --check &&
-cargo clippy -- -Dwarnings &&
-cargo clippy --tests -- -Dwarnings &&
+cargo clippy --workspace --all-features -- -Dwarnings &&
+cargo clippy --workspace --all-features --tests -- -Dwarnings &&
 # cargo test --doc &&
-cargo test &&
-cargo doc
+cargo test --workspace --all-features &&
+cargo doc --all-features
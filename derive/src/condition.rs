@@ -0,0 +1,297 @@
+//! The `condition!` macro.
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    ext::IdentExt,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token, Error, Ident, LitStr, Result, Token,
+};
+
+use crate::syntax::{
+    expect_end, krate, parse_keyword, peek_function, peek_keyword, Interpolated, PathSyntax,
+    ValueSyntax,
+};
+
+pub(crate) fn expand(input: TokenStream) -> Result<TokenStream> {
+    syn::parse2::<ConditionSyntax>(input).map(ToTokens::into_token_stream)
+}
+
+/// A complete condition. Parses `OR`, which has the lowest precedence.
+struct ConditionSyntax(TokenStream);
+
+impl Parse for ConditionSyntax {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let condition = or(input)?;
+        if !input.is_empty() {
+            return Err(input.error("expected `AND`, `OR`, or the end of the condition"));
+        }
+
+        Ok(Self(condition))
+    }
+}
+
+impl ToTokens for ConditionSyntax {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.0.to_tokens(tokens)
+    }
+}
+
+fn or(input: ParseStream) -> Result<TokenStream> {
+    let mut condition = and(input)?;
+    while peek_keyword(input, "OR") {
+        Ident::parse_any(input)?;
+        let right = and(input)?;
+        condition = quote!(#condition.or(#right));
+    }
+
+    Ok(condition)
+}
+
+fn and(input: ParseStream) -> Result<TokenStream> {
+    let mut condition = not(input)?;
+    while peek_keyword(input, "AND") {
+        Ident::parse_any(input)?;
+        let right = not(input)?;
+        condition = quote!(#condition.and(#right));
+    }
+
+    Ok(condition)
+}
+
+fn not(input: ParseStream) -> Result<TokenStream> {
+    if peek_keyword(input, "NOT") {
+        Ident::parse_any(input)?;
+        let condition = not(input)?;
+        return Ok(quote!(#condition.not()));
+    }
+
+    primary(input)
+}
+
+fn primary(input: ParseStream) -> Result<TokenStream> {
+    if input.is_empty() {
+        return Err(input.error("expected a condition"));
+    }
+
+    if input.peek(token::Paren) {
+        let content;
+        parenthesized!(content in input);
+        let condition: ConditionSyntax = content.parse()?;
+        return Ok(quote!(#condition.parenthesize()));
+    }
+
+    for name in [
+        "attribute_exists",
+        "attribute_not_exists",
+        "attribute_type",
+        "begins_with",
+        "contains",
+    ] {
+        if peek_function(input, name) {
+            return function(input);
+        }
+    }
+
+    // A `{condition}` on its own, rather than the start of a comparison.
+    if input.peek(token::Brace) {
+        let fork = input.fork();
+        fork.parse::<Interpolated>()?;
+        if !peek_operator(&fork) {
+            let Interpolated(expr) = input.parse()?;
+            let krate = krate();
+            // Kept grouped, as it would be if it were written out in
+            // parentheses.
+            return Ok(quote! {
+                match <#krate::condition::Condition as ::core::convert::From<_>>::from(#expr) {
+                    condition @ (#krate::condition::Condition::And(_)
+                    | #krate::condition::Condition::Or(_)) => condition.parenthesize(),
+                    condition => condition,
+                }
+            });
+        }
+    }
+
+    comparison(input)
+}
+
+/// `attribute_exists(path)`, `begins_with(path, "prefix")`, etc.
+fn function(input: ParseStream) -> Result<TokenStream> {
+    let name = Ident::parse_any(input)?;
+    let content;
+    parenthesized!(content in input);
+    let path: PathSyntax = content.parse()?;
+
+    let condition = match name.to_string().as_str() {
+        "attribute_exists" => quote!(#path.attribute_exists()),
+        "attribute_not_exists" => quote!(#path.attribute_not_exists()),
+        "attribute_type" => {
+            content.parse::<Token![,]>()?;
+            let attribute_type = attribute_type(&content)?;
+            quote!(#path.attribute_type(#attribute_type))
+        }
+        "begins_with" => {
+            content.parse::<Token![,]>()?;
+            let prefix: ValueSyntax = content.parse()?;
+            quote!(#path.begins_with(#prefix))
+        }
+        "contains" => {
+            content.parse::<Token![,]>()?;
+            let value: ValueSyntax = content.parse()?;
+            quote!(#path.contains(#value))
+        }
+        _ => unreachable!("only known functions are parsed"),
+    };
+
+    expect_end(&content)?;
+
+    Ok(condition)
+}
+
+/// The type for `attribute_type`: a DynamoDB type code, like `S` or `"S"`,
+/// or `{expr}`.
+fn attribute_type(input: ParseStream) -> Result<TokenStream> {
+    if input.peek(token::Brace) {
+        let Interpolated(expr) = input.parse()?;
+        return Ok(expr.into_token_stream());
+    }
+
+    let (code, span) = if input.peek(LitStr) {
+        let code: LitStr = input.parse()?;
+        (code.value(), code.span())
+    } else {
+        let code = Ident::parse_any(input)?;
+        (code.to_string(), code.span())
+    };
+
+    let variant = match code.as_str() {
+        "S" => quote!(String),
+        "SS" => quote!(StringSet),
+        "N" => quote!(Number),
+        "NS" => quote!(NumberSet),
+        "B" => quote!(Binary),
+        "BS" => quote!(BinarySet),
+        "BOOL" => quote!(Boolean),
+        "NULL" => quote!(Null),
+        "L" => quote!(List),
+        "M" => quote!(Map),
+        _ => {
+            return Err(Error::new(
+                span,
+                "unknown attribute type; expected one of \
+                S, SS, N, NS, B, BS, BOOL, NULL, L, or M",
+            ))
+        }
+    };
+
+    let krate = krate();
+    Ok(quote!(#krate::condition::attribute_type::Type::#variant))
+}
+
+/// Whether the next token is a comparison operator, `BETWEEN`, or `IN`.
+fn peek_operator(input: ParseStream) -> bool {
+    input.peek(Token![=])
+        || input.peek(Token![!=])
+        || input.peek(Token![<])
+        || input.peek(Token![>])
+        || peek_keyword(input, "BETWEEN")
+        || peek_keyword(input, "IN")
+}
+
+/// `a = b`, `a BETWEEN b AND c`, `a IN (b, c)`, etc.
+fn comparison(input: ParseStream) -> Result<TokenStream> {
+    let left: OperandSyntax = input.parse()?;
+
+    if peek_keyword(input, "BETWEEN") {
+        Ident::parse_any(input)?;
+        let lower: OperandSyntax = input.parse()?;
+        parse_keyword(input, "AND")?;
+        let upper: OperandSyntax = input.parse()?;
+
+        return Ok(quote!(#left.between(#lower, #upper)));
+    }
+
+    if peek_keyword(input, "IN") {
+        Ident::parse_any(input)?;
+        let content;
+        parenthesized!(content in input);
+        let items = Punctuated::<OperandSyntax, Token![,]>::parse_terminated(&content)?;
+        if items.is_empty() {
+            return Err(content.error("expected at least one operand"));
+        }
+        let items = items.iter();
+
+        return Ok(quote!(#left.in_([#(#items),*])));
+    }
+
+    let method = if input.peek(Token![==]) {
+        input.parse::<Token![==]>()?;
+        quote!(equal)
+    } else if input.peek(Token![=]) {
+        input.parse::<Token![=]>()?;
+        quote!(equal)
+    } else if input.peek(Token![!=]) {
+        input.parse::<Token![!=]>()?;
+        quote!(not_equal)
+    } else if input.peek(Token![<=]) {
+        input.parse::<Token![<=]>()?;
+        quote!(less_than_or_equal)
+    } else if input.peek(Token![<]) {
+        input.parse::<Token![<]>()?;
+        if input.peek(Token![>]) {
+            input.parse::<Token![>]>()?;
+            quote!(not_equal)
+        } else {
+            quote!(less_than)
+        }
+    } else if input.peek(Token![>=]) {
+        input.parse::<Token![>=]>()?;
+        quote!(greater_than_or_equal)
+    } else if input.peek(Token![>]) {
+        input.parse::<Token![>]>()?;
+        quote!(greater_than)
+    } else {
+        return Err(input.error("expected a comparison operator, `BETWEEN`, or `IN`"));
+    };
+
+    let right: OperandSyntax = input.parse()?;
+
+    Ok(quote!(#left.#method(#right)))
+}
+
+/// One side of a comparison: an attribute path, `size(path)`, or a value.
+/// Expands to an `Operand`.
+struct OperandSyntax(TokenStream);
+
+impl Parse for OperandSyntax {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let operand = if peek_function(input, "size") {
+            Ident::parse_any(input)?;
+            let content;
+            parenthesized!(content in input);
+            let path: PathSyntax = content.parse()?;
+            expect_end(&content)?;
+
+            quote!(#path.size())
+        } else if PathSyntax::peek_literal(input) {
+            input.parse::<PathSyntax>()?.into_token_stream()
+        } else if ValueSyntax::peek(input) {
+            input.parse::<ValueSyntax>()?.into_token_stream()
+        } else {
+            return Err(input.error("expected an attribute path, `size(...)`, or a value"));
+        };
+
+        let krate = krate();
+        Ok(Self(quote! {
+            <#krate::operand::Operand as ::core::convert::From<_>>::from(#operand)
+        }))
+    }
+}
+
+impl ToTokens for OperandSyntax {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.0.to_tokens(tokens)
+    }
+}
@@ -0,0 +1,352 @@
+/*!
+Procedural macros for [`dynamodb-expression`][1]. Use them through that
+crate's `derive` and `macros` features, rather than depending on this crate
+directly.
+
+[1]: https://docs.rs/dynamodb-expression/
+*/
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    meta::ParseNestedMeta, parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput,
+    Error, Fields, LitStr, Result, Token,
+};
+
+mod condition;
+mod syntax;
+mod update;
+
+/// Generates typed paths for the fields of a struct.
+///
+/// For a struct named `Person`, this generates a `PersonPaths` type with a
+/// method for each field that returns the typed path to that attribute, and
+/// a `Person::paths()` function to get one.
+///
+/// These `serde` attributes are respected: `rename`, `rename_all`, `skip`,
+/// `skip_serializing`, `flatten`, and `with` (for the set helpers in
+/// `dynamodb_expression::value`).
+///
+/// See `dynamodb_expression::path::typed` for details and examples.
+#[proc_macro_derive(DynamoPaths, attributes(serde))]
+pub fn derive_dynamo_paths(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Builds a `Condition` from DynamoDB-like syntax.
+///
+/// ```text
+/// condition!(age >= {min_age} AND begins_with(name, {prefix}) AND attribute_exists(email))
+/// ```
+///
+/// Supported syntax, where keywords (`AND`, `OR`, `NOT`, `BETWEEN`, `IN`) are
+/// case-insensitive:
+///
+/// * Comparisons: `=` (or `==`), `<>` (or `!=`), `<`, `<=`, `>`, `>=`,
+///   `a BETWEEN b AND c`, and `a IN (b, c, ...)`.
+/// * Functions: `attribute_exists(path)`, `attribute_not_exists(path)`,
+///   `attribute_type(path, S)`, `begins_with(path, prefix)`,
+///   `contains(path, value)`, and `size(path)` as an operand.
+/// * Logical operators: `AND`, `OR`, and `NOT`, with the same precedence as
+///   DynamoDB, and parentheses for grouping.
+///
+/// Attribute paths are written as-is (`foo.bar[2]`). String, number, and
+/// boolean literals become values, with numbers becoming a `Num`. Any other
+/// Rust expression can be interpolated in braces: `{expr}`. In place of an
+/// operand, it must be something that converts into an `Operand` (a `Path`,
+/// `Num`, etc.); on its own, something that converts into a `Condition`. An
+/// interpolated `AND` or `OR` condition is wrapped in parentheses, so it stays
+/// grouped as one condition.
+///
+/// Syntax errors are reported at the offending token.
+#[proc_macro]
+pub fn condition(input: TokenStream) -> TokenStream {
+    condition::expand(input.into())
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Builds an `Update` from DynamoDB-like syntax.
+///
+/// ```text
+/// update!(SET count = count + 1, name = {name}, tags = list_append(tags, {tags}))
+/// update!(REMOVE a, b[0])
+/// update!(ADD count 1)
+/// update!(DELETE colors {colors})
+/// update!(SET a = 1 REMOVE b ADD c 1, d {set})
+/// ```
+///
+/// `SET` supports assigning a value, `path + value`, `path - value`,
+/// `if_not_exists(path, value)`, and `list_append` with the attribute path as
+/// either argument. Each clause can be used once, in any order, with any
+/// number of comma-separated actions.
+///
+/// Paths, values, and `{expr}` interpolation work as they do for
+/// [`condition!`].
+#[proc_macro]
+pub fn update(input: TokenStream) -> TokenStream {
+    update::expand(input.into())
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "`DynamoPaths` can't be derived for generic types",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "`DynamoPaths` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "`DynamoPaths` can only be derived for structs",
+            ))
+        }
+    };
+
+    let container = SerdeAttrs::parse(&input.attrs)?;
+    let rename_all = container
+        .rename_all
+        .map(|rename_all| {
+            RenameRule::parse(&rename_all.value())
+                .ok_or_else(|| Error::new(rename_all.span(), "unknown `rename_all` rule"))
+        })
+        .transpose()?;
+
+    let crate_path = quote!(::dynamodb_expression);
+    let typed = quote!(#crate_path::path::typed);
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let paths_ident = format_ident!("{}Paths", ident);
+    let paths_doc = format!("Typed paths to the attributes of [`{ident}`].");
+
+    let mut accessors = Vec::new();
+    for field in fields {
+        let attrs = SerdeAttrs::parse(&field.attrs)?;
+        if attrs.skip {
+            continue;
+        }
+
+        let field_ident = field.ident.as_ref().expect("named fields have names");
+        let ty = &field.ty;
+
+        let accessor = if attrs.flatten {
+            quote! {
+                #vis fn #field_ident(&self) -> <#ty as #typed::PathType>::Path {
+                    #typed::TypedPath::from_path(::core::clone::Clone::clone(&self.path))
+                }
+            }
+        } else {
+            let name = match attrs.rename {
+                Some(rename) => rename.value(),
+                None => {
+                    let name = field_ident.to_string();
+                    let name = name.strip_prefix("r#").unwrap_or(&name);
+
+                    match rename_all {
+                        Some(rule) => rule.apply(name),
+                        None => name.to_owned(),
+                    }
+                }
+            };
+
+            let doc = format!("The path to the `{name}` attribute.");
+            let ret = if attrs.set {
+                quote!(#typed::SetPath)
+            } else {
+                quote!(<#ty as #typed::PathType>::Path)
+            };
+
+            quote! {
+                #[doc = #doc]
+                #vis fn #field_ident(&self) -> #ret {
+                    #typed::TypedPath::from_path(#typed::field(&self.path, #name))
+                }
+            }
+        };
+
+        accessors.push(accessor);
+    }
+
+    Ok(quote! {
+        #[doc = #paths_doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis struct #paths_ident {
+            path: #crate_path::path::Path,
+        }
+
+        impl #paths_ident {
+            #(#accessors)*
+        }
+
+        impl #typed::TypedPath for #paths_ident {
+            fn from_path(path: #crate_path::path::Path) -> Self {
+                Self { path }
+            }
+        }
+
+        impl ::core::convert::From<#paths_ident> for #crate_path::path::Path {
+            fn from(paths: #paths_ident) -> Self {
+                paths.path
+            }
+        }
+
+        impl #typed::PathType for #ident {
+            type Path = #paths_ident;
+        }
+
+        impl #ident {
+            /// The typed paths to the top-level attributes of this type.
+            #vis fn paths() -> #paths_ident {
+                #typed::root::<Self>()
+            }
+        }
+    })
+}
+
+/// The parts of `#[serde(...)]` attributes that affect attribute paths.
+#[derive(Default)]
+struct SerdeAttrs {
+    rename: Option<LitStr>,
+    rename_all: Option<LitStr>,
+    skip: bool,
+    flatten: bool,
+    /// Serialized as a DynamoDB set, with `with = "...::string_set"`, etc.
+    set: bool,
+}
+
+impl SerdeAttrs {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    parsed.rename = serialize_name(&meta)?;
+                } else if meta.path.is_ident("rename_all") {
+                    parsed.rename_all = serialize_name(&meta)?;
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_serializing") {
+                    parsed.skip = true;
+                } else if meta.path.is_ident("flatten") {
+                    parsed.flatten = true;
+                } else if meta.path.is_ident("with") {
+                    let with: LitStr = meta.value()?.parse()?;
+                    let with = with.value();
+                    parsed.set = ["string_set", "num_set", "binary_set"]
+                        .iter()
+                        .any(|set| with.rsplit("::").next() == Some(*set));
+                } else {
+                    skip(&meta)?;
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Parses `name = "..."` or `name(serialize = "...", deserialize = "...")`,
+/// keeping the name used when serializing.
+fn serialize_name(meta: &ParseNestedMeta) -> Result<Option<LitStr>> {
+    if meta.input.peek(Token![=]) {
+        return meta.value()?.parse().map(Some);
+    }
+
+    let mut name = None;
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident("serialize") {
+            name = Some(meta.value()?.parse()?);
+        } else {
+            skip(&meta)?;
+        }
+
+        Ok(())
+    })?;
+
+    Ok(name)
+}
+
+/// Skips over an attribute that isn't needed.
+fn skip(meta: &ParseNestedMeta) -> Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<syn::Lit>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|meta| skip(&meta))?;
+    }
+
+    Ok(())
+}
+
+/// The `serde` `rename_all` rules.
+#[derive(Debug, Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn parse(rule: &str) -> Option<Self> {
+        Some(match rule {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            _ => return None,
+        })
+    }
+
+    /// Applies the rule to a (`snake_case`) field name.
+    fn apply(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_owned(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal | Self::Camel => {
+                let mut name = String::with_capacity(field.len());
+                let mut capitalize = matches!(self, Self::Pascal);
+                for c in field.chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        name.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        name.push(c);
+                    }
+                }
+
+                name
+            }
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.replace('_', "-").to_ascii_uppercase(),
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! Parsing shared by the `condition!` and `update!` macros.
+
+use proc_macro2::{Literal, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    braced, bracketed,
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    token, Error, Expr, Ident, Lit, LitBool, LitInt, Result, Token,
+};
+
+/// The path to the `dynamodb_expression` crate in generated code.
+pub(crate) fn krate() -> TokenStream {
+    quote!(::dynamodb_expression)
+}
+
+/// Whether the next token is the given keyword, ignoring case.
+pub(crate) fn peek_keyword(input: ParseStream, keyword: &str) -> bool {
+    input
+        .cursor()
+        .ident()
+        .is_some_and(|(ident, _)| ident.to_string().eq_ignore_ascii_case(keyword))
+}
+
+/// Parses the given keyword, ignoring case.
+pub(crate) fn parse_keyword(input: ParseStream, keyword: &str) -> Result<Ident> {
+    if peek_keyword(input, keyword) {
+        Ident::parse_any(input)
+    } else {
+        Err(input.error(format!("expected `{keyword}`")))
+    }
+}
+
+/// Whether the next tokens are a function call, like `size(`.
+pub(crate) fn peek_function(input: ParseStream, name: &str) -> bool {
+    input
+        .cursor()
+        .ident()
+        .is_some_and(|(ident, _)| ident == name)
+        && input.peek2(token::Paren)
+}
+
+/// Errors if there's anything left in a parenthesized group.
+pub(crate) fn expect_end(input: ParseStream) -> Result<()> {
+    if input.is_empty() {
+        Ok(())
+    } else {
+        Err(input.error("unexpected token"))
+    }
+}
+
+/// A Rust expression in braces: `{expr}`.
+pub(crate) struct Interpolated(pub(crate) Expr);
+
+impl Parse for Interpolated {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        braced!(content in input);
+        let expr = content.parse()?;
+        expect_end(&content)?;
+
+        Ok(Self(expr))
+    }
+}
+
+/// An attribute path, like `foo.bar[2]`, or `{expr}` for anything that's
+/// `Into<Path>`.
+pub(crate) struct PathSyntax(TokenStream);
+
+impl PathSyntax {
+    /// Whether the next token starts an attribute path (not an interpolated
+    /// expression).
+    pub(crate) fn peek_literal(input: ParseStream) -> bool {
+        input.peek(Ident::peek_any) && !input.peek(LitBool)
+    }
+}
+
+impl Parse for PathSyntax {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let krate = krate();
+
+        if input.peek(token::Brace) {
+            let Interpolated(expr) = input.parse()?;
+            return Ok(Self(quote! {
+                ::core::convert::Into::<#krate::path::Path>::into(#expr)
+            }));
+        }
+
+        if !Self::peek_literal(input) {
+            return Err(input.error("expected an attribute path"));
+        }
+
+        let mut elements = Vec::new();
+        loop {
+            let name = Ident::parse_any(input)?.unraw().to_string();
+
+            let mut indexes = Vec::new();
+            while input.peek(token::Bracket) {
+                let content;
+                bracketed!(content in input);
+                let index: LitInt = content.parse()?;
+                expect_end(&content)?;
+                indexes.push(Literal::usize_suffixed(index.base10_parse()?));
+            }
+
+            elements.push(if indexes.is_empty() {
+                quote!(#krate::path::Element::new_name(#name))
+            } else {
+                quote!(#krate::path::Element::new_indexed_field(#name, [#(#indexes),*]))
+            });
+
+            if !input.peek(Token![.]) {
+                break;
+            }
+            input.parse::<Token![.]>()?;
+        }
+
+        Ok(Self(quote! {
+            <#krate::path::Path as ::core::iter::FromIterator<_>>::from_iter([#(#elements),*])
+        }))
+    }
+}
+
+impl ToTokens for PathSyntax {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.0.to_tokens(tokens)
+    }
+}
+
+/// A value: a string, number, or boolean literal, or `{expr}`.
+///
+/// Number literals become a `Num`.
+pub(crate) struct ValueSyntax(TokenStream);
+
+impl ValueSyntax {
+    pub(crate) fn peek(input: ParseStream) -> bool {
+        input.peek(token::Brace) || input.peek(Lit) || input.peek(Token![-])
+    }
+}
+
+impl Parse for ValueSyntax {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(token::Brace) {
+            let Interpolated(expr) = input.parse()?;
+            return Ok(Self(expr.into_token_stream()));
+        }
+
+        let minus: Option<Token![-]> = input.parse()?;
+        let lit: Lit = input.parse().map_err(|_| input.error("expected a value"))?;
+
+        let krate = krate();
+        let value = match lit {
+            Lit::Int(_) | Lit::Float(_) => quote!(#krate::Num::new(#minus #lit)),
+            Lit::Str(_) | Lit::Bool(_) if minus.is_none() => lit.into_token_stream(),
+            Lit::Str(_) | Lit::Bool(_) => {
+                return Err(Error::new(lit.span(), "expected a number after `-`"))
+            }
+            _ => {
+                return Err(Error::new(
+                    lit.span(),
+                    "only string, number, and boolean literals are supported",
+                ))
+            }
+        };
+
+        Ok(Self(value))
+    }
+}
+
+impl ToTokens for ValueSyntax {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.0.to_tokens(tokens)
+    }
+}
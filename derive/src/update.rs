@@ -0,0 +1,194 @@
+//! The `update!` macro.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    ext::IdentExt,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    Error, Ident, Result, Token,
+};
+
+use crate::syntax::{expect_end, krate, peek_function, peek_keyword, PathSyntax, ValueSyntax};
+
+pub(crate) fn expand(input: TokenStream) -> Result<TokenStream> {
+    syn::parse2::<UpdateSyntax>(input).map(|update| update.0)
+}
+
+const CLAUSES: [&str; 4] = ["SET", "REMOVE", "ADD", "DELETE"];
+
+fn peek_clause(input: ParseStream) -> bool {
+    CLAUSES.iter().any(|clause| peek_keyword(input, clause))
+}
+
+struct UpdateSyntax(TokenStream);
+
+impl Parse for UpdateSyntax {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if !peek_clause(input) {
+            return Err(input.error("expected `SET`, `REMOVE`, `ADD`, or `DELETE`"));
+        }
+
+        let krate = krate();
+        let mut seen = Vec::new();
+        let mut update = None;
+        while peek_clause(input) {
+            let keyword = Ident::parse_any(input)?;
+            let clause = keyword.to_string().to_ascii_uppercase();
+            if seen.contains(&clause) {
+                return Err(Error::new(
+                    keyword.span(),
+                    format!("`{clause}` can only be used once in an update"),
+                ));
+            }
+
+            let actions = match clause.as_str() {
+                "SET" => vec![set(input)?],
+                "REMOVE" => vec![remove(input)?],
+                "ADD" | "DELETE" => add_or_delete(input, &clause)?,
+                _ => unreachable!("only known clauses are parsed"),
+            };
+            seen.push(clause);
+
+            for action in actions {
+                update = Some(match update {
+                    None => quote! {
+                        <#krate::update::Update as ::core::convert::From<_>>::from(#action)
+                    },
+                    Some(update) => quote!(#update.and(#action)),
+                });
+            }
+        }
+
+        if !input.is_empty() {
+            return Err(input.error("expected `,`, another clause, or the end of the update"));
+        }
+
+        Ok(Self(update.expect("at least one clause is parsed")))
+    }
+}
+
+/// `SET a = b, c = d + 1, ...`
+fn set(input: ParseStream) -> Result<TokenStream> {
+    let krate = krate();
+
+    let mut actions = Vec::new();
+    loop {
+        let action = set_action(input)?;
+        actions.push(quote!(#krate::update::SetAction::from(#action)));
+
+        if !input.peek(Token![,]) {
+            break;
+        }
+        input.parse::<Token![,]>()?;
+    }
+
+    Ok(quote! {
+        <#krate::update::Set as ::core::iter::FromIterator<_>>::from_iter([#(#actions),*])
+    })
+}
+
+fn set_action(input: ParseStream) -> Result<TokenStream> {
+    let dst: PathSyntax = input.parse()?;
+    input.parse::<Token![=]>()?;
+
+    if peek_function(input, "if_not_exists") {
+        Ident::parse_any(input)?;
+        let content;
+        parenthesized!(content in input);
+        let src: PathSyntax = content.parse()?;
+        content.parse::<Token![,]>()?;
+        let value: ValueSyntax = content.parse()?;
+        expect_end(&content)?;
+
+        return Ok(quote!(#dst.if_not_exists().src(#src).assign(#value)));
+    }
+
+    if peek_function(input, "list_append") {
+        Ident::parse_any(input)?;
+        let content;
+        parenthesized!(content in input);
+
+        let action = if PathSyntax::peek_literal(&content) {
+            let src: PathSyntax = content.parse()?;
+            content.parse::<Token![,]>()?;
+            let list: ValueSyntax = content.parse()?;
+
+            quote!(#dst.list_append().src(#src).after().list(#list))
+        } else {
+            let list: ValueSyntax = content.parse()?;
+            content.parse::<Token![,]>()?;
+            if !PathSyntax::peek_literal(&content) {
+                return Err(content.error("expected an attribute path"));
+            }
+            let src: PathSyntax = content.parse()?;
+
+            quote!(#dst.list_append().src(#src).before().list(#list))
+        };
+        expect_end(&content)?;
+
+        return Ok(action);
+    }
+
+    if PathSyntax::peek_literal(input) {
+        let span = input.span();
+        let src: PathSyntax = input.parse()?;
+        let op = if input.peek(Token![+]) {
+            input.parse::<Token![+]>()?;
+            quote!(add)
+        } else if input.peek(Token![-]) {
+            input.parse::<Token![-]>()?;
+            quote!(sub)
+        } else {
+            return Err(Error::new(
+                span,
+                "expected `+` or `-` after this path; an attribute can only be assigned a value, \
+                or the result of math, `if_not_exists`, or `list_append`",
+            ));
+        };
+        let num: ValueSyntax = input.parse()?;
+
+        return Ok(quote!(#dst.math().src(#src).#op(#num)));
+    }
+
+    let value: ValueSyntax = input.parse()?;
+
+    Ok(quote!(#dst.assign(#value)))
+}
+
+/// `REMOVE a, b, ...`
+fn remove(input: ParseStream) -> Result<TokenStream> {
+    let mut paths = Vec::new();
+    loop {
+        paths.push(input.parse::<PathSyntax>()?.into_token_stream());
+
+        if !input.peek(Token![,]) {
+            break;
+        }
+        input.parse::<Token![,]>()?;
+    }
+
+    let krate = krate();
+    Ok(quote! {
+        <#krate::update::Remove as ::core::iter::FromIterator<_>>::from_iter([#(#paths),*])
+    })
+}
+
+/// `ADD a 1, b 2, ...` or `DELETE a {set}, ...`
+fn add_or_delete(input: ParseStream, clause: &str) -> Result<Vec<TokenStream>> {
+    let method = Ident::new(&clause.to_ascii_lowercase(), Span::call_site());
+
+    let mut actions = Vec::new();
+    loop {
+        let path: PathSyntax = input.parse()?;
+        let value: ValueSyntax = input.parse()?;
+        actions.push(quote!(#path.#method(#value)));
+
+        if !input.peek(Token![,]) {
+            break;
+        }
+        input.parse::<Token![,]>()?;
+    }
+
+    Ok(actions)
+}
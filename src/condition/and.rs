@@ -8,15 +8,28 @@ use crate::condition::Condition;
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.LogicalEvaluations
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct And {
     pub(crate) left: Box<Condition>,
     pub(crate) right: Box<Condition>,
 }
 
+impl And {
+    /// The condition on the left side of the `AND`.
+    pub fn left(&self) -> &Condition {
+        &self.left
+    }
+
+    /// The condition on the right side of the `AND`.
+    pub fn right(&self) -> &Condition {
+        &self.right
+    }
+}
+
 impl fmt::Display for And {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.left.fmt(f)?;
-        f.write_str(" AND ")?;
+        f.write_str(if f.alternate() { "\nAND " } else { " AND " })?;
         self.right.fmt(f)
     }
 }
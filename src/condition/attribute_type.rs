@@ -1,6 +1,9 @@
 use core::fmt::{self, Write};
 
-use crate::path::Path;
+use crate::{
+    path::Path,
+    value::{Scalar, Set, Value},
+};
 
 /// The [DynamoDB `attribute_type` function][1]. True if the attribute at
 /// the specified [`Path`] is of the specified data type.
@@ -9,6 +12,7 @@ use crate::path::Path;
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.Functions
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AttributeType {
     // `Path` is correct here
     // https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.Syntax
@@ -26,6 +30,16 @@ impl AttributeType {
             attribute_type,
         }
     }
+
+    /// The path to the attribute being checked.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The type the attribute must be.
+    pub fn attribute_type(&self) -> Type {
+        self.attribute_type
+    }
 }
 
 impl fmt::Display for AttributeType {
@@ -42,6 +56,8 @@ impl fmt::Display for AttributeType {
 ///
 /// See also: [Path::attribute_type]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Type {
     String,
     StringSet,
@@ -70,6 +86,22 @@ impl Type {
             Self::Map => "M",
         }
     }
+
+    /// The type of the value.
+    pub(crate) fn of(value: &Value) -> Self {
+        match value {
+            Value::Scalar(Scalar::String(_)) => Self::String,
+            Value::Scalar(Scalar::Num(_)) => Self::Number,
+            Value::Scalar(Scalar::Bool(_)) => Self::Boolean,
+            Value::Scalar(Scalar::Binary(_)) => Self::Binary,
+            Value::Scalar(Scalar::Null) => Self::Null,
+            Value::Set(Set::StringSet(_)) => Self::StringSet,
+            Value::Set(Set::NumSet(_)) => Self::NumberSet,
+            Value::Set(Set::BinarySet(_)) => Self::BinarySet,
+            Value::Map(_) => Self::Map,
+            Value::List(_) => Self::List,
+        }
+    }
 }
 
 impl fmt::Display for Type {
@@ -28,6 +28,7 @@ use crate::{
 /// [`Key::begins_with`]: crate::key::Key::begins_with
 /// [`Ref`]: crate::value::Ref
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BeginsWith {
     // `Path` is correct here
     // https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.Syntax
@@ -57,6 +58,16 @@ impl BeginsWith {
             substr: substr.into().into(),
         }
     }
+
+    /// The path to the attribute being checked.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The prefix the attribute must begin with.
+    pub fn prefix(&self) -> &ValueOrRef {
+        &self.substr
+    }
 }
 
 impl fmt::Display for BeginsWith {
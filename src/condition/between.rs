@@ -10,6 +10,7 @@ use crate::operand::Operand;
 /// [`Path::between`]: crate::path::Path::between
 /// [`Key::between`]: crate::key::Key::between
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Between {
     pub(crate) op: Operand,
 
@@ -20,6 +21,23 @@ pub struct Between {
     pub(crate) upper: Operand,
 }
 
+impl Between {
+    /// The operand being checked.
+    pub fn operand(&self) -> &Operand {
+        &self.op
+    }
+
+    /// The lower bound, inclusive.
+    pub fn lower(&self) -> &Operand {
+        &self.lower
+    }
+
+    /// The upper bound, inclusive.
+    pub fn upper(&self) -> &Operand {
+        &self.upper
+    }
+}
+
 impl fmt::Display for Between {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.op.fmt(f)?;
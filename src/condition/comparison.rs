@@ -7,12 +7,30 @@ use crate::operand::Operand;
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.Comparators
 /// [`Condition`]: crate::condition::Condition
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Comparison {
     pub(crate) left: Operand,
     pub(crate) cmp: Comparator,
     pub(crate) right: Operand,
 }
 
+impl Comparison {
+    /// The operand on the left side of the comparison.
+    pub fn left(&self) -> &Operand {
+        &self.left
+    }
+
+    /// The comparison being made.
+    pub fn cmp(&self) -> Comparator {
+        self.cmp
+    }
+
+    /// The operand on the right side of the comparison.
+    pub fn right(&self) -> &Operand {
+        &self.right
+    }
+}
+
 impl fmt::Display for Comparison {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.left.fmt(f)?;
@@ -36,6 +54,8 @@ comparator ::=
     | >=
 */
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Comparator {
     /// Equal (`=`)
     Eq,
@@ -182,7 +202,7 @@ where
 mod test {
     use pretty_assertions::assert_str_eq;
 
-    use crate::path::Name;
+    use crate::{operand::OperandType, path::Name, Num};
 
     use super::{Comparator::*, *};
 
@@ -243,4 +263,15 @@ mod test {
             greater_than_or_equal(Name::from("foo"), Name::from("bar")).to_string()
         );
     }
+
+    #[test]
+    fn getters() {
+        let comparison = less_than(Name::from("a"), Num::new(5));
+        assert_eq!(
+            &OperandType::Path(Name::from("a").into()),
+            comparison.left().operand_type()
+        );
+        assert_eq!(Lt, comparison.cmp());
+        assert_eq!("5", comparison.right().to_string());
+    }
 }
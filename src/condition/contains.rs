@@ -35,6 +35,7 @@ use crate::{
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.Functions
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Contains {
     // `Path` is correct here
     // https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.Syntax
@@ -53,6 +54,16 @@ impl Contains {
             operand: operand.into().into(),
         }
     }
+
+    /// The path to the attribute being checked.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The value the attribute must contain.
+    pub fn operand(&self) -> &ValueOrRef {
+        &self.operand
+    }
 }
 
 impl fmt::Display for Contains {
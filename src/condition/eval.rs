@@ -0,0 +1,588 @@
+//! Evaluating a [`Condition`] against an item in memory, the way DynamoDB
+//! would.
+
+use core::{cmp::Ordering, fmt};
+use std::{borrow::Cow, error::Error};
+
+use crate::{
+    condition::{
+        attribute_type::Type, And, BeginsWith, Between, Comparator, Comparison, Condition,
+        Contains, In, Not, Or, Parenthetical,
+    },
+    operand::{Operand, OperandType, Size},
+    path::{Element, FieldName, IndexedField, NameRef, Path},
+    value::{decimal, Map, Num, Ref, Scalar, Set, Value, ValueOrRef},
+    visit::Visit,
+};
+
+/// The result of evaluating a [`Condition`] against an item, along with why.
+///
+/// Each `AND`, `OR`, and `NOT` has the conditions it's made of as
+/// [children](Explanation::children). Chains of `AND` or `OR` are flattened,
+/// so `a AND b AND c` has three children. The other conditions, such as
+/// comparisons and functions, are leaves that have the
+/// [values](Explanation::values) found in the item at the paths they use.
+///
+/// Every part of the condition is evaluated, even where DynamoDB could stop
+/// early, so the explanation is complete.
+///
+/// Its [`Display`] is a tree meant for logs:
+///
+/// ```text
+/// false: a = 1 AND attribute_exists(b)
+///   false: a = 1
+///     a: 2
+///   true: attribute_exists(b)
+///     b: "x"
+/// ```
+///
+/// See also: [`Condition::explain`], [`Expression::explain`]
+///
+/// [`Display`]: core::fmt::Display
+/// [`Expression::explain`]: crate::Expression::explain
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Explanation {
+    condition: Condition,
+    result: bool,
+    values: Vec<(Path, Option<Value>)>,
+    children: Vec<Explanation>,
+}
+
+impl Explanation {
+    /// The condition that was evaluated.
+    pub fn condition(&self) -> &Condition {
+        &self.condition
+    }
+
+    /// Whether the condition was true for the item.
+    pub fn result(&self) -> bool {
+        self.result
+    }
+
+    /// For a leaf condition, the paths it uses and the values found in the
+    /// item at them. `None` where the item doesn't have the attribute.
+    pub fn values(&self) -> &[(Path, Option<Value>)] {
+        &self.values
+    }
+
+    /// The conditions an `AND`, `OR`, or `NOT` is made of.
+    pub fn children(&self) -> &[Explanation] {
+        &self.children
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let indent = depth * 2;
+        writeln!(f, "{:indent$}{}: {}", "", self.result, self.condition)?;
+        for (path, value) in &self.values {
+            let indent = indent + 2;
+            match value {
+                Some(value) => writeln!(f, "{:indent$}{path}: {value}", "")?,
+                None => writeln!(f, "{:indent$}{path}: missing", "")?,
+            }
+        }
+
+        self.children
+            .iter()
+            .try_for_each(|child| child.fmt_indented(f, depth + 1))
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+/// An error evaluating a [`Condition`] in memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvalError {
+    /// The condition uses a [`NameRef`], which can't be resolved without the
+    /// expression attribute names.
+    UnresolvedName(NameRef),
+
+    /// The condition uses a [`Ref`], which can't be resolved without the
+    /// expression attribute values.
+    UnresolvedValue(Ref),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnresolvedName(name) => write!(f, "no attribute name for {name}"),
+            Self::UnresolvedValue(value) => write!(f, "no attribute value for {value}"),
+        }
+    }
+}
+
+impl Error for EvalError {}
+
+pub(super) fn evaluate(condition: &Condition, item: &Map) -> Result<bool, EvalError> {
+    Ok(match condition {
+        Condition::AttributeExists(condition) => resolve(item, &condition.path)?.is_some(),
+        Condition::AttributeNotExists(condition) => resolve(item, &condition.path)?.is_none(),
+        Condition::AttributeType(condition) => resolve(item, &condition.path)?
+            .is_some_and(|value| Type::of(value) == condition.attribute_type),
+        Condition::BeginsWith(BeginsWith { path, substr }) => {
+            match (resolve(item, path)?, value(substr)?) {
+                (
+                    Some(Value::Scalar(Scalar::String(string))),
+                    Value::Scalar(Scalar::String(prefix)),
+                ) => string.starts_with(prefix.as_str()),
+                (
+                    Some(Value::Scalar(Scalar::Binary(binary))),
+                    Value::Scalar(Scalar::Binary(prefix)),
+                ) => binary.starts_with(prefix),
+                _ => false,
+            }
+        }
+        Condition::Contains(Contains { path, operand }) => {
+            match (resolve(item, path)?, value(operand)?) {
+                (
+                    Some(Value::Scalar(Scalar::String(string))),
+                    Value::Scalar(Scalar::String(sub)),
+                ) => string.contains(sub.as_str()),
+                (Some(Value::Set(Set::StringSet(set))), Value::Scalar(Scalar::String(string))) => {
+                    set.0.contains(string)
+                }
+                (Some(Value::Set(Set::NumSet(set))), Value::Scalar(Scalar::Num(num))) => {
+                    set.0.iter().any(|element| num_eq(element, num))
+                }
+                (Some(Value::Set(Set::BinarySet(set))), Value::Scalar(Scalar::Binary(binary))) => {
+                    set.0.contains(binary)
+                }
+                (Some(Value::List(list)), value) => {
+                    list.list.iter().any(|element| equal(element, value))
+                }
+                _ => false,
+            }
+        }
+        Condition::Between(Between { op, lower, upper }) => {
+            match (
+                operand(item, op)?,
+                operand(item, lower)?,
+                operand(item, upper)?,
+            ) {
+                (Some(op), Some(lower), Some(upper)) => {
+                    compare(&lower, &op).is_some_and(Ordering::is_le)
+                        && compare(&op, &upper).is_some_and(Ordering::is_le)
+                }
+                _ => false,
+            }
+        }
+        Condition::In(In { op, items }) => {
+            let op = operand(item, op)?;
+            let items = items
+                .iter()
+                .map(|operand_item| operand(item, operand_item))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            op.is_some_and(|op| items.iter().flatten().any(|item| equal(&op, item)))
+        }
+        Condition::Comparison(Comparison { left, cmp, right }) => compare_with(
+            *cmp,
+            operand(item, left)?.as_deref(),
+            operand(item, right)?.as_deref(),
+        ),
+        Condition::Not(Not { condition }) => !evaluate(condition, item)?,
+        // Both sides are evaluated so an unresolved reference is always an
+        // error, rather than only when it's reached.
+        Condition::And(And { left, right }) => {
+            let left = evaluate(left, item)?;
+            evaluate(right, item)? && left
+        }
+        Condition::Or(Or { left, right }) => {
+            let left = evaluate(left, item)?;
+            evaluate(right, item)? || left
+        }
+        Condition::Parenthetical(Parenthetical { condition }) => evaluate(condition, item)?,
+    })
+}
+
+pub(super) fn explain(condition: &Condition, item: &Map) -> Result<Explanation, EvalError> {
+    let (result, values, children) = match condition {
+        Condition::And(_) | Condition::Or(_) => {
+            let mut operands = Vec::new();
+            flatten(condition, &mut operands);
+            let children = operands
+                .into_iter()
+                .map(|operand| explain(operand, item))
+                .collect::<Result<Vec<_>, _>>()?;
+            let result = if matches!(condition, Condition::And(_)) {
+                children.iter().all(Explanation::result)
+            } else {
+                children.iter().any(Explanation::result)
+            };
+
+            (result, Vec::new(), children)
+        }
+        Condition::Not(Not { condition: inner }) => {
+            let child = explain(inner, item)?;
+
+            (!child.result, Vec::new(), vec![child])
+        }
+        Condition::Parenthetical(Parenthetical { condition }) => return explain(condition, item),
+        _ => {
+            let mut paths = Paths::default();
+            paths.visit_condition(condition);
+            let values = paths
+                .0
+                .into_iter()
+                .map(|path| {
+                    let value = resolve(item, &path)?.cloned();
+
+                    Ok((path, value))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            (evaluate(condition, item)?, values, Vec::new())
+        }
+    };
+
+    Ok(Explanation {
+        condition: condition.clone(),
+        result,
+        values,
+        children,
+    })
+}
+
+/// Collects the operands of a chain of the same `AND` or `OR`.
+fn flatten<'a>(condition: &'a Condition, operands: &mut Vec<&'a Condition>) {
+    match condition {
+        Condition::And(And { left, right }) | Condition::Or(Or { left, right }) => {
+            for side in [left, right] {
+                if core::mem::discriminant(&**side) == core::mem::discriminant(condition) {
+                    flatten(side, operands);
+                } else {
+                    operands.push(side);
+                }
+            }
+        }
+        _ => operands.push(condition),
+    }
+}
+
+/// The distinct paths used by a condition, in order.
+#[derive(Default)]
+struct Paths(Vec<Path>);
+
+impl Visit for Paths {
+    fn visit_path(&mut self, path: &Path) {
+        if !self.0.contains(path) {
+            self.0.push(path.clone());
+        }
+    }
+}
+
+/// Finds the value at the path in the item, if there is one.
+pub(crate) fn resolve<'a>(item: &'a Map, path: &Path) -> Result<Option<&'a Value>, EvalError> {
+    let mut map = item;
+    let mut found: Option<&Value> = None;
+    for element in &path.elements {
+        let (name, indexes) = match element {
+            Element::Name(name) => (name, &[][..]),
+            Element::IndexedField(IndexedField {
+                name: FieldName::Name(name),
+                indexes,
+            }) => (name, &indexes[..]),
+            Element::NameRef(name)
+            | Element::IndexedField(IndexedField {
+                name: FieldName::NameRef(name),
+                ..
+            }) => return Err(EvalError::UnresolvedName(name.clone())),
+        };
+
+        if found.is_some() {
+            let Some(Value::Map(inner)) = found else {
+                return Ok(None);
+            };
+
+            map = inner;
+        }
+
+        found = map.map.get(name);
+        for &index in indexes {
+            found = match found {
+                Some(Value::List(list)) => list.list.get(index),
+                _ => None,
+            };
+        }
+
+        if found.is_none() {
+            return Ok(None);
+        }
+    }
+
+    Ok(found)
+}
+
+/// The value, if it's not a reference.
+pub(crate) fn value(value: &ValueOrRef) -> Result<&Value, EvalError> {
+    match value {
+        ValueOrRef::Value(value) => Ok(value),
+        ValueOrRef::Ref(value) => Err(EvalError::UnresolvedValue(value.clone())),
+    }
+}
+
+/// The value of an operand, or `None` if it refers to an attribute the item
+/// doesn't have.
+fn operand<'a>(item: &'a Map, operand: &'a Operand) -> Result<Option<Cow<'a, Value>>, EvalError> {
+    Ok(match &operand.op {
+        OperandType::Path(path) => resolve(item, path)?.map(Cow::Borrowed),
+        OperandType::Scalar(scalar) => Some(Cow::Borrowed(value(scalar)?)),
+        OperandType::Size(Size { path }) => resolve(item, path)?.and_then(size).map(Cow::Owned),
+        OperandType::Condition(condition) => {
+            Some(Cow::Owned(Value::new_bool(evaluate(condition, item)?)))
+        }
+    })
+}
+
+/// The result of the `size()` function for a value, if it has a size.
+fn size(value: &Value) -> Option<Value> {
+    let size = match value {
+        // DynamoDB measures strings in UTF-8 bytes.
+        Value::Scalar(Scalar::String(string)) => string.len(),
+        Value::Scalar(Scalar::Binary(binary)) => binary.len(),
+        Value::Set(Set::StringSet(set)) => set.0.len(),
+        Value::Set(Set::NumSet(set)) => set.0.len(),
+        Value::Set(Set::BinarySet(set)) => set.0.len(),
+        Value::Map(map) => map.map.len(),
+        Value::List(list) => list.list.len(),
+        Value::Scalar(Scalar::Num(_) | Scalar::Bool(_) | Scalar::Null) => return None,
+    };
+
+    Some(Num::new(size).into())
+}
+
+fn compare_with(cmp: Comparator, left: Option<&Value>, right: Option<&Value>) -> bool {
+    let (Some(left), Some(right)) = (left, right) else {
+        return cmp == Comparator::Ne;
+    };
+
+    match cmp {
+        Comparator::Eq => equal(left, right),
+        Comparator::Ne => !equal(left, right),
+        Comparator::Lt => compare(left, right).is_some_and(Ordering::is_lt),
+        Comparator::Le => compare(left, right).is_some_and(Ordering::is_le),
+        Comparator::Gt => compare(left, right).is_some_and(Ordering::is_gt),
+        Comparator::Ge => compare(left, right).is_some_and(Ordering::is_ge),
+    }
+}
+
+/// Orders two values of the same type, for the types DynamoDB can order:
+/// numbers, strings, and binary.
+pub(crate) fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Scalar(Scalar::Num(left)), Value::Scalar(Scalar::Num(right))) => {
+            decimal::compare(left, right)
+        }
+        (Value::Scalar(Scalar::String(left)), Value::Scalar(Scalar::String(right))) => {
+            Some(left.cmp(right))
+        }
+        (Value::Scalar(Scalar::Binary(left)), Value::Scalar(Scalar::Binary(right))) => {
+            Some(left.cmp(right))
+        }
+        _ => None,
+    }
+}
+
+/// Whether two values are equal, where numbers are compared by value rather
+/// than how they're written. E.g., `1.0` equals `1`.
+pub(crate) fn equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Scalar(Scalar::Num(left)), Value::Scalar(Scalar::Num(right))) => {
+            num_eq(left, right)
+        }
+        (Value::Set(Set::NumSet(left)), Value::Set(Set::NumSet(right))) => {
+            left.0.len() == right.0.len()
+                && left
+                    .0
+                    .iter()
+                    .all(|left| right.0.iter().any(|right| num_eq(left, right)))
+        }
+        (Value::Map(left), Value::Map(right)) => {
+            left.map.len() == right.map.len()
+                && left
+                    .map
+                    .iter()
+                    .all(|(name, left)| right.map.get(name).is_some_and(|right| equal(left, right)))
+        }
+        (Value::List(left), Value::List(right)) => {
+            left.list.len() == right.list.len()
+                && left
+                    .list
+                    .iter()
+                    .zip(&right.list)
+                    .all(|(left, right)| equal(left, right))
+        }
+        _ => left == right,
+    }
+}
+
+fn num_eq(left: &Num, right: &Num) -> bool {
+    left == right || decimal::compare(left, right) == Some(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        condition::{attribute_type::Type, Condition},
+        path::NameRef,
+        value::{Map, NumSet, Ref, StringSet, Value},
+        Num, Path,
+    };
+
+    use super::EvalError;
+
+    fn item() -> Map {
+        Map::from([
+            ("name", Value::new_string("Jane")),
+            ("age", Value::new_num(40)),
+            ("tags", StringSet::from(["a", "b"]).into()),
+            ("scores", NumSet::from([1, 2]).into()),
+            (
+                "address",
+                Map::from([(
+                    "lines",
+                    Value::new_list([Value::new_string("1 Main St"), Value::new_null()]),
+                )])
+                .into(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn evaluate() {
+        let item = item();
+        let path = |path: &str| path.parse::<Path>().unwrap();
+        let eval = |condition: Condition| condition.evaluate(&item).unwrap();
+
+        assert!(eval(path("age").equal(Num::new_lower_exp(40.0))));
+        assert!(eval(path("age").greater_than(Num::new(3))));
+        assert!(!eval(path("age").greater_than("3")), "different types");
+        assert!(eval(path("age").not_equal("40")), "different types");
+        assert!(!eval(path("missing").equal(Num::new(1))));
+        assert!(eval(path("missing").not_equal(Num::new(1))));
+        assert!(!eval(path("missing").less_than(Num::new(1)).not().not()));
+        assert!(eval(path("name").between("A", "K")));
+        assert!(eval(path("name").in_(["John", "Jane"])));
+        assert!(eval(path("name").begins_with("Ja")));
+        assert!(eval(path("name").contains("an")));
+        assert!(eval(path("tags").contains("b")));
+        assert!(eval(path("scores").contains(Num::new(2))));
+        assert!(eval(path("address.lines").contains("1 Main St")));
+        assert!(eval(path("address.lines[1]").attribute_type(Type::Null)));
+        assert!(eval(path("address.lines[2]").attribute_not_exists()));
+        assert!(eval(path("address.lines[0].x").attribute_not_exists()));
+        assert!(eval(
+            path("address.lines[0]").size().equal(Num::new(9)).into()
+        ));
+        assert!(eval(
+            Condition::from(path("tags").size().less_than(path("age").size())).not()
+        ));
+        assert!(eval(
+            path("age")
+                .attribute_exists()
+                .and(path("missing").attribute_exists())
+                .parenthesize()
+                .or(path("tags").size().equal(Num::new(2)))
+        ));
+
+        assert_eq!(
+            Err(EvalError::UnresolvedName(NameRef::from("n"))),
+            Path::from(NameRef::from("n"))
+                .attribute_exists()
+                .evaluate(&item)
+        );
+        assert_eq!(
+            Err(EvalError::UnresolvedValue(Ref::new("v"))),
+            path("age")
+                .attribute_exists()
+                .or(path("age").equal(Ref::new("v")))
+                .evaluate(&item)
+        );
+    }
+
+    #[test]
+    fn explain() {
+        let condition = Path::new_name("age")
+            .greater_than(Num::new(50))
+            .and(Path::new_name("name").attribute_exists())
+            .and(
+                Path::new_name("nickname")
+                    .attribute_exists()
+                    .or(Path::new_name("tags")
+                        .size()
+                        .between(Num::new(1), Num::new(5)))
+                    .parenthesize(),
+            )
+            .and(Path::new_name("deleted").attribute_exists().not());
+
+        let explanation = condition.explain(&item()).unwrap();
+        assert!(!explanation.result());
+        assert_eq!(4, explanation.children().len());
+        assert_eq!(
+            "false: age > 50 AND attribute_exists(name) \
+                AND (attribute_exists(nickname) OR size(tags) BETWEEN 1 AND 5) \
+                AND NOT attribute_exists(deleted)\n  \
+              false: age > 50\n    \
+                age: 40\n  \
+              true: attribute_exists(name)\n    \
+                name: \"Jane\"\n  \
+              true: attribute_exists(nickname) OR size(tags) BETWEEN 1 AND 5\n    \
+                false: attribute_exists(nickname)\n      \
+                  nickname: missing\n    \
+                true: size(tags) BETWEEN 1 AND 5\n      \
+                  tags: [\"a\", \"b\"]\n  \
+              true: NOT attribute_exists(deleted)\n    \
+                false: attribute_exists(deleted)\n      \
+                  deleted: missing\n",
+            explanation.to_string()
+        );
+    }
+
+    #[test]
+    fn precedence() {
+        let item = Map::from([("a", Value::new_num(1))]);
+        let a = || Path::new_name("a").equal(Num::new(1));
+        let b = || Path::new_name("b").attribute_exists();
+        let c = || Path::new_name("c").attribute_exists();
+
+        // Built as `(a OR b) AND c`, but written (and so evaluated by
+        // DynamoDB) as `a OR (b AND c)`.
+        let condition = a().or(b()).and(c());
+        assert_eq!(
+            "a = 1 OR attribute_exists(b) AND attribute_exists(c)",
+            condition.to_string()
+        );
+        assert_eq!(Ok(true), condition.evaluate(&item));
+        assert_eq!(Ok(true), condition.clone().simplify().evaluate(&item));
+        assert_eq!(
+            Ok(false),
+            a().or(b()).parenthesize().and(c()).evaluate(&item)
+        );
+
+        // Built as `NOT (a AND b)`, but written as `(NOT a) AND b`.
+        let condition = a().and(c()).not();
+        assert_eq!("NOT a = 1 AND attribute_exists(c)", condition.to_string());
+        assert_eq!(Ok(false), condition.evaluate(&item));
+        assert_eq!(Ok(true), a().and(c()).parenthesize().not().evaluate(&item));
+
+        let explanation = a().or(b()).and(c()).explain(&item).unwrap();
+        assert!(explanation.result());
+        assert_eq!(
+            "true: a = 1 OR attribute_exists(b) AND attribute_exists(c)\n  \
+              true: a = 1\n    \
+                a: 1\n  \
+              false: attribute_exists(b) AND attribute_exists(c)\n    \
+                false: attribute_exists(b)\n      \
+                  b: missing\n    \
+                false: attribute_exists(c)\n      \
+                  c: missing\n",
+            explanation.to_string()
+        );
+    }
+}
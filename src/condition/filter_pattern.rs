@@ -0,0 +1,777 @@
+//! Converting a [`Condition`] to [Lambda event filter patterns][1] for
+//! DynamoDB Streams.
+//!
+//! [1]: https://docs.aws.amazon.com/lambda/latest/dg/with-ddb-filtering.html
+
+use core::fmt;
+use std::{collections::BTreeMap, error::Error};
+
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Map as JsonMap, Number, Value as Json};
+
+use crate::{
+    operand::{Operand, OperandType},
+    path::{Element, Path},
+    value::{Num, Scalar, Value},
+};
+
+use super::{
+    attribute_type::Type,
+    eval::{self, EvalError},
+    simplify, AttributeExists, AttributeNotExists, AttributeType, BeginsWith, Between, Comparator,
+    Comparison, Condition, In,
+};
+
+/// The part of a DynamoDB Streams record a filter pattern applies to.
+///
+/// See also: [`Condition::to_filter_patterns`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StreamImage {
+    /// The item as it was after it was modified.
+    NewImage,
+    /// The item as it was before it was modified.
+    OldImage,
+    /// The key attributes of the modified item.
+    Keys,
+}
+
+impl StreamImage {
+    /// The name of the field in the stream record.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NewImage => "NewImage",
+            Self::OldImage => "OldImage",
+            Self::Keys => "Keys",
+        }
+    }
+}
+
+impl fmt::Display for StreamImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Why a [`Condition`] couldn't be converted to filter patterns.
+///
+/// See also: [`Condition::to_filter_patterns`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterPatternError {
+    /// The condition, or part of it, has no filter pattern equivalent.
+    Unsupported(Condition),
+
+    /// The condition requires an attribute to exist, but filter patterns can
+    /// only check for an attribute of a specific type. Combine it (with `AND`)
+    /// with an [`attribute_type`] condition, or a comparison, for the same
+    /// attribute.
+    ///
+    /// [`attribute_type`]: crate::Path::attribute_type
+    UnknownType(Path),
+
+    /// More than one condition for the same attribute that can't be combined
+    /// into a single filter pattern.
+    Conflict(Path),
+
+    /// The condition uses an expression attribute name or value, which can't
+    /// be resolved.
+    Unresolved(EvalError),
+}
+
+impl fmt::Display for FilterPatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported(condition) => {
+                write!(f, "no filter pattern equivalent for: {condition}")
+            }
+            Self::UnknownType(path) => write!(
+                f,
+                "the type of `{path}` is needed to check that it exists; \
+                combine it with `attribute_type`"
+            ),
+            Self::Conflict(path) => {
+                write!(f, "conditions for `{path}` can't be combined")
+            }
+            Self::Unresolved(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for FilterPatternError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unresolved(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<EvalError> for FilterPatternError {
+    fn from(err: EvalError) -> Self {
+        Self::Unresolved(err)
+    }
+}
+
+/// See [`Condition::to_filter_patterns`].
+pub(super) fn filter_patterns(
+    condition: &Condition,
+    image: StreamImage,
+) -> Result<Vec<Json>, FilterPatternError> {
+    let mut patterns = Vec::new();
+    for term in simplify::dnf_terms(condition.clone()) {
+        let alternatives = term
+            .into_iter()
+            .map(|(negated, condition)| constraints(negated, condition))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for constraints in product(alternatives) {
+            // Combinations that can't be met, like `a` both being and not
+            // being a string, don't need a pattern.
+            let Some(pattern) = pattern(constraints)? else {
+                continue;
+            };
+
+            let pattern = json!({ "dynamodb": { image.as_str(): pattern } });
+            if !patterns.contains(&pattern) {
+                patterns.push(pattern);
+            }
+        }
+    }
+
+    // No patterns at all would match every record.
+    if patterns.is_empty() {
+        return Err(FilterPatternError::Unsupported(condition.clone()));
+    }
+
+    Ok(patterns)
+}
+
+/// What a condition requires of the value of one type of an attribute. E.g.,
+/// `a = "x"` requires `{"a": {"S": ["x"]}}`.
+#[derive(Debug, Clone)]
+struct Constraint {
+    path: Path,
+    names: Vec<String>,
+    /// `None` only for `attribute_exists`, which doesn't care about the type.
+    type_key: Option<&'static str>,
+    matchers: Vec<Json>,
+}
+
+/// The alternatives (to `OR` together) of constraints (to `AND` together) for
+/// a condition that isn't `NOT`, `AND`, `OR`, or parentheses.
+fn constraints(
+    negated: bool,
+    condition: Condition,
+) -> Result<Vec<Vec<Constraint>>, FilterPatternError> {
+    let unsupported = || {
+        FilterPatternError::Unsupported(if negated {
+            condition.clone().not()
+        } else {
+            condition.clone()
+        })
+    };
+    let constraint = |path: &Path,
+                      type_key: Option<&'static str>,
+                      matchers: Vec<Json>|
+     -> Result<Constraint, FilterPatternError> {
+        Ok(Constraint {
+            path: path.clone(),
+            names: names(path)?,
+            type_key,
+            matchers,
+        })
+    };
+    let existence = |path: &Path, exists: bool| -> Result<_, FilterPatternError> {
+        if exists {
+            return Ok(vec![vec![constraint(
+                path,
+                None,
+                vec![json!({ "exists": true })],
+            )?]]);
+        }
+
+        // Filter patterns can only check leaves, so an attribute that doesn't
+        // exist is one that doesn't exist as any type.
+        Ok(vec![TYPES
+            .iter()
+            .map(|ty| constraint(path, Some(ty.as_str()), vec![json!({ "exists": false })]))
+            .collect::<Result<_, _>>()?])
+    };
+    // An attribute isn't any of the values if it's one of their types with a
+    // different value, or it isn't any of their types (including not
+    // existing), as with `attribute_not_exists`.
+    let none_of = |path: &Path, by_type: BTreeMap<&'static str, Vec<Json>>| {
+        let mut alternatives = Vec::new();
+        for (type_key, matchers) in &by_type {
+            let matcher = match *type_key {
+                "S" | "N" | "B" => json!({ "anything-but": matchers }),
+                "BOOL" => match matchers.as_slice() {
+                    [Json::Bool(b), rest @ ..] if rest.iter().all(|other| other == b) => {
+                        Json::Bool(!b)
+                    }
+                    // Both `true` and `false`.
+                    _ => continue,
+                },
+                // There's only one `NULL`.
+                _ => continue,
+            };
+
+            alternatives.push(vec![constraint(path, Some(type_key), vec![matcher])?]);
+        }
+
+        alternatives.push(
+            by_type
+                .keys()
+                .map(|type_key| constraint(path, Some(type_key), vec![json!({ "exists": false })]))
+                .collect::<Result<_, _>>()?,
+        );
+
+        Ok(alternatives)
+    };
+
+    match &condition {
+        Condition::AttributeExists(AttributeExists { path }) => existence(path, !negated),
+        Condition::AttributeNotExists(AttributeNotExists { path }) => existence(path, negated),
+        Condition::AttributeType(AttributeType {
+            path,
+            attribute_type,
+        }) => Ok(vec![vec![constraint(
+            path,
+            Some(attribute_type.as_str()),
+            vec![json!({ "exists": !negated })],
+        )?]]),
+        Condition::BeginsWith(BeginsWith { path, substr }) if !negated => {
+            match eval::value(substr)? {
+                Value::Scalar(Scalar::String(prefix)) => Ok(vec![vec![constraint(
+                    path,
+                    Some("S"),
+                    vec![json!({ "prefix": prefix })],
+                )?]]),
+                _ => Err(unsupported()),
+            }
+        }
+        Condition::Between(Between { op, lower, upper }) if !negated => {
+            let (Some(path), Some(lower), Some(upper)) = (path(op), number(lower)?, number(upper)?)
+            else {
+                return Err(unsupported());
+            };
+
+            Ok(vec![vec![constraint(
+                path,
+                Some("N"),
+                vec![json!({ "numeric": [">=", lower, "<=", upper] })],
+            )?]])
+        }
+        Condition::Comparison(Comparison { left, cmp, right }) => {
+            // Negated `=` and `<>` are inverted when converting to disjunctive
+            // normal form, so only ordered comparisons are ever negated here.
+            let (path, cmp, value) = match (path(left), path(right)) {
+                (Some(path), None) => (path, *cmp, right),
+                (None, Some(path)) => (path, flip(*cmp), left),
+                _ => return Err(unsupported()),
+            };
+
+            let (type_key, matcher) = match cmp {
+                Comparator::Eq => match scalar(value)? {
+                    Some(scalar) => scalar,
+                    None => return Err(unsupported()),
+                },
+                Comparator::Ne => match scalar(value)? {
+                    Some((type_key, matcher)) => {
+                        return none_of(path, BTreeMap::from([(type_key, vec![matcher])]))
+                    }
+                    None => return Err(unsupported()),
+                },
+                Comparator::Lt | Comparator::Le | Comparator::Gt | Comparator::Ge => {
+                    let Some(n) = number(value)? else {
+                        return Err(unsupported());
+                    };
+
+                    if negated {
+                        // `NOT a < 5` is also true when `a` is missing or
+                        // isn't a number.
+                        let inverse = simplify::inverse(cmp);
+                        return Ok(vec![
+                            vec![constraint(
+                                path,
+                                Some("N"),
+                                vec![json!({ "numeric": [inverse.as_str(), n] })],
+                            )?],
+                            vec![constraint(
+                                path,
+                                Some("N"),
+                                vec![json!({ "exists": false })],
+                            )?],
+                        ]);
+                    }
+
+                    ("N", json!({ "numeric": [cmp.as_str(), n] }))
+                }
+            };
+
+            Ok(vec![vec![constraint(path, Some(type_key), vec![matcher])?]])
+        }
+        Condition::In(In { op, items }) => {
+            let Some(path) = path(op) else {
+                return Err(unsupported());
+            };
+
+            let mut by_type = BTreeMap::<_, Vec<_>>::new();
+            for item in items {
+                let Some((type_key, matcher)) = scalar(item)? else {
+                    return Err(unsupported());
+                };
+
+                by_type.entry(type_key).or_default().push(matcher);
+            }
+
+            if negated {
+                return none_of(path, by_type);
+            }
+
+            by_type
+                .into_iter()
+                .map(|(type_key, matchers)| Ok(vec![constraint(path, Some(type_key), matchers)?]))
+                .collect()
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+/// Every data type an attribute can have.
+const TYPES: [Type; 10] = [
+    Type::String,
+    Type::StringSet,
+    Type::Number,
+    Type::NumberSet,
+    Type::Binary,
+    Type::BinarySet,
+    Type::Boolean,
+    Type::Null,
+    Type::List,
+    Type::Map,
+];
+
+/// Combines each of the alternatives of the first with each of the
+/// alternatives of the rest.
+fn product(alternatives: Vec<Vec<Vec<Constraint>>>) -> Vec<Vec<Constraint>> {
+    alternatives
+        .into_iter()
+        .fold(vec![vec![]], |acc, alternatives| {
+            acc.iter()
+                .flat_map(|left| {
+                    alternatives.iter().map(move |right| {
+                        let mut combined = left.clone();
+                        combined.extend(right.iter().cloned());
+                        combined
+                    })
+                })
+                .collect()
+        })
+}
+
+/// Builds the pattern for the image from constraints that must all be met.
+/// `None` if they can't all be met.
+fn pattern(constraints: Vec<Constraint>) -> Result<Option<Json>, FilterPatternError> {
+    let mut typed = BTreeMap::<_, (Path, Vec<Vec<Json>>)>::new();
+    let mut untyped = Vec::new();
+    for constraint in constraints {
+        match constraint.type_key {
+            Some(type_key) => {
+                typed
+                    .entry((constraint.names, type_key))
+                    .or_insert_with(|| (constraint.path, Vec::new()))
+                    .1
+                    .push(constraint.matchers);
+            }
+            None => untyped.push(constraint),
+        }
+    }
+
+    // `attribute_exists` is implied by any other constraint for the attribute
+    // (or one nested in it), other than that it doesn't exist.
+    for constraint in untyped {
+        let implied = typed.iter().any(|((names, _), (_, matchers))| {
+            names.starts_with(&constraint.names)
+                && !matchers
+                    .iter()
+                    .all(|matchers| matchers == &[json!({ "exists": false })])
+        });
+
+        if !implied {
+            return Err(FilterPatternError::UnknownType(constraint.path));
+        }
+    }
+
+    let mut pattern = Json::Object(JsonMap::new());
+    for ((names, type_key), (path, matchers)) in typed {
+        let Some(matchers) = combine(&path, matchers)? else {
+            return Ok(None);
+        };
+
+        let (last, parents) = names.split_last().expect("paths have at least one element");
+        let mut object = &mut pattern;
+        for name in parents {
+            let map = &mut object[name]["M"];
+            if !map.is_object() {
+                // Replaces `attribute_type(a, M)`, which this implies.
+                *map = Json::Object(JsonMap::new());
+            }
+
+            object = map;
+        }
+
+        let slot = &mut object[last][type_key];
+        if !slot.is_object() {
+            *slot = Json::Array(matchers);
+        }
+    }
+
+    Ok(Some(pattern))
+}
+
+/// Combines the matchers from multiple constraints for the same type of the
+/// same attribute, which must all be met. `None` if they can't all be met.
+fn combine(
+    path: &Path,
+    constraints: Vec<Vec<Json>>,
+) -> Result<Option<Vec<Json>>, FilterPatternError> {
+    // The attribute not existing as the type contradicts anything else.
+    let not_exists = [json!({ "exists": false })];
+    if constraints.contains(&not_exists.to_vec())
+        && constraints.iter().any(|matchers| matchers != &not_exists)
+    {
+        return Ok(None);
+    }
+
+    // That the attribute exists as the type is implied by anything else.
+    let exists = [json!({ "exists": true })];
+    let only_exists = constraints.iter().all(|matchers| matchers == &exists);
+    let mut unique = Vec::with_capacity(constraints.len());
+    for matchers in constraints {
+        if (only_exists || matchers != exists) && !unique.contains(&matchers) {
+            unique.push(matchers);
+        }
+    }
+    let mut constraints = unique;
+
+    if constraints.len() == 1 {
+        return Ok(constraints.pop());
+    }
+
+    // Numeric ranges can be combined, with at most one lower and one upper
+    // bound.
+    let mut lower = None;
+    let mut upper = None;
+    for matchers in &constraints {
+        let [matcher] = matchers.as_slice() else {
+            return Err(FilterPatternError::Conflict(path.clone()));
+        };
+
+        let Some([op, n]) = matcher
+            .get("numeric")
+            .and_then(Json::as_array)
+            .map(Vec::as_slice)
+        else {
+            return Err(FilterPatternError::Conflict(path.clone()));
+        };
+
+        let bound = match op.as_str() {
+            Some(">" | ">=") => &mut lower,
+            Some("<" | "<=") => &mut upper,
+            _ => return Err(FilterPatternError::Conflict(path.clone())),
+        };
+
+        if bound.replace([op.clone(), n.clone()]).is_some() {
+            return Err(FilterPatternError::Conflict(path.clone()));
+        }
+    }
+
+    Ok(Some(vec![
+        json!({ "numeric": lower.into_iter().chain(upper).flatten().collect::<Vec<_>>() }),
+    ]))
+}
+
+/// The names of the attributes in the path, from the top-level attribute.
+fn names(path: &Path) -> Result<Vec<String>, FilterPatternError> {
+    path.elements
+        .iter()
+        .map(|element| match element {
+            Element::Name(name) => Ok(name.name.clone()),
+            // Filter patterns match any element of a list.
+            Element::IndexedField(_) => Err(FilterPatternError::Unsupported(
+                path.clone().attribute_exists(),
+            )),
+            Element::NameRef(name) => Err(EvalError::UnresolvedName(name.clone()).into()),
+        })
+        .collect()
+}
+
+/// The path, if the operand is one.
+fn path(operand: &Operand) -> Option<&Path> {
+    match &operand.op {
+        OperandType::Path(path) => Some(path),
+        _ => None,
+    }
+}
+
+/// The type key and matcher for a scalar value, as it appears in a stream
+/// record.
+fn scalar(operand: &Operand) -> Result<Option<(&'static str, Json)>, FilterPatternError> {
+    let OperandType::Scalar(value) = &operand.op else {
+        return Ok(None);
+    };
+
+    let Value::Scalar(scalar) = eval::value(value)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(match scalar {
+        Scalar::String(s) => ("S", json!(s)),
+        // Numbers are strings in stream records, so these must match exactly.
+        Scalar::Num(Num { n }) => ("N", json!(n)),
+        Scalar::Bool(b) => ("BOOL", json!(b)),
+        Scalar::Binary(b) => ("B", json!(general_purpose::STANDARD.encode(b))),
+        Scalar::Null => ("NULL", json!(true)),
+    }))
+}
+
+/// The number for a numeric matcher, if the operand is one.
+fn number(operand: &Operand) -> Result<Option<Number>, FilterPatternError> {
+    let OperandType::Scalar(value) = &operand.op else {
+        return Ok(None);
+    };
+
+    let Value::Scalar(Scalar::Num(Num { n })) = eval::value(value)? else {
+        return Ok(None);
+    };
+
+    Ok(serde_json::from_str(n)
+        .ok()
+        .or_else(|| n.parse().ok().and_then(Number::from_f64)))
+}
+
+/// The comparator for the operands in the other order. E.g., `1 < a` is
+/// `a > 1`.
+fn flip(cmp: Comparator) -> Comparator {
+    match cmp {
+        Comparator::Lt => Comparator::Gt,
+        Comparator::Le => Comparator::Ge,
+        Comparator::Gt => Comparator::Lt,
+        Comparator::Ge => Comparator::Le,
+        cmp => cmp,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use crate::{
+        condition::{attribute_type::Type, comparison::less_than},
+        value::Scalar,
+        Map, Num, Path,
+    };
+
+    use super::{FilterPatternError, StreamImage};
+
+    #[test]
+    fn filter_patterns() {
+        let [status, age, name] = ["status", "age", "name"].map(Path::new_name);
+
+        assert_eq!(
+            vec![json!({ "dynamodb": { "NewImage": { "status": { "S": ["ACTIVE"] } } } })],
+            status
+                .clone()
+                .equal("ACTIVE")
+                .to_filter_patterns(StreamImage::NewImage)
+                .unwrap()
+        );
+
+        assert_eq!(
+            vec![json!({ "dynamodb": { "OldImage": {
+                "status": { "S": ["A", "B"] },
+                "age": { "N": [{ "numeric": [">", 17, "<=", 65] }] },
+                "name": { "S": [{ "prefix": "Jo" }] },
+            } } })],
+            status
+                .clone()
+                .in_(["A", "B"])
+                .and(less_than(Num::new(17), age.clone()))
+                .and(age.clone().less_than_or_equal(Num::new(65)))
+                .and(name.clone().begins_with("Jo"))
+                .to_filter_patterns(StreamImage::OldImage)
+                .unwrap()
+        );
+
+        // `OR` becomes separate patterns, as does `IN` with mixed types.
+        assert_eq!(
+            vec![
+                json!({ "dynamodb": { "Keys": { "pk": { "N": ["1"] } } } }),
+                json!({ "dynamodb": { "Keys": { "pk": { "S": ["a"] } } } }),
+                json!({ "dynamodb": { "Keys": { "sk": { "N": [{ "numeric": [">=", 1, "<=", 2] }] } } } }),
+            ],
+            Path::new_name("pk")
+                .in_([Scalar::from("a"), Num::new(1).into()])
+                .or(Path::new_name("sk").between(Num::new(1), Num::new(2)))
+                .to_filter_patterns(StreamImage::Keys)
+                .unwrap()
+        );
+
+        // Like in DynamoDB, an attribute that's missing or of another type
+        // isn't equal to the value, or in the list.
+        assert_eq!(
+            vec![
+                json!({ "dynamodb": { "NewImage": {
+                    "address": { "M": { "city": {
+                        "S": [{ "anything-but": ["Boston", "Austin"] }],
+                    } } },
+                    "name": { "S": [{ "exists": true }] },
+                } } }),
+                json!({ "dynamodb": { "NewImage": {
+                    "address": { "M": { "city": { "S": [{ "exists": false }] } } },
+                    "name": { "S": [{ "exists": true }] },
+                } } }),
+            ],
+            "address.city"
+                .parse::<Path>()
+                .unwrap()
+                .in_(["Boston", "Austin"])
+                .not()
+                .and(name.clone().attribute_exists())
+                .and(name.clone().attribute_type(Type::String))
+                .to_filter_patterns(StreamImage::NewImage)
+                .unwrap()
+        );
+        assert_eq!(
+            vec![
+                json!({ "dynamodb": { "NewImage": { "deleted": { "BOOL": [false] } } } }),
+                json!({ "dynamodb": { "NewImage": { "deleted": { "BOOL": [{ "exists": false }] } } } }),
+            ],
+            Path::new_name("deleted")
+                .not_equal(true)
+                .to_filter_patterns(StreamImage::NewImage)
+                .unwrap()
+        );
+        assert_eq!(
+            vec![
+                json!({ "dynamodb": { "NewImage": { "a": { "N": [{ "anything-but": ["1"] }] } } } }),
+                json!({ "dynamodb": { "NewImage": { "a": { "S": [{ "anything-but": ["x"] }] } } } }),
+                json!({ "dynamodb": { "NewImage": { "a": {
+                    "N": [{ "exists": false }],
+                    "S": [{ "exists": false }],
+                } } } }),
+            ],
+            Path::new_name("a")
+                .in_([Scalar::from("x"), Num::new(1).into()])
+                .not()
+                .to_filter_patterns(StreamImage::NewImage)
+                .unwrap()
+        );
+
+        // `NOT a < 5` is true for an item without `a`, so it's not `a >= 5`.
+        let not_less = Path::new_name("a").less_than(Num::new(5)).not();
+        assert!(not_less.evaluate(&Map::default()).unwrap());
+        assert_eq!(
+            vec![
+                json!({ "dynamodb": { "NewImage": { "a": { "N": [{ "numeric": [">=", 5] }] } } } }),
+                json!({ "dynamodb": { "NewImage": { "a": { "N": [{ "exists": false }] } } } }),
+            ],
+            not_less.to_filter_patterns(StreamImage::NewImage).unwrap()
+        );
+
+        // Combinations that can't be met are left out.
+        assert_eq!(
+            vec![
+                json!({ "dynamodb": { "NewImage": { "a": { "S": [{ "anything-but": ["x"] }] } } } })
+            ],
+            Path::new_name("a")
+                .attribute_type(Type::String)
+                .and(Path::new_name("a").not_equal("x"))
+                .to_filter_patterns(StreamImage::NewImage)
+                .unwrap()
+        );
+
+        // `attribute_exists` is implied by other conditions for the attribute.
+        assert_eq!(
+            vec![
+                json!({ "dynamodb": { "NewImage": { "address": { "M": { "city": { "S": ["Boston"] } } } } } })
+            ],
+            Path::new_name("address")
+                .attribute_exists()
+                .and("address.city".parse::<Path>().unwrap().equal("Boston"))
+                .to_filter_patterns(StreamImage::NewImage)
+                .unwrap()
+        );
+
+        let patterns = name
+            .clone()
+            .attribute_not_exists()
+            .to_filter_patterns(StreamImage::NewImage)
+            .unwrap();
+        assert_eq!(
+            json!([{ "exists": false }]),
+            patterns[0]["dynamodb"]["NewImage"]["name"]["NULL"]
+        );
+        assert_eq!(
+            10,
+            patterns[0]["dynamodb"]["NewImage"]["name"]
+                .as_object()
+                .unwrap()
+                .len()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let [a, b] = ["a", "b"].map(Path::new_name);
+
+        assert_eq!(
+            Err(FilterPatternError::UnknownType(a.clone())),
+            a.clone()
+                .attribute_exists()
+                .to_filter_patterns(StreamImage::NewImage)
+        );
+        assert_eq!(
+            Err(FilterPatternError::Conflict(a.clone())),
+            a.clone()
+                .equal("x")
+                .and(a.clone().begins_with("y"))
+                .to_filter_patterns(StreamImage::NewImage)
+        );
+        assert_eq!(
+            Err(FilterPatternError::Unsupported(a.clone().contains("x"))),
+            a.clone()
+                .contains("x")
+                .or(b.clone().equal("y"))
+                .to_filter_patterns(StreamImage::NewImage)
+        );
+        assert_eq!(
+            Err(FilterPatternError::Unsupported(
+                a.clone().begins_with("x").not()
+            )),
+            a.clone()
+                .begins_with("x")
+                .not()
+                .to_filter_patterns(StreamImage::NewImage)
+        );
+        // A condition that's never met has no patterns, which would match
+        // everything.
+        let never = a
+            .clone()
+            .attribute_type(Type::String)
+            .and(a.clone().attribute_type(Type::String).not());
+        assert_eq!(
+            Err(FilterPatternError::Unsupported(never.clone())),
+            never.to_filter_patterns(StreamImage::NewImage)
+        );
+        assert_eq!(
+            Err(FilterPatternError::Unsupported(
+                a.clone().less_than(b.clone())
+            )),
+            a.clone()
+                .less_than(b.clone())
+                .to_filter_patterns(StreamImage::NewImage)
+        );
+    }
+}
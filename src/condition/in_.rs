@@ -1,6 +1,9 @@
 use core::fmt::{self, Write};
 
-use crate::operand::Operand;
+use crate::{
+    indent::{Indented, INDENT},
+    operand::Operand,
+};
 
 /// A [DynamoDB `IN` condition][1].
 ///
@@ -9,6 +12,7 @@ use crate::operand::Operand;
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.Comparators
 /// [`Path::in_`]: crate::path::Path::in_
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct In {
     pub(crate) op: Operand,
     pub(crate) items: Vec<Operand>,
@@ -44,6 +48,16 @@ impl In {
             items: items.into_iter().map(Into::into).collect(),
         }
     }
+
+    /// The operand being checked.
+    pub fn operand(&self) -> &Operand {
+        &self.op
+    }
+
+    /// The values the operand is checked against.
+    pub fn items(&self) -> &[Operand] {
+        &self.items
+    }
 }
 
 impl fmt::Display for In {
@@ -51,6 +65,23 @@ impl fmt::Display for In {
         self.op.fmt(f)?;
         f.write_str(" IN (")?;
 
+        if f.alternate() {
+            let mut first = true;
+            self.items.iter().try_for_each(|item| {
+                if first {
+                    first = false;
+                } else {
+                    f.write_char(',')?;
+                }
+
+                f.write_char('\n')?;
+                f.write_str(INDENT)?;
+                write!(Indented::new(f), "{item:#}")
+            })?;
+
+            return f.write_str("\n)");
+        }
+
         let mut first = true;
         self.items.iter().try_for_each(|item| {
             if first {
@@ -6,10 +6,13 @@ mod begins_with;
 mod between;
 mod comparison;
 mod contains;
+pub(crate) mod eval;
+mod filter_pattern;
 mod in_;
 mod not;
 mod or;
 mod parenthetical;
+mod simplify;
 
 pub use and::And;
 pub use attribute_exists::AttributeExists;
@@ -22,12 +25,16 @@ pub use comparison::{
     Comparator, Comparison,
 };
 pub use contains::Contains;
+pub use eval::{EvalError, Explanation};
+pub use filter_pattern::{FilterPatternError, StreamImage};
 pub use in_::In;
 pub use not::Not;
 pub use or::Or;
 pub use parenthetical::Parenthetical;
 
-use core::{fmt, ops};
+use core::{fmt, mem, ops};
+
+use crate::{path::Path, value::Map};
 
 /// Represents a logical condition in a [DynamoDB expression][1].
 ///
@@ -36,6 +43,8 @@ use core::{fmt, ops};
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.Syntax
 /// [`Path`]: crate::path::Path
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Condition {
     AttributeExists(AttributeExists),
     AttributeNotExists(AttributeNotExists),
@@ -142,6 +151,328 @@ impl Condition {
     pub fn parenthesize(self) -> Self {
         Self::Parenthetical(self.into())
     }
+
+    /// Combines conditions with [`AND`][1], or returns `None` if there are no
+    /// conditions.
+    ///
+    /// The conditions are combined into a balanced tree, rather than a chain.
+    /// Any `OR` conditions are wrapped in parentheses so they're evaluated
+    /// as a unit.
+    ///
+    /// ```
+    /// use dynamodb_expression::{condition::Condition, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let condition = Condition::all([
+    ///     Path::new_name("a").greater_than(Num::new(1)),
+    ///     Path::new_name("b").less_than(Num::new(2)),
+    ///     Path::new_name("c")
+    ///         .attribute_exists()
+    ///         .or(Path::new_name("d").attribute_exists()),
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(
+    ///     "a > 1 AND b < 2 AND (attribute_exists(c) OR attribute_exists(d))",
+    ///     condition.to_string()
+    /// );
+    ///
+    /// assert_eq!(None, Condition::all(Vec::<Condition>::new()));
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.LogicalEvaluations
+    pub fn all<I, T>(conditions: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Condition>,
+    {
+        let conditions: Vec<_> = conditions
+            .into_iter()
+            .map(|condition| condition.into().parenthesize_or())
+            .collect();
+
+        balanced(conditions, Self::and)
+    }
+
+    /// Combines conditions with [`OR`][1], or returns `None` if there are no
+    /// conditions.
+    ///
+    /// The conditions are combined into a balanced tree, rather than a chain.
+    ///
+    /// ```
+    /// use dynamodb_expression::{condition::Condition, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let condition = Condition::any(
+    ///     ["a", "b", "c"].map(|name| Path::new_name(name).attribute_exists()),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     "attribute_exists(a) OR attribute_exists(b) OR attribute_exists(c)",
+    ///     condition.to_string()
+    /// );
+    ///
+    /// assert_eq!(None, Condition::any(Vec::<Condition>::new()));
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.LogicalEvaluations
+    pub fn any<I, T>(conditions: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Condition>,
+    {
+        balanced(conditions.into_iter().map(Into::into).collect(), Self::or)
+    }
+
+    /// Simplifies the condition, such as one built up from user input.
+    ///
+    /// * Double negation is removed: `NOT NOT a` becomes `a`.
+    /// * Parentheses that don't change the meaning are removed.
+    /// * Nested `AND`s and `OR`s are flattened, and duplicate conditions
+    ///   within them are removed.
+    /// * A negated comparison is inverted: `NOT (a = b)` becomes `a <> b`,
+    ///   and `NOT a < b` becomes `a >= b`.
+    ///
+    /// The condition is simplified based on how DynamoDB evaluates it as
+    /// written (see [`Display`]), where `NOT` takes precedence over `AND`,
+    /// which takes precedence over `OR`.
+    ///
+    /// Note that DynamoDB comparisons involving an attribute that doesn't
+    /// exist are always false, so `NOT a < b` matches items without `a`, while
+    /// `a >= b` doesn't.
+    ///
+    /// ```
+    /// use dynamodb_expression::{Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let condition = Path::new_name("a")
+    ///     .less_than(Num::new(5))
+    ///     .parenthesize()
+    ///     .not()
+    ///     .and(
+    ///         Path::new_name("b")
+    ///             .attribute_exists()
+    ///             .and(Path::new_name("c").equal(Num::new(1)).not().not())
+    ///             .parenthesize(),
+    ///     )
+    ///     .and(Path::new_name("b").attribute_exists());
+    /// assert_eq!(
+    ///     "NOT (a < 5) AND (attribute_exists(b) AND NOT NOT c = 1) AND attribute_exists(b)",
+    ///     condition.to_string()
+    /// );
+    /// assert_eq!(
+    ///     "a >= 5 AND attribute_exists(b) AND c = 1",
+    ///     condition.simplify().to_string()
+    /// );
+    /// ```
+    ///
+    /// [`Display`]: core::fmt::Display
+    pub fn simplify(self) -> Self {
+        simplify::simplify(self)
+    }
+
+    /// Converts the condition to [conjunctive normal form][1]: `AND`s of
+    /// `OR`s, where `NOT` only applies to individual conditions.
+    ///
+    /// Negations are pushed down as with [`Condition::simplify`], which
+    /// includes inverting comparisons. The result can be exponentially larger
+    /// than the original condition.
+    ///
+    /// ```
+    /// use dynamodb_expression::Path;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let [a, b, c] = ["a", "b", "c"].map(|name| Path::new_name(name).attribute_exists());
+    ///
+    /// // a OR (b AND c)
+    /// let condition = a.or(b.and(c).parenthesize());
+    /// assert_eq!(
+    ///     "(attribute_exists(a) OR attribute_exists(b)) \
+    ///     AND (attribute_exists(a) OR attribute_exists(c))",
+    ///     condition.to_cnf().to_string()
+    /// );
+    /// ```
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Conjunctive_normal_form
+    pub fn to_cnf(self) -> Self {
+        simplify::to_cnf(self)
+    }
+
+    /// Converts the condition to [disjunctive normal form][1]: `OR`s of
+    /// `AND`s, where `NOT` only applies to individual conditions.
+    ///
+    /// Negations are pushed down as with [`Condition::simplify`], which
+    /// includes inverting comparisons. The result can be exponentially larger
+    /// than the original condition.
+    ///
+    /// ```
+    /// use dynamodb_expression::Path;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let [a, b, c] = ["a", "b", "c"].map(|name| Path::new_name(name).attribute_exists());
+    ///
+    /// // a AND (b OR c)
+    /// let condition = a.and(b.or(c).parenthesize());
+    /// assert_eq!(
+    ///     "attribute_exists(a) AND attribute_exists(b) \
+    ///     OR attribute_exists(a) AND attribute_exists(c)",
+    ///     condition.to_dnf().to_string()
+    /// );
+    /// ```
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Disjunctive_normal_form
+    pub fn to_dnf(self) -> Self {
+        simplify::to_dnf(self)
+    }
+
+    /// Evaluates the condition against an item in memory, the way DynamoDB
+    /// would. Like DynamoDB, it's grouped as it's written, with `NOT` binding
+    /// tighter than `AND`, which binds tighter than `OR`, regardless of how
+    /// the condition was built. E.g., `a.or(b).and(c)` is written
+    /// `a OR b AND c`, which is `a OR (b AND c)`.
+    ///
+    /// Fails if the condition uses a [`NameRef`] or [`Ref`], since those can
+    /// only be resolved by DynamoDB.
+    ///
+    /// ```
+    /// use dynamodb_expression::{
+    ///     value::{Map, Value},
+    ///     Num, Path,
+    /// };
+    ///
+    /// let item = Map::from([("age", Value::new_num(40))]);
+    ///
+    /// let condition = Path::new_name("age").greater_than(Num::new(21));
+    /// assert!(condition.evaluate(&item).unwrap());
+    ///
+    /// // Comparisons with an attribute that doesn't exist are false.
+    /// let condition = Path::new_name("name").less_than("M");
+    /// assert!(!condition.evaluate(&item).unwrap());
+    /// ```
+    ///
+    /// See also: [`Condition::explain`]
+    ///
+    /// [`NameRef`]: crate::path::NameRef
+    /// [`Ref`]: crate::value::Ref
+    pub fn evaluate(&self, item: &Map) -> Result<bool, EvalError> {
+        eval::evaluate(&simplify::by_precedence(self), item)
+    }
+
+    /// Evaluates the condition against an item in memory, like
+    /// [`Condition::evaluate`], and explains the result: which parts of the
+    /// condition were true or false, and the values found in the item. The
+    /// parts are grouped the way the condition is evaluated.
+    ///
+    /// ```
+    /// use dynamodb_expression::{
+    ///     value::{Map, Value},
+    ///     Num, Path,
+    /// };
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let item = Map::from([("age", Value::new_num(17))]);
+    ///
+    /// let condition = Path::new_name("age")
+    ///     .greater_than_or_equal(Num::new(18))
+    ///     .and(Path::new_name("consent").attribute_exists());
+    /// let explanation = condition.explain(&item).unwrap();
+    /// assert!(!explanation.result());
+    /// assert_eq!(
+    ///     "false: age >= 18 AND attribute_exists(consent)\n  \
+    ///        false: age >= 18\n    \
+    ///          age: 17\n  \
+    ///        false: attribute_exists(consent)\n    \
+    ///          consent: missing\n",
+    ///     explanation.to_string()
+    /// );
+    /// ```
+    pub fn explain(&self, item: &Map) -> Result<Explanation, EvalError> {
+        eval::explain(&simplify::by_precedence(self), item)
+    }
+
+    /// Converts the condition to [Lambda event filter patterns][1] for a
+    /// DynamoDB Streams event source mapping, for the given part of the
+    /// stream record. A record matches the condition if it matches any of the
+    /// patterns.
+    ///
+    /// Equality, `IN`, `begins_with`, numeric comparisons and `BETWEEN`,
+    /// `attribute_exists`, `attribute_not_exists`, and `attribute_type` are
+    /// supported, as are `NOT`, `AND`, and `OR` of them where a pattern can
+    /// express it. Anything else is an error, rather than a pattern that
+    /// matches something different.
+    ///
+    /// Numbers are strings in stream records, so equality with a number only
+    /// matches if it's written the same way (e.g., `1` doesn't match `1.0`).
+    /// As in DynamoDB, `a <> 1` and `NOT a IN (...)` match items where `a` is
+    /// missing or of another type, using patterns with `{"exists": false}`.
+    ///
+    /// ```
+    /// use dynamodb_expression::{condition::StreamImage, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    /// use serde_json::json;
+    ///
+    /// let condition = Path::new_name("status")
+    ///     .equal("ACTIVE")
+    ///     .and(Path::new_name("age").greater_than_or_equal(Num::new(18)));
+    /// assert_eq!(
+    ///     vec![json!({
+    ///         "dynamodb": {
+    ///             "NewImage": {
+    ///                 "status": { "S": ["ACTIVE"] },
+    ///                 "age": { "N": [{ "numeric": [">=", 18] }] },
+    ///             }
+    ///         }
+    ///     })],
+    ///     condition.to_filter_patterns(StreamImage::NewImage)?,
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/lambda/latest/dg/with-ddb-filtering.html
+    pub fn to_filter_patterns(
+        &self,
+        image: StreamImage,
+    ) -> Result<Vec<serde_json::Value>, FilterPatternError> {
+        filter_pattern::filter_patterns(self, image)
+    }
+
+    /// Wraps an `OR` condition in parentheses so it can be combined with
+    /// `AND` without changing its meaning.
+    fn parenthesize_or(self) -> Self {
+        match self {
+            Self::Or(_) => self.parenthesize(),
+            _ => self,
+        }
+    }
+
+    /// Takes the condition, leaving a cheap placeholder to be overwritten.
+    fn take(&mut self) -> Self {
+        mem::replace(
+            self,
+            Self::AttributeExists(AttributeExists {
+                path: Path {
+                    elements: Vec::new(),
+                },
+            }),
+        )
+    }
+}
+
+/// Combines the conditions into a balanced tree with the given operation.
+fn balanced(
+    mut conditions: Vec<Condition>,
+    op: fn(Condition, Condition) -> Condition,
+) -> Option<Condition> {
+    match conditions.len() {
+        0 => None,
+        1 => conditions.pop(),
+        len => {
+            let right = conditions.split_off(len / 2);
+            let left = balanced(conditions, op)?;
+            let right = balanced(right, op)?;
+
+            Some(op(left, right))
+        }
+    }
 }
 
 impl ops::Not for Condition {
@@ -149,6 +480,9 @@ impl ops::Not for Condition {
 
     /// A [DynamoDB logical `NOT`][1] condition.
     ///
+    /// Unlike [`Condition::not`], an `AND` or `OR` condition is wrapped in
+    /// parentheses, so the result matches how the Rust expression groups.
+    ///
     /// ```
     /// use dynamodb_expression::Path;
     /// # use pretty_assertions::assert_eq;
@@ -158,14 +492,202 @@ impl ops::Not for Condition {
     ///
     /// let condition = !a.greater_than(b);
     /// assert_eq!("NOT a > b", condition.to_string());
+    ///
+    /// let a = Path::new_name("a");
+    /// let b = Path::new_name("b");
+    ///
+    /// let condition = !(a.attribute_exists() & b.attribute_exists());
+    /// assert_eq!(
+    ///     "NOT (attribute_exists(a) AND attribute_exists(b))",
+    ///     condition.to_string()
+    /// );
     /// ```
     ///
     /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.LogicalEvaluations
     fn not(self) -> Self::Output {
-        Condition::not(self)
+        match self {
+            Self::And(_) | Self::Or(_) => self.parenthesize().not(),
+            _ => Condition::not(self),
+        }
     }
 }
 
+impl<R> ops::BitAnd<R> for Condition
+where
+    R: Into<Condition>,
+{
+    type Output = Condition;
+
+    /// A [DynamoDB logical `AND`][1] condition.
+    ///
+    /// Unlike [`Condition::and`], an `OR` condition on either side is wrapped
+    /// in parentheses, so the result matches how the Rust expression groups.
+    ///
+    /// ```
+    /// use dynamodb_expression::Path;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let a = Path::new_name("a");
+    /// let b = Path::new_name("b");
+    /// let c = Path::new_name("c");
+    ///
+    /// let condition = a.attribute_exists() & b.attribute_exists() | c.attribute_exists();
+    /// assert_eq!(
+    ///     "attribute_exists(a) AND attribute_exists(b) OR attribute_exists(c)",
+    ///     condition.to_string()
+    /// );
+    ///
+    /// let a = Path::new_name("a");
+    /// let b = Path::new_name("b");
+    /// let c = Path::new_name("c");
+    ///
+    /// let condition = a.attribute_exists() & (b.attribute_exists() | c.attribute_exists());
+    /// assert_eq!(
+    ///     "attribute_exists(a) AND (attribute_exists(b) OR attribute_exists(c))",
+    ///     condition.to_string()
+    /// );
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.LogicalEvaluations
+    fn bitand(self, right: R) -> Self::Output {
+        self.parenthesize_or().and(right.into().parenthesize_or())
+    }
+}
+
+impl<R> ops::BitOr<R> for Condition
+where
+    R: Into<Condition>,
+{
+    type Output = Condition;
+
+    /// A [DynamoDB logical `OR`][1] condition.
+    ///
+    /// ```
+    /// use dynamodb_expression::Path;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let a = Path::new_name("a");
+    /// let b = Path::new_name("b");
+    ///
+    /// let condition = a.attribute_exists() | b.attribute_exists();
+    /// assert_eq!(
+    ///     "attribute_exists(a) OR attribute_exists(b)",
+    ///     condition.to_string()
+    /// );
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.LogicalEvaluations
+    fn bitor(self, right: R) -> Self::Output {
+        self.or(right)
+    }
+}
+
+impl<R> ops::BitAndAssign<R> for Condition
+where
+    R: Into<Condition>,
+{
+    /// ```
+    /// use dynamodb_expression::{Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let mut condition = Path::new_name("a").greater_than(Num::new(1));
+    /// condition &= Path::new_name("b").less_than(Num::new(2));
+    /// assert_eq!("a > 1 AND b < 2", condition.to_string());
+    /// ```
+    fn bitand_assign(&mut self, right: R) {
+        *self = self.take() & right;
+    }
+}
+
+impl<R> ops::BitOrAssign<R> for Condition
+where
+    R: Into<Condition>,
+{
+    /// ```
+    /// use dynamodb_expression::{Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let mut condition = Path::new_name("a").greater_than(Num::new(1));
+    /// condition |= Path::new_name("b").less_than(Num::new(2));
+    /// assert_eq!("a > 1 OR b < 2", condition.to_string());
+    /// ```
+    fn bitor_assign(&mut self, right: R) {
+        *self = self.take() | right;
+    }
+}
+
+/// Implements `&` and `|` for the individual types of conditions, producing
+/// a [`Condition`].
+macro_rules! condition_ops {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl<R> ops::BitAnd<R> for $ty
+            where
+                R: Into<Condition>,
+            {
+                type Output = Condition;
+
+                fn bitand(self, right: R) -> Self::Output {
+                    Condition::from(self) & right
+                }
+            }
+
+            impl<R> ops::BitOr<R> for $ty
+            where
+                R: Into<Condition>,
+            {
+                type Output = Condition;
+
+                fn bitor(self, right: R) -> Self::Output {
+                    Condition::from(self) | right
+                }
+            }
+        )*
+    };
+}
+
+condition_ops!(
+    AttributeExists,
+    AttributeNotExists,
+    AttributeType,
+    BeginsWith,
+    Between,
+    Contains,
+    In,
+    Not,
+    And,
+    Or,
+    Comparison,
+    Parenthetical,
+);
+
+/// The alternate form (`{:#}`) breaks lines at `AND` and `OR`, and indents
+/// inside parentheses and `IN` lists.
+///
+/// ```
+/// use dynamodb_expression::{Num, Path};
+/// # use pretty_assertions::assert_eq;
+///
+/// let condition = Path::new_name("age")
+///     .greater_than_or_equal(Num::new(21))
+///     .and(
+///         Path::new_name("status")
+///             .in_(["ACTIVE", "PENDING"])
+///             .or(Path::new_name("vip").equal(true))
+///             .parenthesize(),
+///     );
+/// assert_eq!(
+///     "age >= 21\n\
+///     AND (\n  \
+///       status IN (\n    \
+///         \"ACTIVE\",\n    \
+///         \"PENDING\"\n  \
+///       )\n  \
+///       OR vip = true\n\
+///     )",
+///     format!("{condition:#}")
+/// );
+/// ```
 impl fmt::Display for Condition {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -300,6 +822,40 @@ pub(crate) mod test {
         assert_eq!("c < d", cmp_c_lt_d().to_string());
     }
 
+    #[test]
+    fn display_alternate() {
+        let condition = cmp_a_gt_b()
+            .and(
+                cmp_c_lt_d()
+                    .or(cmp_a_gt_b().and(cmp_c_lt_d()).parenthesize().not())
+                    .parenthesize(),
+            )
+            .and(Path::new_name("e").between(Path::new_name("f"), Path::new_name("g")))
+            .or(Path::new_name("h").in_([Path::new_name("i")]));
+
+        assert_eq!(
+            "a > b\n\
+            AND (\n  \
+              c < d\n  \
+              OR NOT (\n    \
+                a > b\n    \
+                AND c < d\n  \
+              )\n\
+            )\n\
+            AND e BETWEEN f AND g\n\
+            OR h IN (\n  \
+              i\n\
+            )",
+            format!("{condition:#}")
+        );
+
+        // Without the alternate flag, it's unchanged.
+        assert_eq!(
+            "a > b AND (c < d OR NOT (a > b AND c < d)) AND e BETWEEN f AND g OR h IN (i)",
+            condition.to_string()
+        );
+    }
+
     #[test]
     fn and() {
         use crate::Path;
@@ -351,4 +907,91 @@ pub(crate) mod test {
         let condition = !a.greater_than(b);
         assert_eq!("NOT a > b", condition.to_string());
     }
+
+    #[test]
+    fn operators() {
+        use crate::{condition::Comparison, Num, Path};
+        use pretty_assertions::assert_eq;
+
+        let a = || Path::new_name("a").attribute_exists();
+        let b = || Path::new_name("b").attribute_exists();
+        let c = || super::greater_than(Path::new_name("c"), Num::new(1));
+
+        assert_eq!(a().and(b()), a() & b());
+        assert_eq!(a().or(b()), a() | b());
+        assert_eq!(a().or(b().and(c())), a() | b() & c());
+        assert_eq!(
+            "(attribute_exists(a) OR attribute_exists(b)) AND c > 1",
+            ((a() | b()) & c()).to_string()
+        );
+
+        // Leaf condition types.
+        let comparison: Comparison = c();
+        assert_eq!(Condition::from(c()).and(a()), comparison & a());
+        assert_eq!(Condition::from(c()).or(a()), c() | a());
+
+        let mut condition = a();
+        condition &= b();
+        condition |= c();
+        assert_eq!(a().and(b()).or(c()), condition);
+    }
+
+    #[test]
+    fn not_operator_groups() {
+        use crate::{Map, Path, Value};
+        use pretty_assertions::assert_eq;
+
+        let a = || Path::new_name("a").attribute_exists();
+        let b = || Path::new_name("b").attribute_exists();
+        let only_a = Map::from([("a", Value::from(true))]);
+
+        let not_and = !(a() & b());
+        assert_eq!(
+            "NOT (attribute_exists(a) AND attribute_exists(b))",
+            not_and.to_string()
+        );
+        assert!(not_and.evaluate(&only_a).unwrap());
+
+        let not_or = !(a() | b());
+        assert_eq!(
+            "NOT (attribute_exists(a) OR attribute_exists(b))",
+            not_or.to_string()
+        );
+        assert!(!not_or.evaluate(&only_a).unwrap());
+        assert!(not_or.evaluate(&Map::default()).unwrap());
+    }
+
+    #[test]
+    fn all_any() {
+        use crate::Path;
+        use pretty_assertions::assert_eq;
+
+        let conditions =
+            ["a", "b", "c", "d", "e"].map(|name| Path::new_name(name).attribute_exists());
+
+        assert_eq!(None, Condition::all(Vec::<Condition>::new()));
+        assert_eq!(None, Condition::any(Vec::<Condition>::new()));
+        assert_eq!(
+            Some(conditions[0].clone()),
+            Condition::all([conditions[0].clone()])
+        );
+
+        let [a, b, c, d, e] = conditions.clone();
+        assert_eq!(
+            Some(a.and(b).and(c.and(d.and(e)))),
+            Condition::all(conditions.clone())
+        );
+
+        let [a, b, c, d, e] = conditions.clone();
+        assert_eq!(
+            Some(a.or(b).or(c.or(d.or(e)))),
+            Condition::any(conditions.clone())
+        );
+
+        let [a, b, c, ..] = conditions;
+        assert_eq!(
+            "attribute_exists(a) AND (attribute_exists(b) OR attribute_exists(c))",
+            Condition::all([a, b.or(c)]).unwrap().to_string()
+        );
+    }
 }
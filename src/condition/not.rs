@@ -19,42 +19,16 @@ use crate::condition::Condition;
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.LogicalEvaluations
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Not {
     pub(crate) condition: Box<Condition>,
 }
 
 impl Not {
-    // /// Normalizes pairs of `NOT` statements by removing them. E.g.,
-    // /// `NOT NOT a < b` becomes `a < b`.
-    // /// `NOT (NOT a < b)` becomes `a < b`.
-    // pub fn normalize(self) -> Expression {
-    //     // `NOT inner`
-
-    //     if let Expression::Logical(Logical::Not(Self(inner))) = *self.0 {
-    //         // `NOT NOT inner`
-    //         inner.normalize()
-    //     } else if let Expression::Parenthetical(parens) = *self.0 {
-    //         // `NOT (inner)`
-
-    //         // Flatten nested paren statements to turn `NOT (((inner)))` into `NOT (inner)`
-    //         let Parenthetical(inner) = parens.flatten();
-
-    //         if let Expression::Logical(Logical::Not(Self(inner))) = *inner {
-    //             // `NOT (NOT inner)`
-    //             inner.normalize()
-    //         } else {
-    //             // `NOT (inner)
-    //             //
-    //             // Put it back in the parentheses.
-    //             let inner = inner.normalize().parenthesize();
-
-    //             // Put it back in `NOT`
-    //             Self::from(inner).into()
-    //         }
-    //     } else {
-    //         Expression::Logical(Logical::Not(self))
-    //     }
-    // }
+    /// The condition being negated.
+    pub fn condition(&self) -> &Condition {
+        &self.condition
+    }
 }
 
 impl<T> From<T> for Not
@@ -8,15 +8,28 @@ use crate::condition::Condition;
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.LogicalEvaluations
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Or {
     pub(crate) left: Box<Condition>,
     pub(crate) right: Box<Condition>,
 }
 
+impl Or {
+    /// The condition on the left side of the `OR`.
+    pub fn left(&self) -> &Condition {
+        &self.left
+    }
+
+    /// The condition on the right side of the `OR`.
+    pub fn right(&self) -> &Condition {
+        &self.right
+    }
+}
+
 impl fmt::Display for Or {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.left.fmt(f)?;
-        f.write_str(" OR ")?;
+        f.write_str(if f.alternate() { "\nOR " } else { " OR " })?;
         self.right.fmt(f)
     }
 }
@@ -1,36 +1,20 @@
 use core::fmt::{self, Write};
 
 use super::Condition;
+use crate::indent::{Indented, INDENT};
 
 /// See also: [`Condition::parenthesize`]
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Parenthetical {
     pub(crate) condition: Box<Condition>,
 }
 
 impl Parenthetical {
-    // /// Unwrap nested parentheses. E.g., `(((a and (((b < c))))))` becomes `(a and (b < c))`
-    // pub fn normalize(self) -> Condition {
-    //     Self(
-    //         self.flatten()
-    //             .0
-    //             // Normalize down the chain.
-    //             .normalize()
-    //             .into(),
-    //     )
-    //     .into()
-    // }
-
-    // /// Removes this level of nested parentheses without any deeper flattening or normalization.
-    // /// E.g., `(((a and (((b < c))))))` becomes `(a and (((b < c))))`
-    // pub fn flatten(self) -> Self {
-    //     let mut inner = self.0;
-    //     while let Expression::Parenthetical(Self(paren_inner)) = *inner {
-    //         inner = paren_inner;
-    //     }
-
-    //     Self(inner)
-    // }
+    /// The condition inside the parentheses.
+    pub fn condition(&self) -> &Condition {
+        &self.condition
+    }
 }
 
 impl<T> From<T> for Parenthetical
@@ -46,6 +30,13 @@ where
 
 impl fmt::Display for Parenthetical {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("(\n")?;
+            f.write_str(INDENT)?;
+            write!(Indented::new(f), "{:#}", self.condition)?;
+            return f.write_str("\n)");
+        }
+
         f.write_char('(')?;
         self.condition.fmt(f)?;
         f.write_char(')')
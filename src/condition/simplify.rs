@@ -0,0 +1,517 @@
+//! Simplification and normalization of conditions.
+//!
+//! Conditions are first converted into an [`Expr`] tree that follows how
+//! DynamoDB evaluates the condition as written (`NOT` binds tighter than
+//! `AND`, which binds tighter than `OR`), regardless of how the [`Condition`]
+//! was built. The result is converted back into a [`Condition`] with only the
+//! parentheses it needs.
+
+use core::iter::Peekable;
+use std::borrow::Cow;
+
+use super::{And, Comparator, Comparison, Condition, Not, Or, Parenthetical};
+
+/// The condition, grouped the way DynamoDB evaluates it as written. Borrowed
+/// if it already is, such as when it was built with the needed parentheses.
+pub(super) fn by_precedence(condition: &Condition) -> Cow<'_, Condition> {
+    if is_grouped(condition) {
+        Cow::Borrowed(condition)
+    } else {
+        Cow::Owned(Expr::parse(condition.clone()).into_condition())
+    }
+}
+
+/// Whether the tree of the condition matches how it's written. That is, no
+/// `OR` is directly under an `AND` or `NOT`, and no `AND` is directly under a
+/// `NOT`. Those would be written without parentheses, and so be grouped
+/// differently by DynamoDB.
+fn is_grouped(condition: &Condition) -> bool {
+    match condition {
+        Condition::And(And { left, right }) => [left, right]
+            .into_iter()
+            .all(|side| !matches!(**side, Condition::Or(_)) && is_grouped(side)),
+        Condition::Or(Or { left, right }) => is_grouped(left) && is_grouped(right),
+        Condition::Not(Not { condition }) => {
+            !matches!(**condition, Condition::And(_) | Condition::Or(_)) && is_grouped(condition)
+        }
+        Condition::Parenthetical(Parenthetical { condition }) => is_grouped(condition),
+        _ => true,
+    }
+}
+
+/// See [`Condition::simplify`].
+pub(super) fn simplify(condition: Condition) -> Condition {
+    Expr::parse(condition).simplify().into_condition()
+}
+
+/// See [`Condition::to_cnf`].
+pub(super) fn to_cnf(condition: Condition) -> Condition {
+    let clauses = Expr::parse(condition).nnf(false, false).cnf();
+
+    Expr::and(clauses.into_iter().map(Expr::or)).into_condition()
+}
+
+/// See [`Condition::to_dnf`].
+pub(super) fn to_dnf(condition: Condition) -> Condition {
+    let terms = Expr::parse(condition).nnf(false, false).dnf();
+
+    Expr::or(terms.into_iter().map(Expr::and)).into_condition()
+}
+
+/// The terms (to `OR` together) of the condition in disjunctive normal form,
+/// each a list of conditions to `AND` together. Each condition is paired with
+/// whether it's negated, and is never a `NOT`, `AND`, `OR`, or parentheses.
+///
+/// Only negated `=` and `<>` comparisons are inverted. E.g., `NOT a < 5`
+/// stays negated, since unlike `a >= 5` it's true when `a` is missing.
+pub(super) fn dnf_terms(condition: Condition) -> Vec<Vec<(bool, Condition)>> {
+    Expr::parse(condition)
+        .nnf(false, true)
+        .dnf()
+        .into_iter()
+        .map(|term| {
+            term.into_iter()
+                .map(|expr| match expr {
+                    Expr::Leaf(condition) => (false, condition),
+                    Expr::Not(expr) => match *expr {
+                        Expr::Leaf(condition) => (true, condition),
+                        _ => unreachable!("`NOT` only applies to leaves in negation normal form"),
+                    },
+                    _ => unreachable!("terms of disjunctive normal form are flattened"),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    /// Any condition other than `NOT`, `AND`, `OR`, or parentheses.
+    Leaf(Condition),
+    Not(Box<Expr>),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+}
+
+/// A condition as it's written, for parsing with precedence.
+enum Token {
+    Not,
+    And,
+    Or,
+    Group(Expr),
+    Leaf(Condition),
+}
+
+impl Expr {
+    fn parse(condition: Condition) -> Self {
+        let mut tokens = Vec::new();
+        tokenize(condition, &mut tokens);
+
+        parse_or(&mut tokens.into_iter().peekable())
+    }
+
+    /// Combines with `AND`, flattening nested `AND`s and removing duplicates.
+    fn and<I>(exprs: I) -> Self
+    where
+        I: IntoIterator<Item = Expr>,
+    {
+        Self::combine(exprs, Self::And, |expr| match expr {
+            Self::And(exprs) => Ok(exprs),
+            expr => Err(expr),
+        })
+    }
+
+    /// Combines with `OR`, flattening nested `OR`s and removing duplicates.
+    fn or<I>(exprs: I) -> Self
+    where
+        I: IntoIterator<Item = Expr>,
+    {
+        Self::combine(exprs, Self::Or, |expr| match expr {
+            Self::Or(exprs) => Ok(exprs),
+            expr => Err(expr),
+        })
+    }
+
+    fn combine<I>(
+        exprs: I,
+        new: fn(Vec<Expr>) -> Self,
+        nested: fn(Expr) -> Result<Vec<Expr>, Expr>,
+    ) -> Self
+    where
+        I: IntoIterator<Item = Expr>,
+    {
+        let mut flat = Vec::new();
+        let mut push = |expr| {
+            if !flat.contains(&expr) {
+                flat.push(expr);
+            }
+        };
+
+        for expr in exprs {
+            match nested(expr) {
+                Ok(exprs) => exprs.into_iter().for_each(&mut push),
+                Err(expr) => push(expr),
+            }
+        }
+
+        if flat.len() == 1 {
+            flat.pop().unwrap()
+        } else {
+            new(flat)
+        }
+    }
+
+    fn simplify(self) -> Self {
+        match self {
+            Self::Leaf(condition) => Self::Leaf(condition),
+            Self::Not(expr) => match expr.simplify() {
+                Self::Not(expr) => *expr,
+                Self::Leaf(Condition::Comparison(comparison)) => {
+                    Self::Leaf(Condition::Comparison(invert(comparison)))
+                }
+                expr => Self::Not(expr.into()),
+            },
+            Self::And(exprs) => Self::and(exprs.into_iter().map(Self::simplify)),
+            Self::Or(exprs) => Self::or(exprs.into_iter().map(Self::simplify)),
+        }
+    }
+
+    /// Converts to negation normal form, where `NOT` only applies to leaves.
+    /// Negated comparisons are inverted, or with `exact`, only those whose
+    /// inverse is true for every item they're false for (`=` and `<>`).
+    fn nnf(self, negate: bool, exact: bool) -> Self {
+        match self {
+            Self::Leaf(Condition::Comparison(comparison))
+                if negate
+                    && (!exact || matches!(comparison.cmp, Comparator::Eq | Comparator::Ne)) =>
+            {
+                Self::Leaf(Condition::Comparison(invert(comparison)))
+            }
+            Self::Leaf(condition) if negate => Self::Not(Self::Leaf(condition).into()),
+            Self::Leaf(condition) => Self::Leaf(condition),
+            Self::Not(expr) => expr.nnf(!negate, exact),
+            Self::And(exprs) if negate => {
+                Self::or(exprs.into_iter().map(|expr| expr.nnf(true, exact)))
+            }
+            Self::And(exprs) => Self::and(exprs.into_iter().map(|expr| expr.nnf(false, exact))),
+            Self::Or(exprs) if negate => {
+                Self::and(exprs.into_iter().map(|expr| expr.nnf(true, exact)))
+            }
+            Self::Or(exprs) => Self::or(exprs.into_iter().map(|expr| expr.nnf(false, exact))),
+        }
+    }
+
+    /// The clauses (to `AND` together) of terms (to `OR` together), from an
+    /// expression in negation normal form.
+    fn cnf(self) -> Vec<Vec<Expr>> {
+        match self {
+            Self::And(exprs) => exprs.into_iter().flat_map(Self::cnf).collect(),
+            Self::Or(exprs) => product(exprs.into_iter().map(Self::cnf)),
+            expr => vec![vec![expr]],
+        }
+    }
+
+    /// The terms (to `OR` together) of clauses (to `AND` together), from an
+    /// expression in negation normal form.
+    fn dnf(self) -> Vec<Vec<Expr>> {
+        match self {
+            Self::Or(exprs) => exprs.into_iter().flat_map(Self::dnf).collect(),
+            Self::And(exprs) => product(exprs.into_iter().map(Self::dnf)),
+            expr => vec![vec![expr]],
+        }
+    }
+
+    fn into_condition(self) -> Condition {
+        match self {
+            Self::Leaf(condition) => condition,
+            Self::Not(expr) => match *expr {
+                expr @ (Self::And(_) | Self::Or(_)) => expr.into_condition().parenthesize().not(),
+                expr => expr.into_condition().not(),
+            },
+            Self::And(exprs) => exprs
+                .into_iter()
+                .map(|expr| match expr {
+                    Self::Or(_) => expr.into_condition().parenthesize(),
+                    expr => expr.into_condition(),
+                })
+                .reduce(Condition::and)
+                .expect("`AND` has at least two conditions"),
+            Self::Or(exprs) => exprs
+                .into_iter()
+                .map(Self::into_condition)
+                .reduce(Condition::or)
+                .expect("`OR` has at least two conditions"),
+        }
+    }
+}
+
+/// Combines each of the groups of the first with each of the groups of the
+/// rest. E.g., `[[a], [b]]` and `[[c], [d]]` become `[[a, c], [a, d], [b, c], [b, d]]`.
+fn product<I>(groups: I) -> Vec<Vec<Expr>>
+where
+    I: Iterator<Item = Vec<Vec<Expr>>>,
+{
+    groups.fold(vec![vec![]], |acc, groups| {
+        acc.iter()
+            .flat_map(|left| {
+                groups.iter().map(move |right| {
+                    let mut combined = left.clone();
+                    combined.extend(right.iter().cloned());
+                    combined
+                })
+            })
+            .collect()
+    })
+}
+
+/// The comparison that's true when the given comparison isn't.
+fn invert(comparison: Comparison) -> Comparison {
+    Comparison {
+        cmp: inverse(comparison.cmp),
+        ..comparison
+    }
+}
+
+/// The comparator that's true when the given one isn't, for values of the
+/// same type.
+pub(super) fn inverse(cmp: Comparator) -> Comparator {
+    match cmp {
+        Comparator::Eq => Comparator::Ne,
+        Comparator::Ne => Comparator::Eq,
+        Comparator::Lt => Comparator::Ge,
+        Comparator::Le => Comparator::Gt,
+        Comparator::Gt => Comparator::Le,
+        Comparator::Ge => Comparator::Lt,
+    }
+}
+
+fn tokenize(condition: Condition, tokens: &mut Vec<Token>) {
+    match condition {
+        Condition::Not(not) => {
+            tokens.push(Token::Not);
+            tokenize(*not.condition, tokens);
+        }
+        Condition::And(and) => {
+            tokenize(*and.left, tokens);
+            tokens.push(Token::And);
+            tokenize(*and.right, tokens);
+        }
+        Condition::Or(or) => {
+            tokenize(*or.left, tokens);
+            tokens.push(Token::Or);
+            tokenize(*or.right, tokens);
+        }
+        Condition::Parenthetical(parens) => {
+            tokens.push(Token::Group(Expr::parse(*parens.condition)));
+        }
+        condition => tokens.push(Token::Leaf(condition)),
+    }
+}
+
+fn parse_or<I>(tokens: &mut Peekable<I>) -> Expr
+where
+    I: Iterator<Item = Token>,
+{
+    let mut exprs = vec![parse_and(tokens)];
+    while tokens.next_if(|token| matches!(token, Token::Or)).is_some() {
+        exprs.push(parse_and(tokens));
+    }
+
+    if exprs.len() == 1 {
+        exprs.pop().unwrap()
+    } else {
+        Expr::Or(exprs)
+    }
+}
+
+fn parse_and<I>(tokens: &mut Peekable<I>) -> Expr
+where
+    I: Iterator<Item = Token>,
+{
+    let mut exprs = vec![parse_not(tokens)];
+    while tokens
+        .next_if(|token| matches!(token, Token::And))
+        .is_some()
+    {
+        exprs.push(parse_not(tokens));
+    }
+
+    if exprs.len() == 1 {
+        exprs.pop().unwrap()
+    } else {
+        Expr::And(exprs)
+    }
+}
+
+fn parse_not<I>(tokens: &mut Peekable<I>) -> Expr
+where
+    I: Iterator<Item = Token>,
+{
+    match tokens.next() {
+        Some(Token::Not) => Expr::Not(parse_not(tokens).into()),
+        Some(Token::Group(expr)) => expr,
+        Some(Token::Leaf(condition)) => Expr::Leaf(condition),
+        Some(Token::And | Token::Or) | None => {
+            unreachable!("tokens from a condition always have an operand here")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{condition::Condition, Num, Path};
+
+    fn a() -> Condition {
+        Path::new_name("a").attribute_exists()
+    }
+
+    fn b() -> Condition {
+        Path::new_name("b").attribute_exists()
+    }
+
+    fn c() -> Condition {
+        Path::new_name("c").greater_than(Num::new(1))
+    }
+
+    fn d() -> Condition {
+        Path::new_name("d").begins_with("x")
+    }
+
+    #[test]
+    fn double_negation() {
+        assert_eq!(a(), a().not().not().simplify());
+        assert_eq!(a(), a().not().parenthesize().not().simplify());
+        assert_eq!(a().not(), a().not().not().parenthesize().not().simplify());
+    }
+
+    #[test]
+    fn parentheses() {
+        assert_eq!(a(), a().parenthesize().parenthesize().simplify());
+        assert_eq!(
+            "attribute_exists(a) AND attribute_exists(b) OR c > 1",
+            a().parenthesize()
+                .and(b())
+                .parenthesize()
+                .or(c().parenthesize())
+                .simplify()
+                .to_string()
+        );
+
+        // These are needed.
+        assert_eq!(
+            "attribute_exists(a) AND (attribute_exists(b) OR c > 1)",
+            a().and(b().or(c()).parenthesize())
+                .parenthesize()
+                .simplify()
+                .to_string()
+        );
+        assert_eq!(
+            "NOT (attribute_exists(a) AND attribute_exists(b))",
+            a().and(b()).parenthesize().not().simplify().to_string()
+        );
+    }
+
+    #[test]
+    fn flatten_and_dedupe() {
+        assert_eq!(
+            "attribute_exists(a) AND attribute_exists(b) AND c > 1",
+            a().and(b().parenthesize())
+                .and(a().and(c()).parenthesize())
+                .and(b())
+                .simplify()
+                .to_string()
+        );
+        assert_eq!(
+            "attribute_exists(a) OR attribute_exists(b)",
+            a().or(b().or(a()).parenthesize()).simplify().to_string()
+        );
+        assert_eq!(a(), a().and(a()).or(a()).simplify());
+    }
+
+    #[test]
+    fn inverted_comparisons() {
+        let x = || Path::new_name("x");
+
+        assert_eq!(
+            "x <> 1",
+            x().equal(Num::new(1))
+                .parenthesize()
+                .not()
+                .simplify()
+                .to_string()
+        );
+        assert_eq!(
+            "x >= 1",
+            x().less_than(Num::new(1)).not().simplify().to_string()
+        );
+        assert_eq!(
+            "x > 1 AND x < 1 AND x <= 1 AND x = 1",
+            x().less_than_or_equal(Num::new(1))
+                .not()
+                .and(x().greater_than_or_equal(Num::new(1)).not())
+                .and(x().greater_than(Num::new(1)).not())
+                .and(x().not_equal(Num::new(1)).not())
+                .simplify()
+                .to_string()
+        );
+    }
+
+    /// How the condition is written is what matters, not how it was built.
+    #[test]
+    fn precedence() {
+        // `NOT a AND b` is `(NOT a) AND b`, even if built as `NOT (a AND b)`.
+        assert_eq!(
+            "NOT attribute_exists(a) AND attribute_exists(b)",
+            a().and(b()).not().simplify().to_string()
+        );
+
+        // `a OR b AND c` is `a OR (b AND c)`, even if built as `(a OR b) AND c`.
+        assert_eq!(
+            "attribute_exists(a) OR attribute_exists(b) AND c > 1",
+            a().or(b()).and(c()).simplify().to_string()
+        );
+    }
+
+    #[test]
+    fn cnf() {
+        // (a AND b) OR (c AND d)
+        let condition = a().and(b()).parenthesize().or(c().and(d()).parenthesize());
+        assert_eq!(
+            "(attribute_exists(a) OR c > 1) \
+            AND (attribute_exists(a) OR begins_with(d, \"x\")) \
+            AND (attribute_exists(b) OR c > 1) \
+            AND (attribute_exists(b) OR begins_with(d, \"x\"))",
+            condition.to_cnf().to_string()
+        );
+
+        // NOT (a OR c)
+        assert_eq!(
+            "NOT attribute_exists(a) AND c <= 1",
+            a().or(c()).parenthesize().not().to_cnf().to_string()
+        );
+    }
+
+    #[test]
+    fn dnf() {
+        // (a OR b) AND (c OR d)
+        let condition = a().or(b()).parenthesize().and(c().or(d()).parenthesize());
+        assert_eq!(
+            "attribute_exists(a) AND c > 1 \
+            OR attribute_exists(a) AND begins_with(d, \"x\") \
+            OR attribute_exists(b) AND c > 1 \
+            OR attribute_exists(b) AND begins_with(d, \"x\")",
+            condition.to_dnf().to_string()
+        );
+
+        // NOT (a AND c)
+        assert_eq!(
+            "NOT attribute_exists(a) OR c <= 1",
+            a().and(c()).parenthesize().not().to_dnf().to_string()
+        );
+
+        // Already in DNF.
+        assert_eq!(a().or(b()), a().or(b()).to_dnf());
+    }
+}
@@ -1,20 +1,24 @@
-use std::collections::HashMap;
+use core::mem;
+use std::collections::{HashMap, HashSet};
 
 use itermap::IterMap;
 use itertools::Itertools;
 use optempty::EmptyIntoNone;
 
-use super::Expression;
+use super::{
+    canonical::value_sort_key,
+    names::{NameStrategy, Rename},
+    prepared::{InferTypes, Prepared},
+    Dependencies, Expression, LimitError, OverlapError,
+};
 use crate::{
-    condition::{
-        And, AttributeExists, AttributeNotExists, AttributeType, BeginsWith, Between, Comparison,
-        Condition, Contains, In, Not, Or, Parenthetical,
-    },
+    condition::Condition,
+    fold::Fold,
     key::KeyCondition,
-    operand::{Operand, OperandType, Size},
-    path::{Element, Name, Path},
-    update::{set::SetAction, Update},
+    path::{Element, FieldName, Name, NameRef},
+    update::Update,
     value::{Ref, Value, ValueOrRef},
+    visit::Visit,
 };
 
 #[must_use = "Call `.build()` to create the `Expression`"]
@@ -27,6 +31,14 @@ pub struct Builder {
     projection: Option<Vec<Name>>,
     names: HashMap<Name, String>,
     values: HashMap<Value, Ref>,
+    name_refs: HashMap<NameRef, Name>,
+    value_refs: HashMap<Ref, Value>,
+    /// The placeholders of the [`NameRef`]s and [`Ref`]s that are used or
+    /// supplied, which generated placeholders must not clash with.
+    reserved_names: HashSet<String>,
+    reserved_values: HashSet<Ref>,
+    name_strategy: NameStrategy,
+    canonical: bool,
 }
 
 /// Functions and methods for building an `Expression`.
@@ -36,7 +48,9 @@ impl Builder {
     where
         T: Into<Condition>,
     {
-        self.condition = Some(self.process_condition(condition.into()));
+        let condition = condition.into();
+        self.reserve_refs(|reserve| reserve.visit_condition(&condition));
+        self.condition = Some(Collect(&mut self).fold_condition(condition));
 
         self
     }
@@ -46,9 +60,9 @@ impl Builder {
     where
         T: Into<KeyCondition>,
     {
-        self.key_condition = Some(KeyCondition {
-            condition: self.process_condition(key_condition.into().condition),
-        });
+        let key_condition = key_condition.into();
+        self.reserve_refs(|reserve| reserve.visit_key_condition(&key_condition));
+        self.key_condition = Some(Collect(&mut self).fold_key_condition(key_condition));
 
         self
     }
@@ -58,7 +72,9 @@ impl Builder {
     where
         T: Into<Update>,
     {
-        self.update = Some(self.process_update(update.into()));
+        let update = update.into();
+        self.reserve_refs(|reserve| reserve.visit_update(&update));
+        self.update = Some(Collect(&mut self).fold_update(update));
 
         self
     }
@@ -68,7 +84,9 @@ impl Builder {
     where
         T: Into<Condition>,
     {
-        self.filter = Some(self.process_condition(filter.into()));
+        let filter = filter.into();
+        self.reserve_refs(|reserve| reserve.visit_condition(&filter));
+        self.filter = Some(Collect(&mut self).fold_condition(filter));
 
         self
     }
@@ -142,8 +160,125 @@ impl Builder {
         self
     }
 
+    /// Supplies the attribute name that a [`NameRef`] refers to. It's added to
+    /// the expression attribute names as-is, and isn't affected by the
+    /// [`NameStrategy`] or canonical ordering. The placeholders generated for
+    /// other names are chosen so they don't clash with it.
+    ///
+    /// Use [`Builder::try_build`] to find any [`NameRef`]s that were not
+    /// supplied.
+    ///
+    /// ```
+    /// use dynamodb_expression::{path::NameRef, value::Ref, Expression, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_key_condition(
+    ///         Path::from(NameRef::new("pk"))
+    ///             .key()
+    ///             .equal(Ref::new("pk"))
+    ///             .and(Path::new_name("sk").key().greater_than(Num::new(7))),
+    ///     )
+    ///     .with_name("pk", "partition_key")
+    ///     .with_value("pk", "abc")
+    ///     .try_build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some(String::from("#pk = :pk AND #0 > :0")),
+    ///     expression.key_condition_expression
+    /// );
+    /// assert_eq!(
+    ///     Some(
+    ///         [("#pk", "partition_key"), ("#0", "sk")]
+    ///             .into_iter()
+    ///             .map(|(k, v)| (String::from(k), String::from(v)))
+    ///             .collect()
+    ///     ),
+    ///     expression.expression_attribute_names
+    /// );
+    /// ```
+    pub fn with_name<R, N>(mut self, name_ref: R, name: N) -> Self
+    where
+        R: Into<NameRef>,
+        N: Into<Name>,
+    {
+        let name_ref = name_ref.into();
+        self.reserve([name_ref.to_string()], []);
+        self.name_refs.insert(name_ref, name.into());
+
+        self
+    }
+
+    /// Supplies the value that a [`Ref`] refers to. It's added to the
+    /// expression attribute values as-is, and isn't affected by canonical
+    /// ordering. The placeholders generated for other values are chosen so
+    /// they don't clash with it.
+    ///
+    /// Use [`Builder::try_build`] to find any [`Ref`]s that were not supplied.
+    ///
+    /// See [`Builder::with_name`] for an example.
+    pub fn with_value<R, V>(mut self, value_ref: R, value: V) -> Self
+    where
+        R: Into<Ref>,
+        V: Into<Value>,
+    {
+        let value_ref = value_ref.into();
+        self.reserve([], [value_ref.clone()]);
+        self.value_refs.insert(value_ref, value.into());
+
+        self
+    }
+
+    /// Sets how attribute names are turned into [expression attribute
+    /// names][1]. This applies to all names in the [`Expression`], regardless
+    /// of when it's called. The default is [`NameStrategy::Numbered`].
+    ///
+    /// See [`NameStrategy`] for examples.
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.ExpressionAttributeNames.html
+    pub fn with_name_strategy(mut self, name_strategy: NameStrategy) -> Self {
+        self.name_strategy = name_strategy;
+
+        self
+    }
+
+    /// Assigns the expression attribute names and values in a canonical order,
+    /// rather than in the order they were added. Names are sorted by name, and
+    /// values by type and then by value. This applies to all names and values
+    /// in the [`Expression`], regardless of when it's called.
+    ///
+    /// This means the same expression built through different code paths gets
+    /// the same placeholders. See also: [`Expression::is_equivalent`]
+    ///
+    /// ```
+    /// use dynamodb_expression::{Expression, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_condition(
+    ///         Path::new_name("name")
+    ///             .attribute_exists()
+    ///             .and(Path::new_name("age").greater_than(Num::new(21))),
+    ///     )
+    ///     .with_canonical_placeholders()
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     Some(String::from("attribute_exists(#1) AND #0 > :0")),
+    ///     expression.condition_expression
+    /// );
+    /// ```
+    pub fn with_canonical_placeholders(mut self) -> Self {
+        self.canonical = true;
+
+        self
+    }
+
     /// Builds the [`Expression`].
-    pub fn build(self) -> Expression {
+    pub fn build(mut self) -> Expression {
+        self.finalize_placeholders();
+
         let Self {
             condition,
             key_condition,
@@ -152,6 +287,12 @@ impl Builder {
             projection,
             names,
             values,
+            name_refs,
+            value_refs,
+            reserved_names: _,
+            reserved_values: _,
+            name_strategy: _,
+            canonical: _,
         } = self;
 
         Expression {
@@ -175,6 +316,12 @@ impl Builder {
                     .into_iter()
                     .map_keys(|name| name.name)
                     .swap()
+                    .chain(
+                        name_refs
+                            .into_iter()
+                            .map_keys(String::from)
+                            .map_values(|name| name.name),
+                    )
                     .collect(),
             )
             .empty_into_none(),
@@ -182,6 +329,7 @@ impl Builder {
                 values
                     .into_iter()
                     .swap()
+                    .chain(value_refs)
                     .map_keys(String::from)
                     .map_values(Value::into_attribute_value)
                     .collect(),
@@ -190,204 +338,353 @@ impl Builder {
         }
     }
 
-    fn process_condition(&mut self, condition: Condition) -> Condition {
-        match condition {
-            Condition::AttributeExists(AttributeExists { path }) => AttributeExists {
-                path: self.process_path(path),
-            }
-            .into(),
-            Condition::AttributeNotExists(AttributeNotExists { path }) => AttributeNotExists {
-                path: self.process_path(path),
-            }
-            .into(),
-            Condition::AttributeType(AttributeType {
-                path,
-                attribute_type,
-            }) => AttributeType {
-                path: self.process_path(path),
-                attribute_type,
-            }
-            .into(),
-            Condition::Contains(Contains { path, operand }) => Contains {
-                path: self.process_path(path),
-                operand: self.process_value(operand).into(),
-            }
-            .into(),
-            Condition::BeginsWith(BeginsWith { path, substr }) => BeginsWith {
-                path: self.process_path(path),
-                substr: self.process_value(substr).into(),
-            }
-            .into(),
-            Condition::Between(Between { op, lower, upper }) => Between {
-                op: self.process_operand(op),
-                lower: self.process_operand(lower),
-                upper: self.process_operand(upper),
-            }
-            .into(),
-            Condition::In(In { op, items }) => In {
-                op: self.process_operand(op),
-                items: items
-                    .into_iter()
-                    .map(|item| self.process_operand(item))
-                    .collect(),
-            }
-            .into(),
-            Condition::Comparison(Comparison { left, cmp, right }) => Comparison {
-                left: self.process_operand(left),
-                cmp,
-                right: self.process_operand(right),
-            }
-            .into(),
-            Condition::And(And { left, right }) => And {
-                left: self.process_condition(*left).into(),
-                right: self.process_condition(*right).into(),
-            }
-            .into(),
-            Condition::Or(Or { left, right }) => Or {
-                left: self.process_condition(*left).into(),
-                right: self.process_condition(*right).into(),
+    /// Builds the [`Expression`], checking it against the DynamoDB
+    /// [expression limits][1]. If any are violated, the returned error lists
+    /// every one of them. This includes any [`NameRef`] or [`Ref`] that was
+    /// used but never supplied (see [`Builder::with_name`] and
+    /// [`Builder::with_value`]).
+    ///
+    /// See also: [`Expression::check_limits`]
+    ///
+    /// ```
+    /// use dynamodb_expression::{
+    ///     expression::{ExpressionKind, LimitViolation},
+    ///     Expression, Num, Path,
+    /// };
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_filter(Path::new_name("age").greater_than(Num::new(21)))
+    ///     .try_build()
+    ///     .unwrap();
+    /// assert_eq!(Some(String::from("#0 > :0")), expression.filter_expression);
+    ///
+    /// let error = Expression::builder()
+    ///     .with_filter(Path::new_name("id").in_((0..=100).map(Num::new)))
+    ///     .try_build()
+    ///     .unwrap_err();
+    /// assert_eq!(
+    ///     [LimitViolation::TooManyInOperands {
+    ///         kind: ExpressionKind::Filter,
+    ///         count: 101
+    ///     }],
+    ///     error.violations()
+    /// );
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/ServiceQuotas.html#limits-expression-parameters
+    pub fn try_build(self) -> Result<Expression, LimitError> {
+        let expression = self.build();
+        expression.check_limits()?;
+
+        Ok(expression)
+    }
+
+    /// Compiles this into a [`Prepared`] expression. Every [`Ref`] that
+    /// wasn't supplied with [`Builder::with_value`] becomes a parameter to
+    /// bind values to with [`Prepared::bind`].
+    ///
+    /// See [`Prepared`] for an example.
+    pub fn prepare(self) -> Prepared {
+        let mut infer = InferTypes::default();
+        self.condition
+            .iter()
+            .chain(self.key_condition.as_ref().map(|key| &key.condition))
+            .chain(&self.filter)
+            .for_each(|condition| infer.visit_condition(condition));
+        if let Some(update) = &self.update {
+            infer.visit_update(update);
+        }
+
+        Prepared::new(self.build(), infer.types)
+    }
+
+    /// The attributes the expression being built reads and writes.
+    ///
+    /// See [`Dependencies`] for an example.
+    pub fn dependencies(&self) -> Dependencies {
+        let mut dependencies = Dependencies::default();
+        self.condition
+            .iter()
+            .chain(self.key_condition.as_ref().map(|key| &key.condition))
+            .chain(&self.filter)
+            .for_each(|condition| dependencies.visit_condition(condition));
+        if let Some(update) = &self.update {
+            dependencies.visit_update(update);
+        }
+        if let Some(projection) = &self.projection {
+            dependencies.add_projection(projection);
+        }
+
+        // By now, the names have been replaced with their placeholders.
+        let placeholders: HashMap<_, _> = self
+            .names
+            .iter()
+            .map(|(name, placeholder)| (placeholder.as_str(), name))
+            .collect();
+        let resolve = |name: FieldName| match name {
+            FieldName::Name(name) => match placeholders.get(name.name.as_str()) {
+                Some(name) => FieldName::Name((*name).clone()),
+                None => FieldName::Name(name),
+            },
+            FieldName::NameRef(name_ref) => match self.name_refs.get(&name_ref) {
+                Some(name) => FieldName::Name(name.clone()),
+                None => FieldName::NameRef(name_ref),
+            },
+        };
+        dependencies.map_elements(|element| match element {
+            Element::Name(name) => resolve(name.into()).into(),
+            Element::IndexedField(mut field) => {
+                field.name = resolve(field.name);
+                Element::IndexedField(field)
             }
-            .into(),
-            Condition::Not(Not { condition }) => Not {
-                condition: self.process_condition(*condition).into(),
+            Element::NameRef(name_ref) => resolve(name_ref.into()).into(),
+        });
+
+        dependencies
+    }
+
+    /// Checks that no two actions in the update expression write to
+    /// [overlapping paths][1], which DynamoDB rejects.
+    ///
+    /// Unlike [`Update::check_overlaps`], any [`NameRef`]s supplied with
+    /// [`Builder::with_name`] are resolved first.
+    ///
+    /// [1]: Dependencies::overlaps
+    pub fn check_overlaps(&self) -> Result<(), OverlapError> {
+        self.dependencies().check_overlaps()
+    }
+
+    fn process_name(&mut self, name: Name) -> Name {
+        if let Some(placeholder) = self.names.get(&name) {
+            return Name::from(placeholder.as_str());
+        }
+
+        let placeholder = self.new_name_placeholder();
+        self.names.insert(name, placeholder.clone());
+
+        Name { name: placeholder }
+    }
+
+    fn process_value(&mut self, value: ValueOrRef) -> Ref {
+        match value {
+            ValueOrRef::Value(value) => {
+                if let Some(value_ref) = self.values.get(&value) {
+                    return value_ref.clone();
+                }
+
+                let value_ref = self.new_value_placeholder();
+                self.values.insert(value, value_ref.clone());
+
+                value_ref
             }
-            .into(),
-            Condition::Parenthetical(Parenthetical { condition }) => Parenthetical {
-                condition: self.process_condition(*condition).into(),
+            ValueOrRef::Ref(value) => value,
+        }
+    }
+
+    /// The next numbered placeholder for a name that isn't reserved or in use.
+    fn new_name_placeholder(&self) -> String {
+        let mut n = self.names.len();
+        loop {
+            let placeholder = format!("#{n}");
+            if !self.reserved_names.contains(&placeholder)
+                && !self.names.values().any(|used| *used == placeholder)
+            {
+                return placeholder;
             }
-            .into(),
+
+            n += 1;
         }
     }
 
-    fn process_operand(&mut self, operand: Operand) -> Operand {
-        match operand.op {
-            OperandType::Path(path) => self.process_path(path).into(),
-            OperandType::Size(Size { path: name }) => Size {
-                path: self.process_path(name),
+    /// The next numbered placeholder for a value that isn't reserved or in
+    /// use.
+    fn new_value_placeholder(&self) -> Ref {
+        let mut n = self.values.len();
+        loop {
+            let placeholder = Ref::from(n.to_string());
+            if !self.reserved_values.contains(&placeholder)
+                && !self.values.values().any(|used| *used == placeholder)
+            {
+                return placeholder;
             }
-            .into(),
-            OperandType::Scalar(value) => Operand {
-                op: OperandType::Scalar(self.process_value(value).into()),
-            },
-            OperandType::Condition(condition) => self.process_condition(*condition).into(),
+
+            n += 1;
         }
     }
 
-    fn process_update(&mut self, update: Update) -> Update {
-        match update {
-            Update::Set(mut update) => {
-                update.actions = update
-                    .actions
-                    .into_iter()
-                    .map(|action| match action {
-                        SetAction::Assign(mut action) => {
-                            action.path = self.process_path(action.path);
-                            action.value = self.process_value(action.value).into();
-
-                            action.into()
-                        }
-                        SetAction::Math(mut action) => {
-                            action.dst = self.process_path(action.dst);
-                            action.src = action.src.map(|src| self.process_path(src));
-                            action.num = self.process_value(action.num).into();
-
-                            action.into()
-                        }
-                        SetAction::ListAppend(mut action) => {
-                            action.dst = self.process_path(action.dst);
-                            action.src = action.src.map(|src| self.process_path(src));
-                            action.list = self.process_value(action.list).into();
-
-                            action.into()
-                        }
-                        SetAction::IfNotExists(mut action) => {
-                            action.dst = self.process_path(action.dst);
-                            action.src = action.src.map(|src| self.process_path(src));
-                            action.value = self.process_value(action.value).into();
-
-                            action.into()
-                        }
-                    })
-                    .collect();
-
-                update.into()
+    /// Reserves the placeholders of the [`NameRef`]s and [`Ref`]s found by
+    /// `visit`.
+    fn reserve_refs<F>(&mut self, visit: F)
+    where
+        F: FnOnce(&mut Reserve),
+    {
+        let mut reserve = Reserve::default();
+        visit(&mut reserve);
+        self.reserve(reserve.names, reserve.values);
+    }
+
+    /// Keeps generated placeholders from clashing with these. Any that were
+    /// already generated are moved to new placeholders.
+    fn reserve<N, V>(&mut self, names: N, values: V)
+    where
+        N: IntoIterator<Item = String>,
+        V: IntoIterator<Item = Ref>,
+    {
+        let mut rename = Rename::default();
+
+        for placeholder in names {
+            if !self.reserved_names.insert(placeholder.clone()) {
+                continue;
             }
-            Update::Remove(mut update) => {
-                update.paths = update
-                    .paths
-                    .into_iter()
-                    .map(|path| self.process_path(path))
-                    .collect();
 
-                update.into()
+            if self.names.values().any(|used| *used == placeholder) {
+                let new = self.new_name_placeholder();
+                if let Some(used) = self.names.values_mut().find(|used| **used == placeholder) {
+                    *used = new.clone();
+                }
+                rename.names.insert(placeholder, new);
             }
-            Update::Add(mut update) => {
-                update.path = self.process_path(update.path);
-                update.value = self.process_value(update.value).into();
+        }
 
-                update.into()
+        for value_ref in values {
+            if !self.reserved_values.insert(value_ref.clone()) {
+                continue;
             }
-            Update::Delete(mut update) => {
-                update.path = self.process_path(update.path);
-                update.subset = self.process_value(update.subset).into();
 
-                update.into()
+            if self.values.values().any(|used| *used == value_ref) {
+                let new = self.new_value_placeholder();
+                if let Some(used) = self.values.values_mut().find(|used| **used == value_ref) {
+                    *used = new.clone();
+                }
+                rename.values.insert(value_ref, new);
             }
         }
+
+        if !rename.names.is_empty() || !rename.values.is_empty() {
+            self.rename(rename);
+        }
     }
 
-    fn process_path(&mut self, mut path: Path) -> Path {
-        path.elements = path
-            .elements
-            .into_iter()
-            .map(|elem| match elem {
-                Element::Name(name) => self.process_name(name).into(),
-                Element::IndexedField(mut new_indexed_field) => {
-                    new_indexed_field.name = self.process_name(new_indexed_field.name);
+    /// Applies the [`NameStrategy`] and canonical ordering (if set) to the
+    /// names and values that have already been processed.
+    fn finalize_placeholders(&mut self) {
+        if self.name_strategy == NameStrategy::Numbered && !self.canonical {
+            return;
+        }
 
-                    new_indexed_field.into()
+        let mut names = mem::take(&mut self.names).into_iter().collect_vec();
+        if self.canonical {
+            names.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        } else {
+            // Put them back in the order they were first seen.
+            names.sort_unstable_by_key(|(_name, placeholder)| {
+                (placeholder.len(), placeholder.clone())
+            });
+        }
+
+        let placeholders = self.name_strategy.placeholders(
+            names.iter().map(|(name, _placeholder)| name.name.as_str()),
+            &self.reserved_names,
+        );
+
+        let mut rename = Rename::default();
+        for ((name, old), new) in names.into_iter().zip(placeholders) {
+            match new {
+                Some(new) => {
+                    rename.names.insert(old, new.clone());
+                    self.names.insert(name, new);
                 }
-            })
-            .collect();
+                None => {
+                    rename.names.insert(old, name.name);
+                }
+            }
+        }
 
-        path
+        if self.canonical {
+            let values = mem::take(&mut self.values)
+                .into_iter()
+                .sorted_by_cached_key(|(value, _value_ref)| value_sort_key(value));
+            for (value, old) in values {
+                let new = self.new_value_placeholder();
+                rename.values.insert(old, new.clone());
+                self.values.insert(value, new);
+            }
+        }
+
+        self.rename(rename);
     }
 
-    fn process_name(&mut self, name: Name) -> Name {
-        let count = self.names.len();
-
-        Name {
-            name: self
-                .names
-                .entry(name)
-                .or_insert(format!("#{count}"))
-                .clone(),
-        }
+    /// Replaces placeholders that have already been used.
+    fn rename(&mut self, mut rename: Rename) {
+        self.condition = self
+            .condition
+            .take()
+            .map(|condition| rename.fold_condition(condition));
+        self.key_condition = self
+            .key_condition
+            .take()
+            .map(|key_condition| rename.fold_key_condition(key_condition));
+        self.update = self.update.take().map(|update| rename.fold_update(update));
+        self.filter = self
+            .filter
+            .take()
+            .map(|filter| rename.fold_condition(filter));
+        self.projection = self.projection.take().map(|names| {
+            names
+                .into_iter()
+                .map(|name| rename.fold_name(name))
+                .collect()
+        });
     }
+}
 
-    fn process_value(&mut self, value: ValueOrRef) -> Ref {
-        match value {
-            ValueOrRef::Value(value) => {
-                let count = self.values.len();
+/// Finds the [`NameRef`]s and [`Ref`]s used, so generated placeholders can
+/// be kept clear of them.
+#[derive(Default)]
+struct Reserve {
+    names: Vec<String>,
+    values: Vec<Ref>,
+}
 
-                self.values
-                    .entry(value)
-                    .or_insert_with(|| count.to_string().into())
-                    .clone()
-            }
-            ValueOrRef::Ref(value) => value,
+impl Visit for Reserve {
+    fn visit_name_ref(&mut self, name_ref: &NameRef) {
+        self.names.push(name_ref.to_string());
+    }
+
+    fn visit_value(&mut self, value: &ValueOrRef) {
+        if let ValueOrRef::Ref(value_ref) = value {
+            self.values.push(value_ref.clone());
         }
     }
 }
 
+/// This is how the [`Builder`] collects the names and values into the
+/// expression attribute names and values.
+struct Collect<'a>(&'a mut Builder);
+
+impl Fold for Collect<'_> {
+    fn fold_name(&mut self, name: Name) -> Name {
+        self.0.process_name(name)
+    }
+
+    fn fold_value(&mut self, value: ValueOrRef) -> ValueOrRef {
+        self.0.process_value(value).into()
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use aws_sdk_dynamodb::operation::query::builders::QueryInputBuilder;
+    use aws_sdk_dynamodb::{operation::query::builders::QueryInputBuilder, types::AttributeValue};
     use pretty_assertions::assert_eq;
 
-    use crate::path::Name;
+    use crate::{
+        expression::LimitViolation,
+        path::{Name, NameRef, Path},
+        value::{Num, Ref},
+    };
 
-    use super::Expression;
+    use super::{Expression, NameStrategy};
 
     #[test]
     fn empty_projection() {
@@ -411,6 +708,146 @@ mod test {
         let query = expression.to_query_input_builder();
         assert_eq!(QueryInputBuilder::default(), query);
     }
+
+    #[test]
+    fn indexed_name_ref() {
+        let path: Path = "#list[0].x".parse().unwrap();
+
+        let expression = Expression::builder()
+            .with_condition(path.clone().equal(Num::new(1)))
+            .with_name("list", "items")
+            .try_build()
+            .unwrap();
+        assert_eq!(
+            Some(String::from("#list[0].#0 = :0")),
+            expression.condition_expression
+        );
+        assert_eq!(
+            Some(
+                [("#list", "items"), ("#0", "x")]
+                    .map(|(k, v)| (String::from(k), String::from(v)))
+                    .into()
+            ),
+            expression.expression_attribute_names
+        );
+
+        let error = Expression::builder()
+            .with_condition(path.equal(Num::new(1)))
+            .try_build()
+            .unwrap_err();
+        assert_eq!(
+            vec![LimitViolation::UnresolvedName {
+                placeholder: "#list".into()
+            }],
+            error.into_violations()
+        );
+    }
+
+    #[test]
+    fn placeholder_collisions() {
+        let names = |names: &[(&str, &str)]| {
+            Some(
+                names
+                    .iter()
+                    .map(|(k, v)| (String::from(*k), String::from(*v)))
+                    .collect(),
+            )
+        };
+        let values = |values: &[(&str, AttributeValue)]| {
+            Some(
+                values
+                    .iter()
+                    .map(|(k, v)| (String::from(*k), v.clone()))
+                    .collect(),
+            )
+        };
+
+        let expression = Expression::builder()
+            .with_condition(
+                Path::from(NameRef::new("0"))
+                    .equal(Ref::new("0"))
+                    .and(Path::new_name("a").equal(Num::new(1))),
+            )
+            .with_name("0", "x")
+            .with_value("0", "v")
+            .try_build()
+            .unwrap();
+        assert_eq!(
+            Some(String::from("#0 = :0 AND #1 = :1")),
+            expression.condition_expression
+        );
+        assert_eq!(
+            names(&[("#0", "x"), ("#1", "a")]),
+            expression.expression_attribute_names
+        );
+        assert_eq!(
+            values(&[
+                (":0", AttributeValue::S("v".into())),
+                (":1", AttributeValue::N("1".into()))
+            ]),
+            expression.expression_attribute_values
+        );
+
+        // The placeholders were already generated by the time the references
+        // turn up, so they're moved.
+        let expression = Expression::builder()
+            .with_condition(Path::new_name("a").equal(Num::new(1)))
+            .with_name("0", "x")
+            .with_filter(Path::from(NameRef::new("0")).equal(Ref::new("0")))
+            .with_value("0", "v")
+            .with_canonical_placeholders()
+            .try_build()
+            .unwrap();
+        assert_eq!(
+            Some(String::from("#1 = :1")),
+            expression.condition_expression
+        );
+        assert_eq!(Some(String::from("#0 = :0")), expression.filter_expression);
+        assert_eq!(
+            names(&[("#0", "x"), ("#1", "a")]),
+            expression.expression_attribute_names
+        );
+        assert_eq!(
+            values(&[
+                (":0", AttributeValue::S("v".into())),
+                (":1", AttributeValue::N("1".into()))
+            ]),
+            expression.expression_attribute_values
+        );
+    }
+
+    #[test]
+    fn name_strategy() {
+        let expression = Expression::builder()
+            .with_update(Path::new_name("name").assign(Num::new(1)))
+            .with_filter(Path::new_name("age").greater_than(Num::new(2)))
+            .with_projection(["name", "age", "status"])
+            .with_name_strategy(NameStrategy::WhenNeeded)
+            .build();
+
+        assert_eq!(
+            Expression {
+                condition_expression: None,
+                key_condition_expression: None,
+                update_expression: Some("SET #name = :0".into()),
+                filter_expression: Some("age > :1".into()),
+                projection_expression: Some("#name, age, #status".into()),
+                expression_attribute_names: Some(
+                    [("#name", "name"), ("#status", "status")]
+                        .into_iter()
+                        .map(|(k, v)| (String::from(k), String::from(v)))
+                        .collect(),
+                ),
+                expression_attribute_values: Some(
+                    [(":0", "1"), (":1", "2")]
+                        .into_iter()
+                        .map(|(k, v)| (String::from(k), AttributeValue::N(v.into())))
+                        .collect(),
+                ),
+            },
+            expression
+        );
+    }
 }
 
 #[cfg(test)]
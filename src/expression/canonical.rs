@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use itertools::Itertools;
+
+use super::{
+    lexer::{tokenize, TokenKind},
+    Expression,
+};
+use crate::value::{Scalar, Set, Value};
+
+/// Gives a total, deterministic ordering of values, for assigning
+/// placeholders in a canonical order.
+pub(crate) fn value_sort_key(value: &Value) -> (u8, String) {
+    let kind = match value {
+        Value::Scalar(Scalar::String(_)) => 0,
+        Value::Scalar(Scalar::Num(_)) => 1,
+        Value::Scalar(Scalar::Bool(_)) => 2,
+        Value::Scalar(Scalar::Binary(_)) => 3,
+        Value::Scalar(Scalar::Null) => 4,
+        Value::Set(Set::StringSet(_)) => 5,
+        Value::Set(Set::NumSet(_)) => 6,
+        Value::Set(Set::BinarySet(_)) => 7,
+        Value::Map(_) => 8,
+        Value::List(_) => 9,
+    };
+
+    (kind, value.to_string())
+}
+
+/// A token in an expression string, with placeholders swapped out for what
+/// they refer to.
+#[derive(Debug)]
+enum Resolved<'a> {
+    Name(&'a str),
+    Value(&'a AttributeValue),
+    Token(TokenKind, &'a str),
+}
+
+impl PartialEq for Resolved<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Name(a), Self::Name(b)) => a == b,
+            (Self::Value(a), Self::Value(b)) => attribute_values_eq(a, b),
+            (Self::Token(a_kind, a), Self::Token(b_kind, b)) => a_kind == b_kind && a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Expression {
+    /// Whether the two expressions are equal, other than the placeholders
+    /// chosen for their [expression attribute names][1] and [values][2].
+    ///
+    /// Attribute names are compared whether or not they're behind a
+    /// placeholder, and sets are compared regardless of the order of their
+    /// elements.
+    ///
+    /// ```
+    /// use dynamodb_expression::{Expression, Num, Path};
+    ///
+    /// let a = Expression::builder()
+    ///     .with_condition(
+    ///         Path::new_name("name")
+    ///             .attribute_exists()
+    ///             .and(Path::new_name("age").greater_than(Num::new(21))),
+    ///     )
+    ///     .build();
+    ///
+    /// let b = Expression::builder()
+    ///     .with_projection(["age"])
+    ///     .with_condition(
+    ///         Path::new_name("name")
+    ///             .attribute_exists()
+    ///             .and(Path::new_name("age").greater_than(Num::new(21))),
+    ///     )
+    ///     .build();
+    ///
+    /// // "attribute_exists(#0) AND #1 > :0" vs. "attribute_exists(#1) AND #0 > :0"
+    /// assert_ne!(a.condition_expression, b.condition_expression);
+    /// // `b` also has a projection expression.
+    /// assert!(!a.is_equivalent(&b));
+    ///
+    /// let b = Expression {
+    ///     projection_expression: None,
+    ///     ..b
+    /// };
+    /// assert!(a.is_equivalent(&b));
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.ExpressionAttributeNames.html
+    /// [2]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.ExpressionAttributeValues.html
+    pub fn is_equivalent(&self, other: &Self) -> bool {
+        let names = |expression: &Self| {
+            expression
+                .expression_attribute_names
+                .as_ref()
+                .map(HashMap::len)
+                .unwrap_or_default()
+        };
+        let values = |expression: &Self| {
+            expression
+                .expression_attribute_values
+                .as_ref()
+                .map(HashMap::len)
+                .unwrap_or_default()
+        };
+
+        names(self) == names(other)
+            && values(self) == values(other)
+            && self
+                .expressions()
+                .zip_longest(other.expressions())
+                .all(|pair| {
+                    pair.both().is_some_and(|((a_kind, a), (b_kind, b))| {
+                        a_kind == b_kind && self.resolve(a).eq(other.resolve(b))
+                    })
+                })
+    }
+
+    fn resolve<'a>(&'a self, expression: &'a str) -> impl Iterator<Item = Resolved<'a>> {
+        tokenize(expression).into_iter().map(|token| {
+            match token.kind {
+                TokenKind::Name => self
+                    .expression_attribute_names
+                    .as_ref()
+                    .and_then(|names| names.get(token.text))
+                    .map(|name| Resolved::Name(name)),
+                TokenKind::Value => self
+                    .expression_attribute_values
+                    .as_ref()
+                    .and_then(|values| values.get(token.text))
+                    .map(Resolved::Value),
+                TokenKind::Ident => {
+                    // Possibly an attribute name used without a placeholder.
+                    Some(Resolved::Name(token.text))
+                }
+                _ => None,
+            }
+            .unwrap_or(Resolved::Token(token.kind, token.text))
+        })
+    }
+}
+
+/// Compares [`AttributeValue`]s, ignoring the order of elements in sets.
+fn attribute_values_eq(a: &AttributeValue, b: &AttributeValue) -> bool {
+    fn sets_eq<T>(a: &[T], b: &[T]) -> bool
+    where
+        T: Ord,
+    {
+        a.len() == b.len() && a.iter().sorted().eq(b.iter().sorted())
+    }
+
+    match (a, b) {
+        (AttributeValue::Ss(a), AttributeValue::Ss(b)) => sets_eq(a, b),
+        (AttributeValue::Ns(a), AttributeValue::Ns(b)) => sets_eq(a, b),
+        (AttributeValue::Bs(a), AttributeValue::Bs(b)) => sets_eq(
+            &a.iter().map(AsRef::as_ref).collect_vec(),
+            &b.iter().map(AsRef::as_ref).collect_vec(),
+        ),
+        (AttributeValue::L(a), AttributeValue::L(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| attribute_values_eq(a, b))
+        }
+        (AttributeValue::M(a), AttributeValue::M(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(k, a)| b.get(k).is_some_and(|b| attribute_values_eq(a, b)))
+        }
+        (a, b) => a == b,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        expression::NameStrategy,
+        value::{Num, Set, StringSet},
+        Expression, Path,
+    };
+
+    fn expression(a_first: bool) -> Expression {
+        let a = Path::new_name("a").equal(Num::new(1));
+        let b = Path::new_name("b").equal("two");
+        let (first, second) = if a_first { (a, b) } else { (b, a) };
+
+        Expression::builder()
+            .with_condition(first)
+            .with_filter(second)
+            .with_canonical_placeholders()
+            .build()
+    }
+
+    #[test]
+    fn canonical_placeholders() {
+        let expected = expression(true);
+        assert_eq!(Some(String::from("#0 = :1")), expected.condition_expression);
+        assert_eq!(Some(String::from("#1 = :0")), expected.filter_expression);
+        assert_eq!(
+            Some(
+                [
+                    (String::from(":0"), AttributeValue::S("two".into())),
+                    (String::from(":1"), AttributeValue::N("1".into())),
+                ]
+                .into()
+            ),
+            Expression::builder()
+                .with_condition(Path::new_name("b").equal(Num::new(1)))
+                .with_filter(Path::new_name("a").equal("two"))
+                .with_canonical_placeholders()
+                .build()
+                .expression_attribute_values,
+            "Strings sort before numbers"
+        );
+
+        let swapped = expression(false);
+        assert_eq!(Some(String::from("#1 = :0")), swapped.condition_expression);
+        assert_eq!(Some(String::from("#0 = :1")), swapped.filter_expression);
+        assert_eq!(
+            expected.expression_attribute_names,
+            swapped.expression_attribute_names
+        );
+        assert_eq!(
+            expected.expression_attribute_values,
+            swapped.expression_attribute_values
+        );
+    }
+
+    #[test]
+    fn canonical_with_name_strategy() {
+        let expression = Expression::builder()
+            .with_condition(
+                Path::new_name("status")
+                    .equal("b")
+                    .and(Path::new_name("age").equal("a")),
+            )
+            .with_canonical_placeholders()
+            .with_name_strategy(NameStrategy::WhenNeeded)
+            .build();
+
+        assert_eq!(
+            Some(String::from("#status = :1 AND age = :0")),
+            expression.condition_expression
+        );
+    }
+
+    #[test]
+    fn is_equivalent() {
+        let a = Expression::builder()
+            .with_filter(Path::new_name("name").contains("x"))
+            .with_update(Path::new_name("tags").add(Set::from(StringSet::from(["a", "b"]))))
+            .build();
+        let b = Expression::builder()
+            .with_projection(["name", "tags"])
+            .with_filter(Path::new_name("name").contains("x"))
+            .with_update(Path::new_name("tags").add(Set::from(StringSet::from(["a", "b"]))))
+            .with_name_strategy(NameStrategy::Readable)
+            .build();
+        let b = Expression {
+            projection_expression: None,
+            ..b
+        };
+        assert!(a.is_equivalent(&b));
+
+        let mut b = b;
+        b.expression_attribute_values
+            .as_mut()
+            .unwrap()
+            .values_mut()
+            .for_each(|value| {
+                if let AttributeValue::Ss(set) = value {
+                    set.reverse();
+                }
+            });
+        assert!(a.is_equivalent(&b), "Set order shouldn't matter");
+
+        let c = Expression::builder()
+            .with_filter(Path::new_name("name").contains("x"))
+            .with_update(Path::new_name("tags").add(Set::from(StringSet::from(["a", "c"]))))
+            .build();
+        assert!(!a.is_equivalent(&c));
+
+        let d = Expression::builder()
+            .with_condition(Path::new_name("name").contains("x"))
+            .with_update(Path::new_name("tags").add(Set::from(StringSet::from(["a", "b"]))))
+            .build();
+        assert!(!a.is_equivalent(&d), "Different kind of expression");
+    }
+}
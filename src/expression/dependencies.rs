@@ -0,0 +1,559 @@
+use core::{fmt, mem};
+use std::{collections::BTreeSet, error::Error};
+
+use crate::{
+    condition::Condition,
+    key::KeyCondition,
+    path::{Element, FieldName, Name, NameRef, Path},
+    update::{SetAction, Update},
+    visit::Visit,
+};
+
+/// The attributes an expression reads and writes.
+///
+/// Useful for caching, and for writing [`dynamodb:Attributes`][1] IAM
+/// policies. Get one from [`Builder::dependencies`], or from the parts of one
+/// with `Dependencies::from`.
+///
+/// The paths used by conditions, filters, key conditions, and projections, as
+/// well as the source paths of `SET` actions (e.g., `b` in `SET a = b + 1`),
+/// are reads. The targets of `SET`, `REMOVE`, `ADD`, and `DELETE` actions are
+/// writes.
+///
+/// ```
+/// use dynamodb_expression::{Expression, Num, Path};
+/// # use pretty_assertions::assert_eq;
+///
+/// let dependencies = Expression::builder()
+///     .with_condition(Path::new_name("version").equal(Num::new(3)))
+///     .with_update(
+///         Path::new_name("count")
+///             .math()
+///             .add(1)
+///             .and("meta.updated".parse::<Path>().unwrap().assign("today")),
+///     )
+///     .dependencies();
+///
+/// assert_eq!(
+///     vec!["count", "version"],
+///     dependencies.reads().iter().map(ToString::to_string).collect::<Vec<_>>()
+/// );
+/// assert_eq!(
+///     vec!["count", "meta.updated"],
+///     dependencies.sets().iter().map(ToString::to_string).collect::<Vec<_>>()
+/// );
+/// assert_eq!(vec!["count", "meta", "version"], dependencies.attributes());
+/// assert!(dependencies.overlaps().is_empty());
+/// ```
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/specifying-conditions.html
+/// [`Builder::dependencies`]: crate::expression::Builder::dependencies
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Dependencies {
+    reads: BTreeSet<Path>,
+    sets: BTreeSet<Path>,
+    removes: BTreeSet<Path>,
+    adds: BTreeSet<Path>,
+    deletes: BTreeSet<Path>,
+
+    /// Every written path, in order, including repeats.
+    targets: Vec<Path>,
+}
+
+impl Dependencies {
+    /// The paths that are read.
+    pub fn reads(&self) -> &BTreeSet<Path> {
+        &self.reads
+    }
+
+    /// The paths assigned by `SET` actions.
+    pub fn sets(&self) -> &BTreeSet<Path> {
+        &self.sets
+    }
+
+    /// The paths removed by `REMOVE` actions.
+    pub fn removes(&self) -> &BTreeSet<Path> {
+        &self.removes
+    }
+
+    /// The paths updated by `ADD` actions.
+    pub fn adds(&self) -> &BTreeSet<Path> {
+        &self.adds
+    }
+
+    /// The paths updated by `DELETE` actions.
+    pub fn deletes(&self) -> &BTreeSet<Path> {
+        &self.deletes
+    }
+
+    /// All of the paths that are written, by any kind of action.
+    pub fn writes(&self) -> BTreeSet<&Path> {
+        self.sets
+            .iter()
+            .chain(&self.removes)
+            .chain(&self.adds)
+            .chain(&self.deletes)
+            .collect()
+    }
+
+    /// The names of the top-level attributes that are read or written, sorted.
+    ///
+    /// Expression attribute names (e.g., `#name`) that weren't supplied with
+    /// [`Builder::with_name`] are included as they are.
+    ///
+    /// [`Builder::with_name`]: crate::expression::Builder::with_name
+    pub fn attributes(&self) -> Vec<String> {
+        self.reads
+            .iter()
+            .chain(self.writes())
+            .filter_map(|path| path.elements.first())
+            .map(|element| match element {
+                Element::Name(name) => name.name.clone(),
+                Element::IndexedField(field) => field.name.to_string(),
+                Element::NameRef(name_ref) => name_ref.to_string(),
+            })
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Every pair of written paths that DynamoDB would reject because they
+    /// overlap (e.g., `a` and `a.b`, or `a` written twice) or conflict (e.g.,
+    /// `a[0]` and `a.b`).
+    ///
+    /// ```
+    /// use dynamodb_expression::{
+    ///     expression::{Dependencies, OverlapKind},
+    ///     update::Update,
+    ///     Path,
+    /// };
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let update = Update::from(
+    ///     "a.b".parse::<Path>().unwrap().assign("w")
+    ///         .and("a".parse::<Path>().unwrap().assign("x"))
+    ///         .and("c[0]".parse::<Path>().unwrap().assign("y"))
+    ///         .and("c.d".parse::<Path>().unwrap().assign("z")),
+    /// );
+    ///
+    /// let overlaps = Dependencies::from(&update).overlaps();
+    /// assert_eq!(2, overlaps.len());
+    /// assert_eq!("a", overlaps[0].first().to_string());
+    /// assert_eq!("a.b", overlaps[0].second().to_string());
+    /// assert_eq!(OverlapKind::Overlap, overlaps[0].kind());
+    /// assert_eq!(OverlapKind::Conflict, overlaps[1].kind());
+    /// ```
+    pub fn overlaps(&self) -> Vec<Overlap> {
+        let mut overlaps = Vec::new();
+        for (i, first) in self.targets.iter().enumerate() {
+            for second in &self.targets[i + 1..] {
+                if let Some(kind) = overlap(first, second) {
+                    let (first, second) = if first <= second {
+                        (first, second)
+                    } else {
+                        (second, first)
+                    };
+
+                    overlaps.push(Overlap {
+                        first: first.clone(),
+                        second: second.clone(),
+                        kind,
+                    });
+                }
+            }
+        }
+
+        overlaps.sort_unstable();
+        overlaps
+    }
+
+    /// Returns an error listing every [overlap][Self::overlaps], if there
+    /// are any.
+    pub fn check_overlaps(&self) -> Result<(), OverlapError> {
+        let overlaps = self.overlaps();
+        if overlaps.is_empty() {
+            Ok(())
+        } else {
+            Err(OverlapError { overlaps })
+        }
+    }
+
+    /// Adds the reads and writes from `other` to this.
+    pub fn extend(&mut self, other: Dependencies) {
+        self.reads.extend(other.reads);
+        self.sets.extend(other.sets);
+        self.removes.extend(other.removes);
+        self.adds.extend(other.adds);
+        self.deletes.extend(other.deletes);
+        self.targets.extend(other.targets);
+    }
+
+    pub(super) fn add_projection<'a, I>(&mut self, names: I)
+    where
+        I: IntoIterator<Item = &'a Name>,
+    {
+        self.reads
+            .extend(names.into_iter().cloned().map(Path::from));
+    }
+
+    /// Replaces each [`Element`] of every path with the result of `f`.
+    pub(super) fn map_elements<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Element) -> Element,
+    {
+        for paths in [
+            &mut self.reads,
+            &mut self.sets,
+            &mut self.removes,
+            &mut self.adds,
+            &mut self.deletes,
+        ] {
+            *paths = mem::take(paths)
+                .into_iter()
+                .map(|path| path.elements.into_iter().map(&mut f).collect())
+                .collect();
+        }
+
+        self.targets = mem::take(&mut self.targets)
+            .into_iter()
+            .map(|path| path.elements.into_iter().map(&mut f).collect())
+            .collect();
+    }
+}
+
+impl From<&Condition> for Dependencies {
+    fn from(condition: &Condition) -> Self {
+        let mut dependencies = Self::default();
+        dependencies.visit_condition(condition);
+        dependencies
+    }
+}
+
+impl From<&KeyCondition> for Dependencies {
+    fn from(key_condition: &KeyCondition) -> Self {
+        let mut dependencies = Self::default();
+        dependencies.visit_key_condition(key_condition);
+        dependencies
+    }
+}
+
+impl From<&Update> for Dependencies {
+    fn from(update: &Update) -> Self {
+        let mut dependencies = Self::default();
+        dependencies.visit_update(update);
+        dependencies
+    }
+}
+
+/// Paths found in conditions are reads. Updates sort their paths into the
+/// kinds of writes.
+impl Visit for Dependencies {
+    fn visit_update(&mut self, update: &Update) {
+        if let Some(set) = &update.set {
+            set.actions
+                .iter()
+                .for_each(|action| self.visit_set_action(action));
+        }
+
+        if let Some(remove) = &update.remove {
+            self.removes.extend(remove.paths.iter().cloned());
+            self.targets.extend(remove.paths.iter().cloned());
+        }
+
+        for add in &update.add {
+            self.adds.insert(add.path.clone());
+            self.targets.push(add.path.clone());
+        }
+
+        for delete in &update.delete {
+            self.deletes.insert(delete.path.clone());
+            self.targets.push(delete.path.clone());
+        }
+    }
+
+    fn visit_set_action(&mut self, action: &SetAction) {
+        let (dst, src) = match action {
+            SetAction::Assign(action) => {
+                self.sets.insert(action.path.clone());
+                self.targets.push(action.path.clone());
+                return;
+            }
+            SetAction::Math(action) => (&action.dst, &action.src),
+            SetAction::ListAppend(action) => (&action.dst, &action.src),
+            SetAction::IfNotExists(action) => (&action.dst, &action.src),
+        };
+
+        // Without a source path, these read from the destination path.
+        self.reads.insert(src.as_ref().unwrap_or(dst).clone());
+        self.sets.insert(dst.clone());
+        self.targets.push(dst.clone());
+    }
+
+    fn visit_path(&mut self, path: &Path) {
+        self.reads.insert(path.clone());
+    }
+}
+
+/// Two written paths that DynamoDB won't accept in the same expression.
+///
+/// See [`Dependencies::overlaps`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Overlap {
+    first: Path,
+    second: Path,
+    kind: OverlapKind,
+}
+
+impl Overlap {
+    /// The path that sorts first.
+    pub fn first(&self) -> &Path {
+        &self.first
+    }
+
+    /// The path that sorts second.
+    pub fn second(&self) -> &Path {
+        &self.second
+    }
+
+    /// How the paths overlap.
+    pub fn kind(&self) -> OverlapKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for Overlap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            first,
+            second,
+            kind,
+        } = self;
+
+        write!(f, "two document paths {kind}: `{first}` and `{second}`")
+    }
+}
+
+/// See [`Overlap::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OverlapKind {
+    /// The paths are the same, or one is inside the other. E.g., `a` and
+    /// `a.b`.
+    Overlap,
+
+    /// The paths treat the same attribute as both a list and a map. E.g.,
+    /// `a[0]` and `a.b`.
+    Conflict,
+}
+
+impl fmt::Display for OverlapKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Overlap => "overlap",
+            Self::Conflict => "conflict",
+        })
+    }
+}
+
+/// Returned by [`Update::check_overlaps`] and [`Builder::check_overlaps`]
+/// when DynamoDB would reject an update expression because its paths
+/// [overlap][Dependencies::overlaps].
+///
+/// [`Builder::check_overlaps`]: crate::expression::Builder::check_overlaps
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverlapError {
+    overlaps: Vec<Overlap>,
+}
+
+impl OverlapError {
+    /// The pairs of paths that overlap.
+    pub fn overlaps(&self) -> &[Overlap] {
+        &self.overlaps
+    }
+
+    /// Consumes the error, returning the pairs of paths that overlap.
+    pub fn into_overlaps(self) -> Vec<Overlap> {
+        self.overlaps
+    }
+}
+
+impl fmt::Display for OverlapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        self.overlaps.iter().try_for_each(|overlap| {
+            if first {
+                first = false;
+            } else {
+                f.write_str("; ")?;
+            }
+
+            overlap.fmt(f)
+        })
+    }
+}
+
+impl Error for OverlapError {}
+
+/// A step along a [`Path`], with the indexes of an [`IndexedField`] split
+/// out so paths can be compared one step at a time.
+///
+/// [`IndexedField`]: crate::path::IndexedField
+#[derive(PartialEq)]
+enum Step<'a> {
+    Name(&'a Name),
+    NameRef(&'a NameRef),
+    Index(usize),
+}
+
+fn steps(path: &Path) -> impl Iterator<Item = Step<'_>> {
+    path.elements.iter().flat_map(|element| {
+        let (name, indexes) = match element {
+            Element::Name(name) => (Step::Name(name), &[][..]),
+            Element::NameRef(name_ref) => (Step::NameRef(name_ref), &[][..]),
+            Element::IndexedField(field) => {
+                let name = match &field.name {
+                    FieldName::Name(name) => Step::Name(name),
+                    FieldName::NameRef(name_ref) => Step::NameRef(name_ref),
+                };
+
+                (name, field.indexes.as_slice())
+            }
+        };
+
+        core::iter::once(name).chain(indexes.iter().copied().map(Step::Index))
+    })
+}
+
+/// How two paths overlap, if they do.
+pub(crate) fn overlap(first: &Path, second: &Path) -> Option<OverlapKind> {
+    let mut first = steps(first);
+    let mut second = steps(second);
+
+    loop {
+        match (first.next(), second.next()) {
+            // One path is the same as, or inside, the other.
+            (None, _) | (_, None) => return Some(OverlapKind::Overlap),
+            (Some(a), Some(b)) if a == b => {}
+            (Some(Step::Index(_)), Some(Step::Index(_))) => return None,
+            (Some(Step::Index(_)), Some(_)) | (Some(_), Some(Step::Index(_))) => {
+                return Some(OverlapKind::Conflict)
+            }
+            _ => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        condition::Condition,
+        expression::Expression,
+        path::{Name, NameRef},
+        update::{Remove, Update},
+        value::{Ref, StringSet},
+        Num, Path,
+    };
+
+    use super::{overlap, Dependencies, OverlapKind};
+
+    fn paths<'a, I>(paths: I) -> Vec<String>
+    where
+        I: IntoIterator<Item = &'a Path>,
+    {
+        paths.into_iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn builder() {
+        let dependencies = Expression::builder()
+            .with_key_condition(Path::new_name("id").key().equal(Ref::new("id")))
+            .with_filter(
+                Condition::from(
+                    "a.b[1]"
+                        .parse::<Path>()
+                        .unwrap()
+                        .size()
+                        .greater_than(Num::new(2)),
+                )
+                .and(Path::from(NameRef::from("n")).attribute_exists()),
+            )
+            .with_projection(["c", "d"])
+            .with_name(NameRef::from("n"), Name::from("name"))
+            .dependencies();
+
+        assert_eq!(
+            vec!["a.b[1]", "c", "d", "id", "name"],
+            paths(dependencies.reads())
+        );
+        assert!(dependencies.writes().is_empty());
+        assert_eq!(vec!["a", "c", "d", "id", "name"], dependencies.attributes());
+    }
+
+    #[test]
+    fn update() {
+        let set = Dependencies::from(&Update::from(
+            Path::new_name("a")
+                .math()
+                .src(Path::new_name("b"))
+                .sub(1)
+                .and(Path::new_name("c").list_append().list(["x"]))
+                .and(Path::new_name("d").if_not_exists().assign(Num::new(0)))
+                .and(Path::new_name("e").assign("x")),
+        ));
+        assert_eq!(vec!["b", "c", "d"], paths(set.reads()));
+        assert_eq!(vec!["a", "c", "d", "e"], paths(set.sets()));
+
+        let mut all = set;
+        all.extend(Dependencies::from(&Update::from(Remove::from_iter([
+            Path::new_name("g"),
+            Path::new_name("h"),
+        ]))));
+        all.extend(Dependencies::from(&Update::from(
+            Path::new_name("i").add(Num::new(1)),
+        )));
+        all.extend(Dependencies::from(&Update::from(
+            Path::new_name("j").delete(StringSet::from(["x"])),
+        )));
+        assert_eq!(vec!["g", "h"], paths(all.removes()));
+        assert_eq!(vec!["i"], paths(all.adds()));
+        assert_eq!(vec!["j"], paths(all.deletes()));
+        assert_eq!(
+            vec!["a", "c", "d", "e", "g", "h", "i", "j"],
+            paths(all.writes())
+        );
+    }
+
+    #[test]
+    fn overlaps() {
+        let check = |a: &str, b: &str| {
+            let a = a.parse::<Path>().unwrap();
+            let b = b.parse::<Path>().unwrap();
+            let result = overlap(&a, &b);
+            assert_eq!(result, overlap(&b, &a), "{a} and {b}");
+            result
+        };
+
+        assert_eq!(Some(OverlapKind::Overlap), check("a", "a"));
+        assert_eq!(Some(OverlapKind::Overlap), check("a", "a.b"));
+        assert_eq!(Some(OverlapKind::Overlap), check("a", "a[0]"));
+        assert_eq!(Some(OverlapKind::Overlap), check("a[0]", "a[0][1].b"));
+        assert_eq!(Some(OverlapKind::Conflict), check("a[0]", "a.b"));
+        assert_eq!(Some(OverlapKind::Conflict), check("a.b[1]", "a.b.c"));
+        assert_eq!(None, check("a", "b"));
+        assert_eq!(None, check("a.b", "a.c"));
+        assert_eq!(None, check("a[0]", "a[1].b"));
+        assert_eq!(None, check("ab", "a.b"));
+
+        let dependencies = Dependencies::from(&Update::from(Remove::from_iter([
+            Path::new_name("a"),
+            "a.b".parse().unwrap(),
+        ])));
+        let overlaps = dependencies.overlaps();
+        assert_eq!(1, overlaps.len());
+        assert_eq!(
+            "two document paths overlap: `a` and `a.b`",
+            overlaps[0].to_string()
+        );
+    }
+}
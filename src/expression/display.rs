@@ -0,0 +1,822 @@
+use core::fmt::{self, Write};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use super::{
+    lexer::{tokenize, Token, TokenKind},
+    Expression, ExpressionKind,
+};
+use crate::{
+    indent::{Indented, INDENT},
+    path::{Element, Path},
+    value::Value,
+};
+
+impl Expression {
+    /// Displays the expression strings with the [expression attribute
+    /// names][1] and [values][2] substituted in, for local debugging. E.g.,
+    /// `condition expression: age >= 21`.
+    ///
+    /// Values are shown in full, so don't use this where values may be
+    /// sensitive. See [`Expression::display_redacted`] for that.
+    ///
+    /// Since it implements [`Display`], it can be recorded as a `tracing`
+    /// field with the `%` sigil.
+    ///
+    /// ```
+    /// use dynamodb_expression::{Expression, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_condition(Path::new_name("age").greater_than_or_equal(Num::new(21)))
+    ///     .with_update(Path::new_name("email").assign("jane@example.com"))
+    ///     .build();
+    /// assert_eq!(
+    ///     r#"condition expression: age >= 21; update expression: SET email = "jane@example.com""#,
+    ///     expression.display_inline().to_string()
+    /// );
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.ExpressionAttributeNames.html
+    /// [2]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.ExpressionAttributeValues.html
+    /// [`Display`]: core::fmt::Display
+    pub fn display_inline(&self) -> InlineDisplay<'_> {
+        InlineDisplay { expression: self }
+    }
+
+    /// Displays the expression strings with the [expression attribute
+    /// names][1] substituted in, and the [values][2] redacted to only their
+    /// type and length, for production logs. E.g.,
+    /// `condition expression: email = <S len=16>`.
+    ///
+    /// Every value is redacted. Values used with
+    /// [sensitive paths](RedactedDisplay::with_sensitive_path) are redacted
+    /// further, to only their type.
+    ///
+    /// Since it implements [`Display`], it can be recorded as a `tracing`
+    /// field with the `%` sigil.
+    ///
+    /// ```
+    /// use dynamodb_expression::{Expression, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_condition(Path::new_name("age").greater_than_or_equal(Num::new(21)))
+    ///     .with_update(Path::new_name("email").assign("jane@example.com"))
+    ///     .build();
+    /// assert_eq!(
+    ///     "condition expression: age >= <N>; update expression: SET email = <S len=16>",
+    ///     expression.display_redacted().to_string()
+    /// );
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.ExpressionAttributeNames.html
+    /// [2]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.ExpressionAttributeValues.html
+    /// [`Display`]: core::fmt::Display
+    pub fn display_redacted(&self) -> RedactedDisplay<'_> {
+        RedactedDisplay {
+            expression: self,
+            sensitive: Vec::new(),
+        }
+    }
+
+    /// Displays the expression strings, as with the [`Display`] of
+    /// [`Expression`], followed by the [expression attribute names][1] and
+    /// [values][2] they use.
+    ///
+    /// Values are shown in full, so don't use this where values may be
+    /// sensitive.
+    ///
+    /// ```
+    /// use dynamodb_expression::{Expression, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_condition(
+    ///         Path::new_name("age")
+    ///             .greater_than_or_equal(Num::new(21))
+    ///             .and(Path::new_name("status").in_(["ACTIVE", "PENDING"])),
+    ///     )
+    ///     .build();
+    /// assert_eq!(
+    ///     "condition expression:\n  \
+    ///        #0 >= :0\n  \
+    ///        AND #1 IN (\n    \
+    ///          :1,\n    \
+    ///          :2\n  \
+    ///        )\n\
+    ///     names:\n  \
+    ///       #0 = age\n  \
+    ///       #1 = status\n\
+    ///     values:\n  \
+    ///       :0 = 21\n  \
+    ///       :1 = \"ACTIVE\"\n  \
+    ///       :2 = \"PENDING\"",
+    ///     format!("{:#}", expression.display_with_tables())
+    /// );
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.ExpressionAttributeNames.html
+    /// [2]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.ExpressionAttributeValues.html
+    /// [`Display`]: core::fmt::Display
+    pub fn display_with_tables(&self) -> TablesDisplay<'_> {
+        TablesDisplay { expression: self }
+    }
+}
+
+/// Shows each of the expression strings, with their kind. E.g.,
+/// `condition expression: #0 >= :0; update expression: SET #1 = :1`.
+///
+/// The alternate form (`{:#}`) puts each expression string on its own lines,
+/// breaking lines and indenting at `AND` and `OR`, parentheses, `IN` lists,
+/// update clauses, and update actions.
+///
+/// ```
+/// use dynamodb_expression::{Expression, Num, Path};
+/// # use pretty_assertions::assert_eq;
+///
+/// let expression = Expression::builder()
+///     .with_filter(
+///         Path::new_name("age")
+///             .greater_than_or_equal(Num::new(21))
+///             .and(
+///                 Path::new_name("status")
+///                     .equal("ACTIVE")
+///                     .or(Path::new_name("vip").equal(true))
+///                     .parenthesize(),
+///             ),
+///     )
+///     .build();
+/// assert_eq!(
+///     "filter expression: #0 >= :0 AND (#1 = :1 OR #2 = :2)",
+///     expression.to_string()
+/// );
+/// assert_eq!(
+///     "filter expression:\n  \
+///        #0 >= :0\n  \
+///        AND (\n    \
+///          #1 = :1\n    \
+///          OR #2 = :2\n  \
+///        )",
+///     format!("{expression:#}")
+/// );
+/// ```
+///
+/// See also: [`Expression::display_with_tables`], [`Expression::display_inline`],
+/// [`Expression::display_redacted`]
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (kind, text)) in self.expressions().enumerate() {
+            if f.alternate() {
+                if i > 0 {
+                    f.write_char('\n')?;
+                }
+
+                writeln!(f, "{kind}:")?;
+                f.write_str(INDENT)?;
+                write_pretty(&mut Indented::new(f), kind, text)?;
+            } else {
+                if i > 0 {
+                    f.write_str("; ")?;
+                }
+
+                write!(f, "{kind}: {text}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Displays an [`Expression`] with its values substituted in.
+///
+/// See: [`Expression::display_inline`]
+#[derive(Debug, Clone, Copy)]
+pub struct InlineDisplay<'a> {
+    expression: &'a Expression,
+}
+
+impl fmt::Display for InlineDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_expressions(f, self.expression, |tokens| {
+            vec![Shown::Value; tokens.len()]
+        })
+    }
+}
+
+/// Displays an [`Expression`] with its values redacted.
+///
+/// See: [`Expression::display_redacted`]
+#[derive(Debug, Clone)]
+pub struct RedactedDisplay<'a> {
+    expression: &'a Expression,
+    sensitive: Vec<Path>,
+}
+
+impl RedactedDisplay<'_> {
+    /// Marks the path as sensitive. Values used with a sensitive path (or one
+    /// nested in it, or one it's nested in) are redacted to only their type,
+    /// without their length. The rest are still redacted to their type and
+    /// length.
+    ///
+    /// A value is used with a path if they're in the same comparison,
+    /// function, or update action. E.g., `:0` and `:1` in
+    /// `#0 BETWEEN :0 AND :1` and `SET #0 = list_append(#0, :1)`.
+    ///
+    /// ```
+    /// use dynamodb_expression::{Expression, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_condition(Path::new_name("age").greater_than_or_equal(Num::new(21)))
+    ///     .with_update(Path::new_name("email").assign("jane@example.com"))
+    ///     .build();
+    /// assert_eq!(
+    ///     "condition expression: age >= <N>; update expression: SET email = <S>",
+    ///     expression
+    ///         .display_redacted()
+    ///         .with_sensitive_path(Path::new_name("email"))
+    ///         .to_string()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_sensitive_path<P>(mut self, path: P) -> Self
+    where
+        P: Into<Path>,
+    {
+        self.sensitive.push(path.into());
+
+        self
+    }
+}
+
+impl fmt::Display for RedactedDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sensitive = self.sensitive.iter().map(segments).collect::<Vec<_>>();
+        write_expressions(f, self.expression, |tokens| {
+            sensitive_values(self.expression, tokens, &sensitive)
+                .into_iter()
+                .map(|sensitive| {
+                    if sensitive {
+                        Shown::Type
+                    } else {
+                        Shown::TypeAndLength
+                    }
+                })
+                .collect()
+        })
+    }
+}
+
+/// Displays an [`Expression`] along with its expression attribute names and
+/// values.
+///
+/// See: [`Expression::display_with_tables`]
+#[derive(Debug, Clone, Copy)]
+pub struct TablesDisplay<'a> {
+    expression: &'a Expression,
+}
+
+impl fmt::Display for TablesDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.expression.fmt(f)?;
+
+        let names = self
+            .expression
+            .expression_attribute_names
+            .iter()
+            .flatten()
+            .map(|(placeholder, name)| (placeholder, name as &dyn fmt::Display))
+            .collect();
+        write_table(f, "names", names)?;
+
+        let values = self
+            .expression
+            .expression_attribute_values
+            .iter()
+            .flatten()
+            .map(|(placeholder, value)| (placeholder, Value::try_from(value.clone())))
+            .collect::<Vec<_>>();
+        let values = values
+            .iter()
+            .map(|(placeholder, value)| {
+                let value = match value {
+                    Ok(value) => value as &dyn fmt::Display,
+                    Err(_) => &"<unknown>",
+                };
+
+                (*placeholder, value)
+            })
+            .collect();
+        write_table(f, "values", values)
+    }
+}
+
+/// Writes the placeholders and what they stand for, in order, if there are
+/// any.
+fn write_table(
+    f: &mut fmt::Formatter<'_>,
+    title: &str,
+    mut rows: Vec<(&String, &dyn fmt::Display)>,
+) -> fmt::Result {
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    // So `#10` comes after `#9`.
+    rows.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+
+    if f.alternate() {
+        write!(f, "\n{title}:")?;
+        rows.into_iter()
+            .try_for_each(|(placeholder, value)| write!(f, "\n{INDENT}{placeholder} = {value}"))
+    } else {
+        write!(f, "; {title}: ")?;
+        rows.into_iter()
+            .enumerate()
+            .try_for_each(|(i, (placeholder, value))| {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+
+                write!(f, "{placeholder} = {value}")
+            })
+    }
+}
+
+/// Writes the expression string with line breaks and indentation. See the
+/// [`Display`] of [`Expression`].
+///
+/// [`Display`]: core::fmt::Display
+fn write_pretty<W>(w: &mut W, kind: ExpressionKind, text: &str) -> fmt::Result
+where
+    W: Write,
+{
+    let newline = |w: &mut W, depth: usize| {
+        w.write_char('\n')?;
+        (0..depth).try_for_each(|_| w.write_str(INDENT))
+    };
+
+    let tokens = tokenize(text);
+    let mut written = 0;
+    let mut depth = 0;
+    let mut parens = Vec::new();
+    let mut between = false;
+    let mut pending = None;
+    for (i, token) in tokens.iter().enumerate() {
+        let mut line_break = pending.take();
+        match token.kind {
+            // The `AND` of a `BETWEEN`.
+            TokenKind::Ident if token.is_keyword("AND") && between => between = false,
+            TokenKind::Ident if token.is_keyword("AND") || token.is_keyword("OR") => {
+                line_break = Some(depth)
+            }
+            TokenKind::Ident if token.is_keyword("BETWEEN") => between = true,
+            TokenKind::Ident
+                if kind == ExpressionKind::Update
+                    && i > 0
+                    && parens.is_empty()
+                    && ["SET", "REMOVE", "ADD", "DELETE"]
+                        .iter()
+                        .any(|clause| token.is_keyword(clause)) =>
+            {
+                line_break = Some(0)
+            }
+            TokenKind::CloseParen => {
+                if let Some(Paren::Group | Paren::In) = parens.pop() {
+                    depth -= 1;
+                    line_break = Some(depth);
+                }
+            }
+            _ => {}
+        }
+
+        match line_break {
+            Some(depth) => newline(w, depth)?,
+            None => w.write_str(&text[written..token.offset])?,
+        }
+        w.write_str(token.text)?;
+        written = token.offset + token.text.len();
+
+        match token.kind {
+            TokenKind::OpenParen => {
+                let paren = Paren::of(&tokens, i);
+                parens.push(paren);
+                if paren != Paren::Arguments {
+                    depth += 1;
+                    pending = Some(depth);
+                }
+            }
+            TokenKind::Comma => match parens.last() {
+                Some(Paren::In) => pending = Some(depth),
+                None if kind == ExpressionKind::Update => pending = Some(1),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    w.write_str(&text[written..])
+}
+
+/// How much of a value to show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shown {
+    /// The value in full.
+    Value,
+    /// Only the type of the value, and its length where it has one.
+    TypeAndLength,
+    /// Only the type of the value.
+    Type,
+}
+
+/// Writes each of the expression strings, with their kind, with placeholders
+/// replaced. `shown` gives, for each token of an expression string, how much
+/// to show if it's a value.
+fn write_expressions<F>(
+    f: &mut fmt::Formatter<'_>,
+    expression: &Expression,
+    shown: F,
+) -> fmt::Result
+where
+    F: Fn(&[Token<'_>]) -> Vec<Shown>,
+{
+    for (i, (kind, text)) in expression.expressions().enumerate() {
+        if i > 0 {
+            f.write_str("; ")?;
+        }
+
+        write!(f, "{kind}: ")?;
+        let tokens = tokenize(text);
+        let shown = shown(&tokens);
+        let mut written = 0;
+        for (token, shown) in tokens.iter().zip(shown) {
+            let name = || {
+                expression
+                    .expression_attribute_names
+                    .as_ref()?
+                    .get(token.text)
+            };
+            let value = || {
+                expression
+                    .expression_attribute_values
+                    .as_ref()?
+                    .get(token.text)
+            };
+
+            match token.kind {
+                TokenKind::Name => {
+                    let Some(name) = name() else { continue };
+                    f.write_str(&text[written..token.offset])?;
+                    f.write_str(name)?;
+                }
+                TokenKind::Value => {
+                    let Some(value) = value() else { continue };
+                    f.write_str(&text[written..token.offset])?;
+                    match shown {
+                        Shown::Value => write_value(f, value)?,
+                        Shown::TypeAndLength => write_redacted(f, value, true)?,
+                        Shown::Type => write_redacted(f, value, false)?,
+                    }
+                }
+                _ => continue,
+            }
+
+            written = token.offset + token.text.len();
+        }
+
+        f.write_str(&text[written..])?;
+    }
+
+    Ok(())
+}
+
+fn write_value(f: &mut fmt::Formatter<'_>, value: &AttributeValue) -> fmt::Result {
+    match Value::try_from(value.clone()) {
+        Ok(value) => write!(f, "{value}"),
+        Err(_) => f.write_str("<unknown>"),
+    }
+}
+
+/// Writes only the type of the value, and its length where it has one if
+/// `with_len` is set.
+fn write_redacted(
+    f: &mut fmt::Formatter<'_>,
+    value: &AttributeValue,
+    with_len: bool,
+) -> fmt::Result {
+    let (type_key, len) = match value {
+        AttributeValue::S(s) => ("S", Some(s.len())),
+        AttributeValue::N(_) => ("N", None),
+        AttributeValue::B(b) => ("B", Some(b.as_ref().len())),
+        AttributeValue::Bool(_) => ("BOOL", None),
+        AttributeValue::Null(_) => ("NULL", None),
+        AttributeValue::Ss(ss) => ("SS", Some(ss.len())),
+        AttributeValue::Ns(ns) => ("NS", Some(ns.len())),
+        AttributeValue::Bs(bs) => ("BS", Some(bs.len())),
+        AttributeValue::L(l) => ("L", Some(l.len())),
+        AttributeValue::M(m) => ("M", Some(m.len())),
+        _ => ("unknown", None),
+    };
+
+    f.write_char('<')?;
+    f.write_str(type_key)?;
+    if let Some(len) = len.filter(|_| with_len) {
+        write!(f, " len={len}")?;
+    }
+    f.write_char('>')
+}
+
+/// An element of a path, for comparing paths in an expression string with
+/// [`Path`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Name(String),
+    Index(usize),
+}
+
+fn segments(path: &Path) -> Vec<Segment> {
+    path.elements
+        .iter()
+        .flat_map(|element| match element {
+            Element::Name(name) => vec![Segment::Name(name.name.clone())],
+            Element::IndexedField(field) => [Segment::Name(field.name.to_string())]
+                .into_iter()
+                .chain(field.indexes.iter().copied().map(Segment::Index))
+                .collect(),
+            Element::NameRef(name) => vec![Segment::Name(name.to_string())],
+        })
+        .collect()
+}
+
+/// Keywords that separate the parts of an expression that values belong to.
+const SEPARATORS: [&str; 7] = ["AND", "OR", "NOT", "SET", "REMOVE", "ADD", "DELETE"];
+
+/// Whether the token is one of the keywords of expression syntax, rather than
+/// an attribute name or function name.
+fn is_keyword(token: &Token<'_>) -> bool {
+    SEPARATORS
+        .iter()
+        .chain(&["BETWEEN", "IN"])
+        .any(|keyword| token.is_keyword(keyword))
+}
+
+/// What an open parenthesis in an expression string is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Paren {
+    /// Grouping conditions.
+    Group,
+    /// The arguments of a function.
+    Arguments,
+    /// The list of an `IN` condition.
+    In,
+}
+
+impl Paren {
+    /// What the open parenthesis at `tokens[i]` is for.
+    fn of(tokens: &[Token<'_>], i: usize) -> Self {
+        match i.checked_sub(1).map(|previous| &tokens[previous]) {
+            Some(previous) if previous.is_keyword("IN") => Self::In,
+            Some(previous) if previous.kind == TokenKind::Ident && !is_keyword(previous) => {
+                Self::Arguments
+            }
+            _ => Self::Group,
+        }
+    }
+}
+
+/// Whether each token of an expression string is a value used with one of the
+/// sensitive paths.
+///
+/// The expression is split into parts at logical operators, update clauses,
+/// and commas between update actions. A value is used with every path in the
+/// same part.
+fn sensitive_values(
+    expression: &Expression,
+    tokens: &[Token<'_>],
+    sensitive: &[Vec<Segment>],
+) -> Vec<bool> {
+    // Where each part starts. A part ends where the next starts.
+    let mut starts = vec![0];
+    let mut parens = Vec::new();
+    let mut between = false;
+    for (i, token) in tokens.iter().enumerate() {
+        match token.kind {
+            TokenKind::OpenParen => parens.push(Paren::of(tokens, i)),
+            TokenKind::CloseParen => {
+                parens.pop();
+            }
+            TokenKind::Comma if matches!(parens.last(), None | Some(Paren::Group)) => {
+                starts.push(i)
+            }
+            TokenKind::Ident if token.is_keyword("BETWEEN") => between = true,
+            // The `AND` of a `BETWEEN`.
+            TokenKind::Ident if token.is_keyword("AND") && between => between = false,
+            TokenKind::Ident if SEPARATORS.iter().any(|keyword| token.is_keyword(keyword)) => {
+                starts.push(i)
+            }
+            _ => {}
+        }
+    }
+
+    let resolve = |token: &Token<'_>| {
+        let name = match token.kind {
+            TokenKind::Name => expression
+                .expression_attribute_names
+                .as_ref()
+                .and_then(|names| names.get(token.text))
+                .map(String::as_str),
+            _ => None,
+        };
+
+        Segment::Name(name.unwrap_or(token.text).to_owned())
+    };
+
+    let mut redact = vec![false; tokens.len()];
+    let ends = starts.iter().skip(1).copied().chain([tokens.len()]);
+    for (start, end) in starts.iter().copied().zip(ends) {
+        let part = &tokens[start..end];
+
+        let mut paths = Vec::new();
+        let mut i = 0;
+        while i < part.len() {
+            let token = &part[i];
+            let is_path = match token.kind {
+                TokenKind::Name => true,
+                TokenKind::Ident => {
+                    !is_keyword(token)
+                        && part
+                            .get(i + 1)
+                            .is_none_or(|next| next.kind != TokenKind::OpenParen)
+                }
+                _ => false,
+            };
+            i += 1;
+            if !is_path {
+                continue;
+            }
+
+            let mut path = vec![resolve(token)];
+            loop {
+                match &part[i..] {
+                    [dot, name, ..]
+                        if dot.kind == TokenKind::Dot
+                            && matches!(name.kind, TokenKind::Ident | TokenKind::Name) =>
+                    {
+                        path.push(resolve(name));
+                        i += 2;
+                    }
+                    [open, index, close, ..]
+                        if open.kind == TokenKind::OpenBracket
+                            && index.kind == TokenKind::Number
+                            && close.kind == TokenKind::CloseBracket =>
+                    {
+                        path.push(Segment::Index(index.text.parse().unwrap_or(usize::MAX)));
+                        i += 3;
+                    }
+                    _ => break,
+                }
+            }
+
+            paths.push(path);
+        }
+
+        let is_sensitive = paths.iter().any(|path| {
+            sensitive
+                .iter()
+                .any(|sensitive| path.starts_with(sensitive) || sensitive.starts_with(path))
+        });
+        if is_sensitive {
+            redact[start..end].fill(true);
+        }
+    }
+
+    redact
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        update::Update,
+        value::{List, Map, Set},
+        Expression, Num, Path, Value,
+    };
+
+    fn expression() -> Expression {
+        Expression::builder()
+            .with_condition(
+                Path::new_name("age")
+                    .between(Num::new(18), Num::new(65))
+                    .and(Path::new_name("email").begins_with("jane"))
+                    .and(
+                        Path::new_name("status")
+                            .in_(["A", "B"])
+                            .or("address.city".parse::<Path>().unwrap().equal("Boston"))
+                            .parenthesize(),
+                    ),
+            )
+            .with_update(
+                Update::from(
+                    Path::new_name("tags")
+                        .list_append()
+                        .list(["x", "y"])
+                        .and(Path::new_name("count").assign(Num::new(1))),
+                )
+                .and(Path::new_name("codes").add(Set::new_num_set([1, 2, 3]))),
+            )
+            .build()
+    }
+
+    #[test]
+    fn inline() {
+        assert_eq!(
+            "condition expression: age BETWEEN 18 AND 65 AND begins_with(email, \"jane\") \
+            AND (status IN (\"A\",\"B\") OR address.city = \"Boston\"); \
+            update expression: SET tags = list_append(tags, [\"x\", \"y\"]), count = 1 \
+            ADD codes [1, 2, 3]",
+            expression().display_inline().to_string()
+        );
+    }
+
+    #[test]
+    fn pretty() {
+        let expression = expression();
+        assert_eq!(
+            "condition expression:\n  \
+               #0 BETWEEN :0 AND :1\n  \
+               AND begins_with(#1, :2)\n  \
+               AND (\n    \
+                 #2 IN (\n      \
+                   :3,\n      \
+                   :4\n    \
+                 )\n    \
+                 OR #3.#4 = :5\n  \
+               )\n\
+            update expression:\n  \
+              SET #5 = list_append(#5, :6),\n    \
+                #6 = :7\n  \
+              ADD #7 :8",
+            format!("{expression:#}")
+        );
+
+        assert_eq!(
+            "condition expression: #0 BETWEEN :0 AND :1 AND begins_with(#1, :2) \
+            AND (#2 IN (:3,:4) OR #3.#4 = :5); \
+            update expression: SET #5 = list_append(#5, :6), #6 = :7 ADD #7 :8; \
+            names: #0 = age, #1 = email, #2 = status, #3 = address, #4 = city, \
+            #5 = tags, #6 = count, #7 = codes; \
+            values: :0 = 18, :1 = 65, :2 = \"jane\", :3 = \"A\", :4 = \"B\", \
+            :5 = \"Boston\", :6 = [\"x\", \"y\"], :7 = 1, :8 = [1, 2, 3]",
+            expression.display_with_tables().to_string()
+        );
+        assert!(format!("{:#}", expression.display_with_tables()).ends_with(
+            "ADD #7 :8\n\
+            names:\n  #0 = age\n  #1 = email\n  #2 = status\n  #3 = address\n  \
+            #4 = city\n  #5 = tags\n  #6 = count\n  #7 = codes\n\
+            values:\n  :0 = 18\n  :1 = 65\n  :2 = \"jane\"\n  :3 = \"A\"\n  :4 = \"B\"\n  \
+            :5 = \"Boston\"\n  :6 = [\"x\", \"y\"]\n  :7 = 1\n  :8 = [1, 2, 3]"
+        ));
+    }
+
+    #[test]
+    fn redacted() {
+        assert_eq!(
+            "condition expression: age BETWEEN <N> AND <N> AND begins_with(email, <S len=4>) \
+            AND (status IN (<S len=1>,<S len=1>) OR address.city = <S len=6>); \
+            update expression: SET tags = list_append(tags, <L len=2>), count = <N> \
+            ADD codes <NS len=3>",
+            expression().display_redacted().to_string()
+        );
+
+        // Sensitive paths only hide more. The rest are still redacted.
+        assert_eq!(
+            "condition expression: age BETWEEN <N> AND <N> AND begins_with(email, <S len=4>) \
+            AND (status IN (<S len=1>,<S len=1>) OR address.city = <S>); \
+            update expression: SET tags = list_append(tags, <L>), count = <N> \
+            ADD codes <NS len=3>",
+            expression()
+                .display_redacted()
+                .with_sensitive_path(Path::new_name("age"))
+                .with_sensitive_path("address.city.name".parse::<Path>().unwrap())
+                .with_sensitive_path(Path::new_name("tags"))
+                .to_string()
+        );
+
+        let expression = Expression::builder()
+            .with_update(
+                Path::new_name("a")
+                    .assign(Map::from([("b", Value::new_binary([1, 2]))]))
+                    .and(
+                        Path::new_name("c")
+                            .assign(List::from([Value::new_bool(true), Value::new_null()])),
+                    ),
+            )
+            .build();
+        assert_eq!(
+            "update expression: SET a = <M len=1>, c = <L len=2>",
+            expression.display_redacted().to_string()
+        );
+    }
+}
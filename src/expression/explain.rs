@@ -0,0 +1,172 @@
+use core::fmt;
+use std::{collections::HashMap, error::Error};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use super::{Expression, ParseError};
+use crate::{
+    condition::Explanation,
+    value::{Map, UnknownAttributeValueError, Value},
+};
+
+impl Expression {
+    /// Explains why the [condition expression][1] was true or false for an
+    /// item, such as the old item DynamoDB returns with a
+    /// `ConditionalCheckFailedException` when the request sets
+    /// [`ReturnValuesOnConditionCheckFailure::AllOld`][2].
+    ///
+    /// The condition expression is parsed back from the string and evaluated
+    /// in memory, using [`Condition::explain`]. If the item didn't exist,
+    /// DynamoDB doesn't return one, so pass an empty item.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use aws_sdk_dynamodb::types::AttributeValue;
+    /// use dynamodb_expression::{Expression, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_condition(
+    ///         Path::new_name("version")
+    ///             .equal(Num::new(3))
+    ///             .and(Path::new_name("status").in_(["active", "pending"])),
+    ///     )
+    ///     .build();
+    ///
+    /// // The item returned with the `ConditionalCheckFailedException`.
+    /// let old_item = HashMap::from([
+    ///     ("version".to_string(), AttributeValue::N("4".into())),
+    ///     ("status".to_string(), AttributeValue::S("active".into())),
+    /// ]);
+    ///
+    /// let explanation = expression.explain(&old_item).unwrap();
+    /// assert!(!explanation.result());
+    /// assert_eq!(
+    ///     "false: version = 3 AND status IN (\"active\",\"pending\")\n  \
+    ///        false: version = 3\n    \
+    ///          version: 4\n  \
+    ///        true: status IN (\"active\",\"pending\")\n    \
+    ///          status: \"active\"\n",
+    ///     explanation.to_string()
+    /// );
+    /// ```
+    ///
+    /// [1]: Self::condition_expression
+    /// [2]: aws_sdk_dynamodb::types::ReturnValuesOnConditionCheckFailure::AllOld
+    /// [`Condition::explain`]: crate::condition::Condition::explain
+    pub fn explain(
+        &self,
+        item: &HashMap<String, AttributeValue>,
+    ) -> Result<Explanation, ExplainError> {
+        let condition = self.parse_condition()?.ok_or(ExplainError::NoCondition)?;
+
+        let item = item
+            .iter()
+            .map(|(name, value)| Value::try_from(value.clone()).map(|value| (name, value)))
+            .collect::<Result<Map, _>>()?;
+
+        Ok(condition
+            .explain(&item)
+            .expect("parsing resolves all attribute names and values"))
+    }
+}
+
+/// An error explaining an [`Expression`]'s condition.
+///
+/// See: [`Expression::explain`]
+#[derive(Debug)]
+pub enum ExplainError {
+    /// The [`Expression`] has no condition expression.
+    NoCondition,
+
+    /// The condition expression couldn't be parsed.
+    Parse(ParseError),
+
+    /// The item has a value of a type this crate doesn't know.
+    UnknownAttributeValue(UnknownAttributeValueError),
+}
+
+impl fmt::Display for ExplainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoCondition => f.write_str("the expression has no condition expression"),
+            Self::Parse(err) => write!(f, "invalid condition expression: {err}"),
+            Self::UnknownAttributeValue(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for ExplainError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::NoCondition => None,
+            Self::Parse(err) => Some(err),
+            Self::UnknownAttributeValue(err) => Some(err),
+        }
+    }
+}
+
+impl From<ParseError> for ExplainError {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl From<UnknownAttributeValueError> for ExplainError {
+    fn from(err: UnknownAttributeValueError) -> Self {
+        Self::UnknownAttributeValue(err)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use pretty_assertions::assert_eq;
+
+    use crate::{Expression, Num, Path};
+
+    use super::ExplainError;
+
+    #[test]
+    fn explain() {
+        let expression = Expression::builder()
+            .with_condition(
+                Path::new_name("pk")
+                    .attribute_not_exists()
+                    .or(Path::new_name("expires")
+                        .less_than(Num::new(1_700_000_000))
+                        .parenthesize()),
+            )
+            .build();
+
+        let item = HashMap::from([
+            ("pk".to_string(), AttributeValue::S("a".into())),
+            (
+                "expires".to_string(),
+                AttributeValue::N("1800000000".into()),
+            ),
+        ]);
+        let explanation = expression.explain(&item).unwrap();
+        assert!(!explanation.result());
+        assert_eq!(2, explanation.children().len());
+
+        let explanation = expression.explain(&HashMap::new()).unwrap();
+        assert!(explanation.result());
+        assert_eq!(
+            "true: attribute_not_exists(pk) OR (expires < 1700000000)\n  \
+               true: attribute_not_exists(pk)\n    \
+                 pk: missing\n  \
+               false: expires < 1700000000\n    \
+                 expires: missing\n",
+            explanation.to_string()
+        );
+
+        assert!(matches!(
+            Expression::builder().build().explain(&item),
+            Err(ExplainError::NoCondition)
+        ));
+    }
+}
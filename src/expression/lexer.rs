@@ -0,0 +1,172 @@
+//! A small tokenizer for DynamoDB expression strings.
+
+/// The kinds of tokens that appear in a DynamoDB expression string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    /// A bare word. An attribute name, keyword, or function name.
+    Ident,
+    /// An expression attribute name placeholder. E.g., `#0`
+    Name,
+    /// An expression attribute value placeholder. E.g., `:0`
+    Value,
+    /// A run of digits, as in a list index.
+    Number,
+    /// A double-quoted string.
+    String,
+    /// A comparison or arithmetic operator. One of `=`, `<>`, `<`, `<=`, `>`,
+    /// `>=`, `+`, or `-`.
+    Operator,
+    OpenParen,
+    CloseParen,
+    OpenBracket,
+    CloseBracket,
+    Comma,
+    Dot,
+    /// Anything else.
+    Other,
+}
+
+/// A token in a DynamoDB expression string, along with where it was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    /// The byte offset of the token in the expression string.
+    pub offset: usize,
+}
+
+impl Token<'_> {
+    /// Whether this is the (case-insensitive) keyword.
+    pub fn is_keyword(&self, keyword: &str) -> bool {
+        self.kind == TokenKind::Ident && self.text.eq_ignore_ascii_case(keyword)
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Splits a DynamoDB expression string into tokens. Never fails; anything
+/// unrecognized becomes a [`TokenKind::Other`] token.
+pub(crate) fn tokenize(expression: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let mut end = start + c.len_utf8();
+
+        let kind = match c {
+            c if c.is_whitespace() => continue,
+            '#' | ':' if chars.peek().is_some_and(|&(_, c)| is_word_char(c)) => {
+                while let Some(&(i, c)) = chars.peek() {
+                    if !is_word_char(c) {
+                        break;
+                    }
+
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+
+                if c == '#' {
+                    TokenKind::Name
+                } else {
+                    TokenKind::Value
+                }
+            }
+            c if is_word_char(c) => {
+                while let Some(&(i, c)) = chars.peek() {
+                    if !is_word_char(c) {
+                        break;
+                    }
+
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+
+                if c.is_ascii_digit() {
+                    TokenKind::Number
+                } else {
+                    TokenKind::Ident
+                }
+            }
+            '"' => {
+                let mut escaped = false;
+                for (i, c) in chars.by_ref() {
+                    end = i + c.len_utf8();
+
+                    match c {
+                        '\\' if !escaped => escaped = true,
+                        '"' if !escaped => break,
+                        _ => escaped = false,
+                    }
+                }
+
+                TokenKind::String
+            }
+            '<' | '>' => {
+                if let Some(&(i, next)) = chars.peek() {
+                    if next == '=' || (c == '<' && next == '>') {
+                        end = i + next.len_utf8();
+                        chars.next();
+                    }
+                }
+
+                TokenKind::Operator
+            }
+            '=' | '+' | '-' => TokenKind::Operator,
+            '(' => TokenKind::OpenParen,
+            ')' => TokenKind::CloseParen,
+            '[' => TokenKind::OpenBracket,
+            ']' => TokenKind::CloseBracket,
+            ',' => TokenKind::Comma,
+            '.' => TokenKind::Dot,
+            _ => TokenKind::Other,
+        };
+
+        tokens.push(Token {
+            kind,
+            text: &expression[start..end],
+            offset: start,
+        });
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{tokenize, TokenKind::*};
+
+    #[test]
+    fn tokens() {
+        let tokens = tokenize(r#"#0[3].foo <> :v_1 AND size(bar)>=:1 OR "a \"b\"""#)
+            .into_iter()
+            .map(|token| (token.kind, token.text))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                (Name, "#0"),
+                (OpenBracket, "["),
+                (Number, "3"),
+                (CloseBracket, "]"),
+                (Dot, "."),
+                (Ident, "foo"),
+                (Operator, "<>"),
+                (Value, ":v_1"),
+                (Ident, "AND"),
+                (Ident, "size"),
+                (OpenParen, "("),
+                (Ident, "bar"),
+                (CloseParen, ")"),
+                (Operator, ">="),
+                (Value, ":1"),
+                (Ident, "OR"),
+                (String, r#""a \"b\"""#),
+            ],
+            tokens
+        );
+    }
+}
@@ -0,0 +1,686 @@
+use core::fmt;
+use std::{
+    collections::{BTreeSet, HashMap},
+    error::Error,
+};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use itertools::Itertools;
+
+use super::{
+    lexer::{tokenize, Token, TokenKind},
+    Expression,
+};
+
+/// The maximum length, in bytes, of any single expression string.
+///
+/// See: [DynamoDB expression parameters limits][1]
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/ServiceQuotas.html#limits-expression-parameters
+pub const MAX_EXPRESSION_LEN: usize = 4 * 1024;
+
+/// The maximum number of operators or functions allowed in a single expression.
+///
+/// See: [DynamoDB expression parameters limits][1]
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/ServiceQuotas.html#limits-expression-parameters
+pub const MAX_OPERATORS: usize = 300;
+
+/// The maximum number of operands for the `IN` comparator.
+///
+/// See: [DynamoDB expression parameters limits][1]
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/ServiceQuotas.html#limits-expression-parameters
+pub const MAX_IN_OPERANDS: usize = 100;
+
+/// The maximum length, in bytes, of an attribute name.
+///
+/// See: [DynamoDB attribute name limits][1]
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/ServiceQuotas.html#limits-attributes
+pub const MAX_ATTRIBUTE_NAME_LEN: usize = 64 * 1024;
+
+/// Identifies one of the expression strings in an [`Expression`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExpressionKind {
+    /// [`Expression::condition_expression`]
+    Condition,
+    /// [`Expression::key_condition_expression`]
+    KeyCondition,
+    /// [`Expression::update_expression`]
+    Update,
+    /// [`Expression::filter_expression`]
+    Filter,
+    /// [`Expression::projection_expression`]
+    Projection,
+}
+
+impl fmt::Display for ExpressionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Condition => "condition expression",
+            Self::KeyCondition => "key condition expression",
+            Self::Update => "update expression",
+            Self::Filter => "filter expression",
+            Self::Projection => "projection expression",
+        })
+    }
+}
+
+/// A single DynamoDB limit or rule that an [`Expression`] violates.
+///
+/// See: [`LimitError`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LimitViolation {
+    /// The expression string is longer than [`MAX_EXPRESSION_LEN`] bytes.
+    ExpressionTooLong { kind: ExpressionKind, len: usize },
+
+    /// The expression contains more than [`MAX_OPERATORS`] operators and
+    /// functions.
+    TooManyOperators { kind: ExpressionKind, count: usize },
+
+    /// An `IN` comparison in the expression has more than [`MAX_IN_OPERANDS`]
+    /// operands.
+    TooManyInOperands { kind: ExpressionKind, count: usize },
+
+    /// The attribute name behind this expression attribute name placeholder
+    /// (e.g., `#0`) is longer than [`MAX_ATTRIBUTE_NAME_LEN`] bytes.
+    AttributeNameTooLong { placeholder: String, len: usize },
+
+    /// The value behind this expression attribute value placeholder (e.g.,
+    /// `:0`) is, or contains, an empty set.
+    EmptySet { placeholder: String },
+
+    /// The value behind this expression attribute value placeholder (e.g.,
+    /// `:0`) is an empty string or binary value used in the key condition.
+    EmptyKeyValue { placeholder: String },
+
+    /// The expression attribute name placeholder (e.g., `#pk`) is used in an
+    /// expression, but isn't in the expression attribute names.
+    ///
+    /// See also: [`NameRef`], [`Builder::with_name`]
+    ///
+    /// [`NameRef`]: crate::path::NameRef
+    /// [`Builder::with_name`]: super::Builder::with_name
+    UnresolvedName { placeholder: String },
+
+    /// The expression attribute value placeholder (e.g., `:pk`) is used in an
+    /// expression, but isn't in the expression attribute values.
+    ///
+    /// See also: [`Ref`], [`Builder::with_value`]
+    ///
+    /// [`Ref`]: crate::value::Ref
+    /// [`Builder::with_value`]: super::Builder::with_value
+    UnresolvedValue { placeholder: String },
+
+    /// The expression attribute names contain this placeholder, but it isn't
+    /// used in any expression.
+    UnusedName { placeholder: String },
+
+    /// The expression attribute values contain this placeholder, but it isn't
+    /// used in any expression.
+    UnusedValue { placeholder: String },
+}
+
+impl fmt::Display for LimitViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExpressionTooLong { kind, len } => write!(
+                f,
+                "{kind} is {len} bytes long; the maximum is {MAX_EXPRESSION_LEN}"
+            ),
+            Self::TooManyOperators { kind, count } => write!(
+                f,
+                "{kind} has {count} operators and functions; the maximum is {MAX_OPERATORS}"
+            ),
+            Self::TooManyInOperands { kind, count } => write!(
+                f,
+                "{kind} has an IN with {count} operands; the maximum is {MAX_IN_OPERANDS}"
+            ),
+            Self::AttributeNameTooLong { placeholder, len } => write!(
+                f,
+                "attribute name for {placeholder} is {len} bytes long; \
+                    the maximum is {MAX_ATTRIBUTE_NAME_LEN}"
+            ),
+            Self::EmptySet { placeholder } => {
+                write!(f, "value for {placeholder} contains an empty set")
+            }
+            Self::UnresolvedName { placeholder } => {
+                write!(f, "{placeholder} is not in the expression attribute names")
+            }
+            Self::UnresolvedValue { placeholder } => {
+                write!(f, "{placeholder} is not in the expression attribute values")
+            }
+            Self::UnusedName { placeholder } => {
+                write!(f, "expression attribute name {placeholder} is not used")
+            }
+            Self::UnusedValue { placeholder } => {
+                write!(f, "expression attribute value {placeholder} is not used")
+            }
+            Self::EmptyKeyValue { placeholder } => {
+                write!(
+                    f,
+                    "value for {placeholder} is empty but is used in a key condition"
+                )
+            }
+        }
+    }
+}
+
+/// Returned by [`Builder::try_build`] and [`Expression::check_limits`] when an
+/// [`Expression`] would be rejected by DynamoDB. Lists every violated limit.
+///
+/// [`Builder::try_build`]: super::Builder::try_build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitError {
+    violations: Vec<LimitViolation>,
+}
+
+impl LimitError {
+    /// The limits that were violated.
+    pub fn violations(&self) -> &[LimitViolation] {
+        &self.violations
+    }
+
+    /// Consumes the error, returning the limits that were violated.
+    pub fn into_violations(self) -> Vec<LimitViolation> {
+        self.violations
+    }
+}
+
+impl fmt::Display for LimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expression exceeds DynamoDB limits: ")?;
+
+        let mut first = true;
+        self.violations.iter().try_for_each(|violation| {
+            if first {
+                first = false;
+            } else {
+                f.write_str("; ")?;
+            }
+
+            violation.fmt(f)
+        })
+    }
+}
+
+impl Error for LimitError {}
+
+impl Expression {
+    /// The combined length, in bytes, of all of the expression strings. This
+    /// does not include the expression attribute names or values.
+    ///
+    /// DynamoDB limits each expression string to [`MAX_EXPRESSION_LEN`] bytes.
+    /// Use [`Expression::check_limits`] to check each of them individually.
+    ///
+    /// ```
+    /// use dynamodb_expression::{Expression, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_condition(Path::new_name("age").greater_than(Num::new(21)))
+    ///     .build();
+    ///
+    /// // "#0 > :0"
+    /// assert_eq!(7, expression.byte_len());
+    /// ```
+    pub fn byte_len(&self) -> usize {
+        self.expressions()
+            .map(|(_kind, expression)| expression.len())
+            .sum()
+    }
+
+    /// The combined number of operators and functions in all of the expression
+    /// strings, counted the way DynamoDB counts them.
+    ///
+    /// DynamoDB limits each expression to [`MAX_OPERATORS`] operators and
+    /// functions. Use [`Expression::check_limits`] to check each of them
+    /// individually.
+    ///
+    /// ```
+    /// use dynamodb_expression::{Expression, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_condition(
+    ///         Path::new_name("name")
+    ///             .attribute_exists()
+    ///             .and(Path::new_name("age").between(Num::new(18), Num::new(65))),
+    ///     )
+    ///     .build();
+    ///
+    /// // `attribute_exists`, `AND`, and `BETWEEN`
+    /// assert_eq!(3, expression.operator_count());
+    /// ```
+    pub fn operator_count(&self) -> usize {
+        self.expressions()
+            .map(|(kind, expression)| count_operators(kind, &tokenize(expression)))
+            .sum()
+    }
+
+    /// Checks this against the DynamoDB [expression limits][1], returning
+    /// every limit that was violated.
+    ///
+    /// See also: [`Builder::try_build`]
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/ServiceQuotas.html#limits-expression-parameters
+    /// [`Builder::try_build`]: super::Builder::try_build
+    pub fn check_limits(&self) -> Result<(), LimitError> {
+        let mut violations = Vec::new();
+        let mut used_names = BTreeSet::new();
+        let mut used_values = BTreeSet::new();
+
+        for (kind, expression) in self.expressions() {
+            let len = expression.len();
+            if len > MAX_EXPRESSION_LEN {
+                violations.push(LimitViolation::ExpressionTooLong { kind, len });
+            }
+
+            let tokens = tokenize(expression);
+            for token in &tokens {
+                match token.kind {
+                    TokenKind::Name => {
+                        used_names.insert(token.text);
+                    }
+                    TokenKind::Value => {
+                        used_values.insert(token.text);
+                    }
+                    _ => {}
+                }
+            }
+
+            let count = count_operators(kind, &tokens);
+            if count > MAX_OPERATORS {
+                violations.push(LimitViolation::TooManyOperators { kind, count });
+            }
+
+            violations.extend(
+                in_operand_counts(&tokens)
+                    .filter(|&count| count > MAX_IN_OPERANDS)
+                    .map(|count| LimitViolation::TooManyInOperands { kind, count }),
+            );
+
+            if kind == ExpressionKind::KeyCondition {
+                let mut placeholders = tokens
+                    .iter()
+                    .filter(|token| token.kind == TokenKind::Value)
+                    .map(|token| token.text)
+                    .filter(|placeholder| {
+                        self.expression_attribute_values
+                            .as_ref()
+                            .and_then(|values| values.get(*placeholder))
+                            .is_some_and(|value| match value {
+                                AttributeValue::S(value) => value.is_empty(),
+                                AttributeValue::B(value) => value.as_ref().is_empty(),
+                                _ => false,
+                            })
+                    })
+                    .collect::<Vec<_>>();
+                placeholders.sort_unstable();
+                placeholders.dedup();
+
+                violations.extend(placeholders.into_iter().map(|placeholder| {
+                    LimitViolation::EmptyKeyValue {
+                        placeholder: placeholder.into(),
+                    }
+                }));
+            }
+        }
+
+        if let Some(names) = &self.expression_attribute_names {
+            let mut names = names
+                .iter()
+                .filter(|(_placeholder, name)| name.len() > MAX_ATTRIBUTE_NAME_LEN)
+                .collect::<Vec<_>>();
+            names.sort_unstable();
+
+            violations.extend(names.into_iter().map(|(placeholder, name)| {
+                LimitViolation::AttributeNameTooLong {
+                    placeholder: placeholder.clone(),
+                    len: name.len(),
+                }
+            }));
+        }
+
+        if let Some(values) = &self.expression_attribute_values {
+            let mut placeholders = values
+                .iter()
+                .filter(|(_placeholder, value)| contains_empty_set(value))
+                .map(|(placeholder, _value)| placeholder)
+                .collect::<Vec<_>>();
+            placeholders.sort_unstable();
+
+            violations.extend(placeholders.into_iter().map(|placeholder| {
+                LimitViolation::EmptySet {
+                    placeholder: placeholder.clone(),
+                }
+            }));
+        }
+
+        let empty_names = HashMap::new();
+        let names = self
+            .expression_attribute_names
+            .as_ref()
+            .unwrap_or(&empty_names);
+        violations.extend(
+            used_names
+                .iter()
+                .filter(|placeholder| !names.contains_key(**placeholder))
+                .map(|placeholder| LimitViolation::UnresolvedName {
+                    placeholder: (*placeholder).into(),
+                }),
+        );
+        violations.extend(
+            names
+                .keys()
+                .filter(|placeholder| !used_names.contains(placeholder.as_str()))
+                .sorted_unstable()
+                .map(|placeholder| LimitViolation::UnusedName {
+                    placeholder: placeholder.clone(),
+                }),
+        );
+
+        let empty_values = HashMap::new();
+        let values = self
+            .expression_attribute_values
+            .as_ref()
+            .unwrap_or(&empty_values);
+        violations.extend(
+            used_values
+                .iter()
+                .filter(|placeholder| !values.contains_key(**placeholder))
+                .map(|placeholder| LimitViolation::UnresolvedValue {
+                    placeholder: (*placeholder).into(),
+                }),
+        );
+        violations.extend(
+            values
+                .keys()
+                .filter(|placeholder| !used_values.contains(placeholder.as_str()))
+                .sorted_unstable()
+                .map(|placeholder| LimitViolation::UnusedValue {
+                    placeholder: placeholder.clone(),
+                }),
+        );
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(LimitError { violations })
+        }
+    }
+
+    /// The expression strings that are set.
+    pub(crate) fn expressions(&self) -> impl Iterator<Item = (ExpressionKind, &str)> {
+        [
+            (ExpressionKind::Condition, &self.condition_expression),
+            (ExpressionKind::KeyCondition, &self.key_condition_expression),
+            (ExpressionKind::Update, &self.update_expression),
+            (ExpressionKind::Filter, &self.filter_expression),
+            (ExpressionKind::Projection, &self.projection_expression),
+        ]
+        .into_iter()
+        .filter_map(|(kind, expression)| expression.as_deref().map(|expression| (kind, expression)))
+    }
+}
+
+/// Counts the operators and functions in an expression.
+///
+/// From the [DynamoDB docs][1], `SET a = :val1 + :val2 + :val3` contains two
+/// operators, so the `=` in an update expression isn't counted. Neither is the
+/// `AND` that is part of a `BETWEEN`.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/ServiceQuotas.html#limits-expression-parameters
+fn count_operators(kind: ExpressionKind, tokens: &[Token<'_>]) -> usize {
+    if kind == ExpressionKind::Projection {
+        return 0;
+    }
+
+    let mut count = 0;
+    let mut in_between = false;
+
+    for (i, token) in tokens.iter().enumerate() {
+        match token.kind {
+            TokenKind::Operator => {
+                let is_arithmetic = matches!(token.text, "+" | "-");
+                if kind == ExpressionKind::Update {
+                    if is_arithmetic {
+                        count += 1;
+                    }
+                } else if !is_arithmetic {
+                    count += 1;
+                }
+            }
+            TokenKind::Ident => {
+                if token.is_keyword("BETWEEN") {
+                    in_between = true;
+                    count += 1;
+                } else if token.is_keyword("AND") && in_between {
+                    in_between = false;
+                } else if ["AND", "OR", "NOT", "IN"]
+                    .into_iter()
+                    .any(|keyword| token.is_keyword(keyword))
+                {
+                    count += 1;
+                } else if tokens
+                    .get(i + 1)
+                    .is_some_and(|next| next.kind == TokenKind::OpenParen)
+                {
+                    // A function
+                    count += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    count
+}
+
+/// The number of operands for each `IN` in the expression.
+fn in_operand_counts<'a>(tokens: &'a [Token<'_>]) -> impl Iterator<Item = usize> + 'a {
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(_i, token)| token.is_keyword("IN"))
+        .filter_map(|(i, _token)| {
+            let mut tokens = tokens[i + 1..].iter();
+            if tokens.next()?.kind != TokenKind::OpenParen {
+                return None;
+            }
+
+            let mut depth = 0_usize;
+            let mut count = 1;
+            for token in tokens {
+                match token.kind {
+                    TokenKind::OpenParen | TokenKind::OpenBracket => depth += 1,
+                    TokenKind::CloseParen if depth == 0 => break,
+                    TokenKind::CloseParen | TokenKind::CloseBracket => {
+                        depth = depth.saturating_sub(1)
+                    }
+                    TokenKind::Comma if depth == 0 => count += 1,
+                    _ => {}
+                }
+            }
+
+            Some(count)
+        })
+}
+
+fn contains_empty_set(value: &AttributeValue) -> bool {
+    match value {
+        AttributeValue::Ss(set) => set.is_empty(),
+        AttributeValue::Ns(set) => set.is_empty(),
+        AttributeValue::Bs(set) => set.is_empty(),
+        AttributeValue::L(list) => list.iter().any(contains_empty_set),
+        AttributeValue::M(map) => map.values().any(contains_empty_set),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        path::{NameRef, Path},
+        value::{Num, NumSet, Ref, StringSet},
+        Expression,
+    };
+
+    use super::{
+        ExpressionKind, LimitViolation, MAX_ATTRIBUTE_NAME_LEN, MAX_EXPRESSION_LEN,
+        MAX_IN_OPERANDS, MAX_OPERATORS,
+    };
+
+    #[test]
+    fn operator_count() {
+        let expression = Expression::builder()
+            .with_condition(
+                Path::new_name("a")
+                    .equal(Num::new(1))
+                    .and(Path::new_name("b").in_([Num::new(1), Num::new(2)]))
+                    .or(!Path::new_name("c").begins_with("foo")),
+            )
+            .with_key_condition(
+                Path::new_name("id")
+                    .key()
+                    .equal(Num::new(42))
+                    .and(Path::new_name("sk").key().between(Num::new(1), Num::new(2))),
+            )
+            .with_update(
+                Path::new_name("d")
+                    .math()
+                    .add(7)
+                    .and(Path::new_name("e").assign(Num::new(1))),
+            )
+            .with_projection(["a", "b"])
+            .build();
+
+        // Condition: `=`, `AND`, `IN`, `OR`, `NOT`, `begins_with`
+        // Key condition: `=`, `AND`, `BETWEEN`
+        // Update: `+`
+        assert_eq!(10, expression.operator_count());
+        assert_eq!(Ok(()), expression.check_limits());
+    }
+
+    #[test]
+    fn byte_len() {
+        let expression = Expression::builder()
+            .with_condition(Path::new_name("a").equal(Num::new(1)))
+            .with_projection(["a", "b"])
+            .build();
+
+        // "#0 = :0" and "#0, #1"
+        assert_eq!(7 + 6, expression.byte_len());
+    }
+
+    #[test]
+    fn violations() {
+        let long_name = "a".repeat(MAX_ATTRIBUTE_NAME_LEN + 1);
+
+        let error = Expression::builder()
+            .with_condition(
+                Path::new_name(long_name)
+                    .attribute_exists()
+                    .and(Path::new_name("a").in_((0..=MAX_IN_OPERANDS).map(Num::new))),
+            )
+            .with_key_condition(Path::new_name("id").key().equal(""))
+            .with_filter(
+                (0..MAX_OPERATORS)
+                    .map(|n| Path::new_name("b").equal(Num::new(n + 1000)))
+                    .reduce(|a, b| a.and(b))
+                    .unwrap(),
+            )
+            .with_update(Path::new_name("c").assign(NumSet::from(Vec::<Num>::new())))
+            .try_build()
+            .unwrap_err();
+
+        let filter_len = "#3 = :102".len()
+            + (1..MAX_OPERATORS)
+                .map(|n| format!(" AND #3 = :{}", n + 102).len())
+                .sum::<usize>();
+        assert!(filter_len > MAX_EXPRESSION_LEN);
+
+        assert_eq!(
+            vec![
+                LimitViolation::TooManyInOperands {
+                    kind: ExpressionKind::Condition,
+                    count: MAX_IN_OPERANDS + 1,
+                },
+                LimitViolation::EmptyKeyValue {
+                    placeholder: ":101".into(),
+                },
+                LimitViolation::ExpressionTooLong {
+                    kind: ExpressionKind::Filter,
+                    len: filter_len,
+                },
+                LimitViolation::TooManyOperators {
+                    kind: ExpressionKind::Filter,
+                    // One `=` for each, plus the `AND`s between them.
+                    count: MAX_OPERATORS * 2 - 1,
+                },
+                LimitViolation::AttributeNameTooLong {
+                    placeholder: "#0".into(),
+                    len: MAX_ATTRIBUTE_NAME_LEN + 1,
+                },
+                LimitViolation::EmptySet {
+                    placeholder: ":402".into(),
+                },
+            ],
+            error.into_violations()
+        );
+    }
+
+    #[test]
+    fn unresolved_and_unused() {
+        let error = Expression::builder()
+            .with_condition(
+                Path::from(NameRef::new("pk"))
+                    .equal(Ref::new("pk"))
+                    .and(Path::from(NameRef::new("sk")).equal(Ref::new("sk"))),
+            )
+            .with_name("pk", "partition_key")
+            .with_name("other", "other")
+            .with_value("sk", Num::new(1))
+            .with_value("other", Num::new(2))
+            .try_build()
+            .unwrap_err();
+
+        assert_eq!(
+            vec![
+                LimitViolation::UnresolvedName {
+                    placeholder: "#sk".into()
+                },
+                LimitViolation::UnusedName {
+                    placeholder: "#other".into()
+                },
+                LimitViolation::UnresolvedValue {
+                    placeholder: ":pk".into()
+                },
+                LimitViolation::UnusedValue {
+                    placeholder: ":other".into()
+                },
+            ],
+            error.into_violations()
+        );
+    }
+
+    #[test]
+    fn empty_nested_set() {
+        let expression = Expression::builder()
+            .with_update(Path::new_name("a").assign(crate::value::List::from([
+                crate::Value::from(StringSet::from(["a"])),
+                StringSet::from(Vec::<String>::new()).into(),
+            ])))
+            .build();
+
+        assert_eq!(
+            vec![LimitViolation::EmptySet {
+                placeholder: ":0".into()
+            }],
+            expression.check_limits().unwrap_err().into_violations()
+        );
+    }
+}
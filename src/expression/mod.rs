@@ -1,7 +1,28 @@
 mod builder;
+mod canonical;
+mod dependencies;
+mod display;
+mod explain;
+mod lexer;
+mod limits;
+mod names;
+mod parse;
+mod prepared;
+mod reserved;
 mod to_aws;
 
 pub use builder::Builder;
+pub use dependencies::{Dependencies, Overlap, OverlapError, OverlapKind};
+pub use display::{InlineDisplay, RedactedDisplay, TablesDisplay};
+pub use explain::ExplainError;
+pub use limits::{
+    ExpressionKind, LimitError, LimitViolation, MAX_ATTRIBUTE_NAME_LEN, MAX_EXPRESSION_LEN,
+    MAX_IN_OPERANDS, MAX_OPERATORS,
+};
+pub use names::NameStrategy;
+pub use parse::ParseError;
+pub use prepared::{BindError, Prepared};
+pub use reserved::{is_reserved_word, RESERVED_WORDS};
 
 use std::collections::HashMap;
 
@@ -0,0 +1,249 @@
+use std::collections::{HashMap, HashSet};
+
+use super::is_reserved_word;
+use crate::{
+    fold::Fold,
+    path::Name,
+    value::{Ref, ValueOrRef},
+};
+
+/// How the [`Builder`] turns attribute names into [expression attribute names][1].
+///
+/// ```
+/// use dynamodb_expression::{expression::NameStrategy, Expression, Num, Path};
+/// # use pretty_assertions::assert_eq;
+///
+/// let condition = Path::new_name("name")
+///     .attribute_exists()
+///     .and(Path::new_name("age").greater_than(Num::new(21)))
+///     .and(Path::new_name("user-email").attribute_exists());
+///
+/// let expression = Expression::builder()
+///     .with_condition(condition.clone())
+///     .build();
+/// assert_eq!(
+///     Some(String::from(
+///         "attribute_exists(#0) AND #1 > :0 AND attribute_exists(#2)"
+///     )),
+///     expression.condition_expression,
+/// );
+///
+/// let expression = Expression::builder()
+///     .with_name_strategy(NameStrategy::Readable)
+///     .with_condition(condition.clone())
+///     .build();
+/// assert_eq!(
+///     Some(String::from(
+///         "attribute_exists(#name) AND #age > :0 AND attribute_exists(#user_email)"
+///     )),
+///     expression.condition_expression,
+/// );
+///
+/// let expression = Expression::builder()
+///     .with_name_strategy(NameStrategy::WhenNeeded)
+///     .with_condition(condition)
+///     .build();
+/// assert_eq!(
+///     Some(String::from(
+///         "attribute_exists(#name) AND age > :0 AND attribute_exists(#user_email)"
+///     )),
+///     expression.condition_expression,
+/// );
+/// assert_eq!(
+///     Some(
+///         [("#name", "name"), ("#user_email", "user-email")]
+///             .into_iter()
+///             .map(|(k, v)| (String::from(k), String::from(v)))
+///             .collect()
+///     ),
+///     expression.expression_attribute_names,
+/// );
+/// ```
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.ExpressionAttributeNames.html
+/// [`Builder`]: super::Builder
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NameStrategy {
+    /// Every attribute name is replaced with a numbered placeholder, such as
+    /// `#0`, `#1`, and so on. This is the default.
+    #[default]
+    Numbered,
+
+    /// Every attribute name is replaced with a placeholder derived from the
+    /// name. `name` becomes `#name`, and `user-email` becomes `#user_email`.
+    Readable,
+
+    /// Attribute names are only replaced with a placeholder if DynamoDB
+    /// requires it. That is, if the name is a [reserved word][1] or contains
+    /// characters that aren't allowed in an expression. Those placeholders are
+    /// the same as with [`NameStrategy::Readable`].
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/ReservedWords.html
+    WhenNeeded,
+}
+
+impl NameStrategy {
+    /// Assigns each of the names (in the order they were first seen) its
+    /// expression attribute name. `None` if the name can be used in the
+    /// expression as-is. None of the `reserved` placeholders are used.
+    pub(crate) fn placeholders<'a, I>(
+        self,
+        names: I,
+        reserved: &HashSet<String>,
+    ) -> Vec<Option<String>>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut used = reserved.clone();
+        let mut numbered = 0..;
+        let mut readable = |name: &str| {
+            let mut base = String::with_capacity(name.len() + 1);
+            base.push('#');
+            base.extend(name.chars().map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            }));
+            if base.len() == 1 {
+                base.push('_');
+            }
+
+            let mut placeholder = base.clone();
+            let mut n = 1;
+            while !used.insert(placeholder.clone()) {
+                n += 1;
+                placeholder = format!("{base}_{n}");
+            }
+
+            placeholder
+        };
+
+        names
+            .into_iter()
+            .map(|name| match self {
+                Self::Numbered => numbered
+                    .by_ref()
+                    .map(|i| format!("#{i}"))
+                    .find(|placeholder| !reserved.contains(placeholder)),
+                Self::Readable => Some(readable(name)),
+                Self::WhenNeeded => {
+                    if is_valid_unaliased(name) {
+                        None
+                    } else {
+                        Some(readable(name))
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether DynamoDB accepts the attribute name directly in an expression,
+/// without an expression attribute name.
+fn is_valid_unaliased(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !is_reserved_word(name)
+}
+
+/// Replaces the placeholders in already-processed expressions.
+#[derive(Debug, Default)]
+pub(crate) struct Rename {
+    pub names: HashMap<String, String>,
+    pub values: HashMap<Ref, Ref>,
+}
+
+impl Fold for Rename {
+    fn fold_name(&mut self, name: Name) -> Name {
+        match self.names.get(&name.name) {
+            Some(renamed) => Name::from(renamed.as_str()),
+            None => name,
+        }
+    }
+
+    fn fold_value(&mut self, value: ValueOrRef) -> ValueOrRef {
+        match value {
+            ValueOrRef::Ref(value_ref) => match self.values.get(&value_ref) {
+                Some(renamed) => renamed.clone().into(),
+                None => value_ref.into(),
+            },
+            value => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use std::collections::HashSet;
+
+    use super::NameStrategy;
+
+    #[test]
+    fn placeholders() {
+        let reserved = HashSet::new();
+        let names = [
+            "age",
+            "name",
+            "user-email",
+            "user.email",
+            "0",
+            "",
+            "user_email",
+        ];
+
+        assert_eq!(
+            vec![
+                Some("#0".into()),
+                Some("#1".into()),
+                Some("#2".into()),
+                Some("#3".into()),
+                Some("#4".into()),
+                Some("#5".into()),
+                Some("#6".into()),
+            ],
+            NameStrategy::Numbered.placeholders(names, &reserved)
+        );
+
+        assert_eq!(
+            vec![
+                Some("#age".into()),
+                Some("#name".into()),
+                Some("#user_email".into()),
+                Some("#user_email_2".into()),
+                Some("#0".into()),
+                Some("#_".into()),
+                Some("#user_email_3".into()),
+            ],
+            NameStrategy::Readable.placeholders(names, &reserved)
+        );
+
+        assert_eq!(
+            vec![
+                None,
+                Some("#name".into()),
+                Some("#user_email".into()),
+                Some("#user_email_2".into()),
+                Some("#0".into()),
+                Some("#_".into()),
+                None,
+            ],
+            NameStrategy::WhenNeeded.placeholders(names, &reserved)
+        );
+
+        let reserved = HashSet::from(["#0".to_string(), "#2".to_string(), "#age".to_string()]);
+        assert_eq!(
+            vec![Some("#1".into()), Some("#3".into()), Some("#4".into())],
+            NameStrategy::Numbered.placeholders(["a", "b", "c"], &reserved)
+        );
+        assert_eq!(
+            vec![Some("#age_2".into()), Some("#name".into())],
+            NameStrategy::Readable.placeholders(["age", "name"], &reserved)
+        );
+    }
+}
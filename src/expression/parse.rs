@@ -0,0 +1,901 @@
+//! Parsing DynamoDB expression strings back into the types in this crate.
+
+use core::fmt;
+use std::{
+    collections::{BTreeSet, HashMap},
+    error::Error,
+};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use super::{
+    lexer::{tokenize, Token, TokenKind},
+    Expression, ExpressionKind,
+};
+use crate::{
+    condition::{
+        attribute_type::Type, AttributeExists, AttributeNotExists, AttributeType, BeginsWith,
+        Between, Comparator, Comparison, Condition, Contains, In,
+    },
+    operand::{Operand, OperandType, Size},
+    path::{Element, Name, Path},
+    update::{
+        set::math::MathOp, Add, Assign, Delete, IfNotExists, ListAppend, Math, Remove, Set,
+        SetAction, Update,
+    },
+    value::{Scalar, Value},
+};
+
+/// An error parsing a DynamoDB expression string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Something other than what was expected was found at the byte offset.
+    Unexpected {
+        offset: usize,
+        found: String,
+        expected: &'static str,
+    },
+
+    /// The expression ended where more was expected.
+    UnexpectedEnd { expected: &'static str },
+
+    /// An expression attribute name (e.g., `#0`) isn't in the expression
+    /// attribute names.
+    MissingName { name: String },
+
+    /// An expression attribute value (e.g., `:0`) isn't in the expression
+    /// attribute values.
+    MissingValue { value: String },
+
+    /// An expression attribute value can't be used where it is, such as an
+    /// unknown type in `attribute_type()`.
+    InvalidValue { value: String },
+
+    /// A clause (e.g., `SET`) is used more than once in an update expression.
+    DuplicateClause { clause: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unexpected {
+                offset,
+                found,
+                expected,
+            } => write!(f, "expected {expected}, found `{found}` at offset {offset}"),
+            Self::UnexpectedEnd { expected } => {
+                write!(f, "expected {expected}, found the end of the expression")
+            }
+            Self::MissingName { name } => write!(f, "no expression attribute name for {name}"),
+            Self::MissingValue { value } => {
+                write!(f, "no expression attribute value for {value}")
+            }
+            Self::InvalidValue { value } => write!(f, "invalid expression attribute value {value}"),
+            Self::DuplicateClause { clause } => {
+                write!(
+                    f,
+                    "`{clause}` can only be used once in an update expression"
+                )
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses a condition expression, resolving the expression attribute names and
+/// values it uses.
+pub(crate) fn parse_condition(
+    expression: &str,
+    names: Option<&HashMap<String, String>>,
+    values: Option<&HashMap<String, AttributeValue>>,
+) -> Result<Condition, ParseError> {
+    let mut parser = Parser::new(expression, names, values);
+    let condition = parser.condition()?;
+    parser.end()?;
+
+    Ok(condition)
+}
+
+/// Parses an update expression, resolving the expression attribute names and
+/// values it uses.
+///
+/// Only the forms of `SET` action that [`SetAction`] can represent are
+/// supported.
+///
+/// [`SetAction`]: crate::update::SetAction
+#[cfg_attr(not(feature = "fake"), allow(dead_code))]
+pub(crate) fn parse_update(
+    expression: &str,
+    names: Option<&HashMap<String, String>>,
+    values: Option<&HashMap<String, AttributeValue>>,
+) -> Result<Update, ParseError> {
+    let mut parser = Parser::new(expression, names, values);
+    let update = parser.update()?;
+    parser.end()?;
+
+    Ok(update)
+}
+
+/// Parses a projection expression into the paths it's made of.
+#[cfg_attr(not(feature = "fake"), allow(dead_code))]
+pub(crate) fn parse_projection(
+    expression: &str,
+    names: Option<&HashMap<String, String>>,
+) -> Result<Vec<Path>, ParseError> {
+    let mut parser = Parser::new(expression, names, None);
+    let mut paths = vec![parser.path()?];
+    while parser.eat(TokenKind::Comma) {
+        paths.push(parser.path()?);
+    }
+    parser.end()?;
+
+    Ok(paths)
+}
+
+impl Expression {
+    /// Parses the condition expression, if there is one.
+    pub(crate) fn parse_condition(&self) -> Result<Option<Condition>, ParseError> {
+        self.condition_expression
+            .as_deref()
+            .map(|expression| self.parse_condition_str(expression))
+            .transpose()
+    }
+
+    /// Parses the key condition expression, if there is one.
+    #[cfg_attr(not(feature = "fake"), allow(dead_code))]
+    pub(crate) fn parse_key_condition(&self) -> Result<Option<Condition>, ParseError> {
+        self.key_condition_expression
+            .as_deref()
+            .map(|expression| self.parse_condition_str(expression))
+            .transpose()
+    }
+
+    /// Parses the filter expression, if there is one.
+    #[cfg_attr(not(feature = "fake"), allow(dead_code))]
+    pub(crate) fn parse_filter(&self) -> Result<Option<Condition>, ParseError> {
+        self.filter_expression
+            .as_deref()
+            .map(|expression| self.parse_condition_str(expression))
+            .transpose()
+    }
+
+    /// Parses the update expression, if there is one.
+    #[cfg_attr(not(feature = "fake"), allow(dead_code))]
+    pub(crate) fn parse_update(&self) -> Result<Option<Update>, ParseError> {
+        self.update_expression
+            .as_deref()
+            .map(|expression| {
+                parse_update(
+                    expression,
+                    self.expression_attribute_names.as_ref(),
+                    self.expression_attribute_values.as_ref(),
+                )
+            })
+            .transpose()
+    }
+
+    /// Parses the projection expression, if there is one.
+    #[cfg_attr(not(feature = "fake"), allow(dead_code))]
+    pub(crate) fn parse_projection(&self) -> Result<Option<Vec<Path>>, ParseError> {
+        self.projection_expression
+            .as_deref()
+            .map(|expression| {
+                parse_projection(expression, self.expression_attribute_names.as_ref())
+            })
+            .transpose()
+    }
+
+    fn parse_condition_str(&self, expression: &str) -> Result<Condition, ParseError> {
+        parse_condition(
+            expression,
+            self.expression_attribute_names.as_ref(),
+            self.expression_attribute_values.as_ref(),
+        )
+    }
+
+    /// The expression attribute names and values that aren't used by any of
+    /// the given kinds of expression, in order.
+    #[cfg_attr(not(feature = "fake"), allow(dead_code))]
+    pub(crate) fn unused_placeholders(&self, kinds: &[ExpressionKind]) -> Vec<&str> {
+        let used = self
+            .expressions()
+            .filter(|(kind, _expression)| kinds.contains(kind))
+            .flat_map(|(_kind, expression)| tokenize(expression))
+            .filter(|token| matches!(token.kind, TokenKind::Name | TokenKind::Value))
+            .map(|token| token.text)
+            .collect::<BTreeSet<_>>();
+
+        let names = self
+            .expression_attribute_names
+            .iter()
+            .flat_map(HashMap::keys);
+        let values = self
+            .expression_attribute_values
+            .iter()
+            .flat_map(HashMap::keys);
+
+        names
+            .chain(values)
+            .map(String::as_str)
+            .filter(|placeholder| !used.contains(placeholder))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+/// A recursive descent parser over the tokens of an expression.
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+    names: Option<&'a HashMap<String, String>>,
+    values: Option<&'a HashMap<String, AttributeValue>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(
+        expression: &'a str,
+        names: Option<&'a HashMap<String, String>>,
+        values: Option<&'a HashMap<String, AttributeValue>>,
+    ) -> Self {
+        Self {
+            tokens: tokenize(expression),
+            pos: 0,
+            names,
+            values,
+        }
+    }
+
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<Token<'a>> {
+        self.tokens.get(self.pos + n).copied()
+    }
+
+    fn next(&mut self, expected: &'static str) -> Result<Token<'a>, ParseError> {
+        let token = self.peek().ok_or(ParseError::UnexpectedEnd { expected })?;
+        self.pos += 1;
+
+        Ok(token)
+    }
+
+    /// Takes the next token if it's the keyword.
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.peek().is_some_and(|token| token.is_keyword(keyword));
+        if found {
+            self.pos += 1;
+        }
+
+        found
+    }
+
+    /// Takes the next token if it's of the kind.
+    fn eat(&mut self, kind: TokenKind) -> bool {
+        let found = self.peek().is_some_and(|token| token.kind == kind);
+        if found {
+            self.pos += 1;
+        }
+
+        found
+    }
+
+    fn expect(&mut self, kind: TokenKind, expected: &'static str) -> Result<Token<'a>, ParseError> {
+        let token = self.next(expected)?;
+        if token.kind == kind {
+            Ok(token)
+        } else {
+            Err(unexpected(token, expected))
+        }
+    }
+
+    /// Fails if there are any tokens left.
+    fn end(&self) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(token) => Err(unexpected(token, "the end of the expression")),
+            None => Ok(()),
+        }
+    }
+
+    /// `condition ::= and ("OR" and)*`
+    fn condition(&mut self) -> Result<Condition, ParseError> {
+        let mut condition = self.and()?;
+        while self.eat_keyword("OR") {
+            condition = condition.or(self.and()?);
+        }
+
+        Ok(condition)
+    }
+
+    /// `and ::= not ("AND" not)*`
+    fn and(&mut self) -> Result<Condition, ParseError> {
+        let mut condition = self.not()?;
+        while self.eat_keyword("AND") {
+            condition = condition.and(self.not()?);
+        }
+
+        Ok(condition)
+    }
+
+    /// `not ::= "NOT" not | primary`
+    fn not(&mut self) -> Result<Condition, ParseError> {
+        if self.eat_keyword("NOT") {
+            Ok(self.not()?.not())
+        } else {
+            self.primary()
+        }
+    }
+
+    fn primary(&mut self) -> Result<Condition, ParseError> {
+        if self.eat(TokenKind::OpenParen) {
+            let condition = self.condition()?;
+            self.expect(TokenKind::CloseParen, "`)`")?;
+
+            return Ok(condition.parenthesize());
+        }
+
+        let function = self
+            .peek()
+            .filter(|token| token.kind == TokenKind::Ident)
+            .filter(|_| {
+                self.peek_nth(1)
+                    .is_some_and(|token| token.kind == TokenKind::OpenParen)
+            })
+            .map(|token| token.text.to_ascii_lowercase());
+        match function.as_deref() {
+            Some("attribute_exists") => {
+                let path = self.function_args(|parser| parser.path())?;
+
+                Ok(Condition::AttributeExists(AttributeExists { path }))
+            }
+            Some("attribute_not_exists") => {
+                let path = self.function_args(|parser| parser.path())?;
+
+                Ok(Condition::AttributeNotExists(AttributeNotExists { path }))
+            }
+            Some("attribute_type") => {
+                let (path, attribute_type) = self.function_args(|parser| {
+                    let path = parser.path()?;
+                    parser.expect(TokenKind::Comma, "`,`")?;
+                    // The type is usually an expression attribute value, but
+                    // can also be written inline, as this crate does.
+                    const EXPECTED: &str = "an attribute type";
+                    let token = parser.next(EXPECTED)?;
+                    let attribute_type = match token.kind {
+                        TokenKind::Ident => {
+                            parse_type(token.text).ok_or_else(|| unexpected(token, EXPECTED))?
+                        }
+                        TokenKind::Value => match parser.value(token)? {
+                            Value::Scalar(Scalar::String(attribute_type)) => {
+                                parse_type(&attribute_type)
+                            }
+                            _ => None,
+                        }
+                        .ok_or_else(|| ParseError::InvalidValue {
+                            value: token.text.to_owned(),
+                        })?,
+                        _ => return Err(unexpected(token, EXPECTED)),
+                    };
+
+                    Ok((path, attribute_type))
+                })?;
+
+                Ok(Condition::AttributeType(AttributeType {
+                    path,
+                    attribute_type,
+                }))
+            }
+            Some("begins_with") => {
+                let (path, substr) = self.function_args(|parser| {
+                    let path = parser.path()?;
+                    parser.expect(TokenKind::Comma, "`,`")?;
+                    let token = parser.expect(TokenKind::Value, "a value")?;
+
+                    Ok((path, parser.value(token)?))
+                })?;
+
+                Ok(Condition::BeginsWith(BeginsWith {
+                    path,
+                    substr: substr.into(),
+                }))
+            }
+            Some("contains") => {
+                let (path, operand) = self.function_args(|parser| {
+                    let path = parser.path()?;
+                    parser.expect(TokenKind::Comma, "`,`")?;
+                    let token = parser.expect(TokenKind::Value, "a value")?;
+
+                    Ok((path, parser.value(token)?))
+                })?;
+
+                Ok(Condition::Contains(Contains {
+                    path,
+                    operand: operand.into(),
+                }))
+            }
+            _ => {
+                let op = self.operand()?;
+                self.comparison(op)
+            }
+        }
+    }
+
+    /// What follows the first operand of a comparison, `BETWEEN`, or `IN`.
+    fn comparison(&mut self, op: Operand) -> Result<Condition, ParseError> {
+        const EXPECTED: &str = "a comparator, `BETWEEN`, or `IN`";
+
+        if self.eat_keyword("BETWEEN") {
+            let lower = self.operand()?;
+            if !self.eat_keyword("AND") {
+                let token = self.next("`AND`")?;
+                return Err(unexpected(token, "`AND`"));
+            }
+            let upper = self.operand()?;
+
+            return Ok(Condition::Between(Between { op, lower, upper }));
+        }
+
+        if self.eat_keyword("IN") {
+            self.expect(TokenKind::OpenParen, "`(`")?;
+            let mut items = vec![self.operand()?];
+            while self.eat(TokenKind::Comma) {
+                items.push(self.operand()?);
+            }
+            self.expect(TokenKind::CloseParen, "`)`")?;
+
+            return Ok(Condition::In(In { op, items }));
+        }
+
+        let token = self.next(EXPECTED)?;
+        let cmp = match (token.kind, token.text) {
+            (TokenKind::Operator, "=") => Comparator::Eq,
+            (TokenKind::Operator, "<>") => Comparator::Ne,
+            (TokenKind::Operator, "<") => Comparator::Lt,
+            (TokenKind::Operator, "<=") => Comparator::Le,
+            (TokenKind::Operator, ">") => Comparator::Gt,
+            (TokenKind::Operator, ">=") => Comparator::Ge,
+            _ => return Err(unexpected(token, EXPECTED)),
+        };
+        let right = self.operand()?;
+
+        Ok(Condition::Comparison(Comparison {
+            left: op,
+            cmp,
+            right,
+        }))
+    }
+
+    /// `update ::= clause+`, where each kind of clause is used at most once.
+    #[cfg_attr(not(feature = "fake"), allow(dead_code))]
+    fn update(&mut self) -> Result<Update, ParseError> {
+        const EXPECTED: &str = "`SET`, `REMOVE`, `ADD`, or `DELETE`";
+
+        let mut update = Update {
+            set: None,
+            remove: None,
+            add: Vec::new(),
+            delete: Vec::new(),
+        };
+        let mut seen = Vec::new();
+        loop {
+            let token = self.next(EXPECTED)?;
+            let clause = token.text.to_ascii_uppercase();
+            if token.kind != TokenKind::Ident {
+                return Err(unexpected(token, EXPECTED));
+            }
+            if seen.contains(&clause) {
+                return Err(ParseError::DuplicateClause { clause });
+            }
+
+            match clause.as_str() {
+                "SET" => {
+                    let mut actions = vec![self.set_action()?];
+                    while self.eat(TokenKind::Comma) {
+                        actions.push(self.set_action()?);
+                    }
+
+                    update.set = Some(Set { actions });
+                }
+                "REMOVE" => {
+                    let mut paths = vec![self.path()?];
+                    while self.eat(TokenKind::Comma) {
+                        paths.push(self.path()?);
+                    }
+
+                    update.remove = Some(Remove { paths });
+                }
+                "ADD" | "DELETE" => loop {
+                    let path = self.path()?;
+                    let token = self.expect(TokenKind::Value, "a value")?;
+                    let value = self.value(token)?.into();
+                    if clause == "ADD" {
+                        update.add.push(Add { path, value });
+                    } else {
+                        update.delete.push(Delete {
+                            path,
+                            subset: value,
+                        });
+                    }
+
+                    if !self.eat(TokenKind::Comma) {
+                        break;
+                    }
+                },
+                _ => return Err(unexpected(token, EXPECTED)),
+            }
+
+            seen.push(clause);
+            if self.peek().is_none() {
+                return Ok(update);
+            }
+        }
+    }
+
+    /// `set_action ::= path "=" set_value`
+    #[cfg_attr(not(feature = "fake"), allow(dead_code))]
+    fn set_action(&mut self) -> Result<SetAction, ParseError> {
+        let dst = self.path()?;
+        let token = self.next("`=`")?;
+        if (token.kind, token.text) != (TokenKind::Operator, "=") {
+            return Err(unexpected(token, "`=`"));
+        }
+
+        // The source path is left out where it's the same as the destination,
+        // as the builders do.
+        let src = |dst: &Path, src: Path| (src != *dst).then_some(src);
+
+        let token = self.peek().ok_or(ParseError::UnexpectedEnd {
+            expected: "a value, path, or function",
+        })?;
+        if token.kind == TokenKind::Value {
+            self.pos += 1;
+
+            return Ok(SetAction::Assign(Assign {
+                path: dst,
+                value: self.value(token)?.into(),
+            }));
+        }
+
+        let function = self
+            .peek_nth(1)
+            .is_some_and(|next| next.kind == TokenKind::OpenParen)
+            .then(|| token.text.to_ascii_lowercase());
+        match function.as_deref() {
+            Some("if_not_exists") => {
+                let (path, value) = self.function_args(|parser| {
+                    let path = parser.path()?;
+                    parser.expect(TokenKind::Comma, "`,`")?;
+                    let token = parser.expect(TokenKind::Value, "a value")?;
+
+                    Ok((path, parser.value(token)?))
+                })?;
+
+                Ok(SetAction::IfNotExists(IfNotExists {
+                    src: src(&dst, path),
+                    dst,
+                    value: value.into(),
+                }))
+            }
+            Some("list_append") => {
+                let (path, list, after) = self.function_args(|parser| {
+                    let first = parser.peek().ok_or(ParseError::UnexpectedEnd {
+                        expected: "a path or value",
+                    })?;
+                    if first.kind == TokenKind::Value {
+                        parser.pos += 1;
+                        let list = parser.value(first)?;
+                        parser.expect(TokenKind::Comma, "`,`")?;
+
+                        Ok((parser.path()?, list, false))
+                    } else {
+                        let path = parser.path()?;
+                        parser.expect(TokenKind::Comma, "`,`")?;
+                        let token = parser.expect(TokenKind::Value, "a value")?;
+
+                        Ok((path, parser.value(token)?, true))
+                    }
+                })?;
+
+                Ok(SetAction::ListAppend(ListAppend {
+                    src: src(&dst, path),
+                    dst,
+                    list: list.into(),
+                    after,
+                }))
+            }
+            _ => {
+                let path = self.path()?;
+                let token = self.next("`+` or `-`")?;
+                let op = match (token.kind, token.text) {
+                    (TokenKind::Operator, "+") => MathOp::Add,
+                    (TokenKind::Operator, "-") => MathOp::Sub,
+                    _ => return Err(unexpected(token, "`+` or `-`")),
+                };
+                let token = self.expect(TokenKind::Value, "a value")?;
+
+                Ok(SetAction::Math(Math {
+                    src: src(&dst, path),
+                    dst,
+                    op,
+                    num: self.value(token)?.into(),
+                }))
+            }
+        }
+    }
+
+    /// Parses `(`, the arguments, then `)`.
+    fn function_args<T, F>(&mut self, args: F) -> Result<T, ParseError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, ParseError>,
+    {
+        self.next("a function")?;
+        self.expect(TokenKind::OpenParen, "`(`")?;
+        let args = args(self)?;
+        self.expect(TokenKind::CloseParen, "`)`")?;
+
+        Ok(args)
+    }
+
+    /// `operand ::= "size" "(" path ")" | path | value`
+    fn operand(&mut self) -> Result<Operand, ParseError> {
+        let token = self.peek().ok_or(ParseError::UnexpectedEnd {
+            expected: "an operand",
+        })?;
+
+        let op = if token.kind == TokenKind::Value {
+            self.pos += 1;
+            OperandType::Scalar(self.value(token)?.into())
+        } else if token.is_keyword("size")
+            && self
+                .peek_nth(1)
+                .is_some_and(|token| token.kind == TokenKind::OpenParen)
+        {
+            OperandType::Size(Size {
+                path: self.function_args(|parser| parser.path())?,
+            })
+        } else {
+            OperandType::Path(self.path()?)
+        };
+
+        Ok(Operand { op })
+    }
+
+    /// A document path, such as `a.#b[1][2].c`.
+    fn path(&mut self) -> Result<Path, ParseError> {
+        const EXPECTED: &str = "an attribute name";
+
+        let mut elements = Vec::new();
+        loop {
+            let token = self.next(EXPECTED)?;
+            let name = match token.kind {
+                TokenKind::Ident => Name::from(token.text),
+                TokenKind::Name => self.name(token)?,
+                _ => return Err(unexpected(token, EXPECTED)),
+            };
+
+            let mut indexes = Vec::new();
+            while self.eat(TokenKind::OpenBracket) {
+                let token = self.expect(TokenKind::Number, "a list index")?;
+                indexes.push(
+                    token
+                        .text
+                        .parse::<usize>()
+                        .map_err(|_| unexpected(token, "a list index"))?,
+                );
+                self.expect(TokenKind::CloseBracket, "`]`")?;
+            }
+
+            elements.push(Element::new_indexed_field(name, indexes));
+
+            if !self.eat(TokenKind::Dot) {
+                return Ok(Path { elements });
+            }
+        }
+    }
+
+    /// Resolves an expression attribute name token, like `#0`.
+    fn name(&self, token: Token<'_>) -> Result<Name, ParseError> {
+        self.names
+            .and_then(|names| names.get(token.text))
+            .map(Name::from)
+            .ok_or_else(|| ParseError::MissingName {
+                name: token.text.to_owned(),
+            })
+    }
+
+    /// Resolves an expression attribute value token, like `:0`.
+    fn value(&self, token: Token<'_>) -> Result<Value, ParseError> {
+        let value = self
+            .values
+            .and_then(|values| values.get(token.text))
+            .ok_or_else(|| ParseError::MissingValue {
+                value: token.text.to_owned(),
+            })?;
+
+        Value::try_from(value.clone()).map_err(|_| ParseError::InvalidValue {
+            value: token.text.to_owned(),
+        })
+    }
+}
+
+fn unexpected(token: Token<'_>, expected: &'static str) -> ParseError {
+    ParseError::Unexpected {
+        offset: token.offset,
+        found: token.text.to_owned(),
+        expected,
+    }
+}
+
+fn parse_type(attribute_type: &str) -> Option<Type> {
+    [
+        Type::String,
+        Type::StringSet,
+        Type::Number,
+        Type::NumberSet,
+        Type::Binary,
+        Type::BinarySet,
+        Type::Boolean,
+        Type::Null,
+        Type::List,
+        Type::Map,
+    ]
+    .into_iter()
+    .find(|t| t.as_str() == attribute_type)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        condition::{attribute_type::Type, Condition},
+        update::Update,
+        value::StringSet,
+        Expression, Num, Path,
+    };
+
+    use super::{parse_condition, parse_projection, parse_update, ParseError};
+
+    #[test]
+    fn round_trip() {
+        let condition = Path::new_name("a")
+            .attribute_exists()
+            .and(
+                Condition::from(Path::new_name("b").size().between(Num::new(1), Num::new(5)))
+                    .or("c.d[1][2]"
+                        .parse::<Path>()
+                        .unwrap()
+                        .in_(["x", "y"])
+                        .parenthesize()
+                        .not())
+                    .parenthesize(),
+            )
+            .or(Path::new_name("e").attribute_type(Type::NumberSet))
+            .or(Path::new_name("f")
+                .begins_with("g")
+                .and(Path::new_name("h").contains(Num::new(1)))
+                .and(Path::new_name("i").attribute_not_exists())
+                .and(Path::new_name("j").less_than_or_equal(Path::new_name("k"))));
+
+        let expression = Expression::builder()
+            .with_condition(condition.clone())
+            .build();
+        let parsed = parse_condition(
+            expression.condition_expression.as_deref().unwrap(),
+            expression.expression_attribute_names.as_ref(),
+            expression.expression_attribute_values.as_ref(),
+        )
+        .unwrap();
+        assert_eq!(condition.to_string(), parsed.to_string());
+    }
+
+    #[test]
+    fn errors() {
+        let names = HashMap::from([("#a".to_string(), "a".to_string())]);
+        let values = HashMap::from([
+            (":s".to_string(), AttributeValue::S("X".into())),
+            (":n".to_string(), AttributeValue::N("1".into())),
+        ]);
+        let parse = |expression| parse_condition(expression, Some(&names), Some(&values));
+
+        assert_eq!(
+            Err(ParseError::Unexpected {
+                offset: 3,
+                found: "+".into(),
+                expected: "a comparator, `BETWEEN`, or `IN`",
+            }),
+            parse("#a + :n")
+        );
+        assert_eq!(
+            Err(ParseError::UnexpectedEnd { expected: "`)`" }),
+            parse("(#a = :n")
+        );
+        assert_eq!(
+            Err(ParseError::Unexpected {
+                offset: 9,
+                found: ":s".into(),
+                expected: "the end of the expression",
+            }),
+            parse("#a = :n  :s")
+        );
+        assert_eq!(
+            Err(ParseError::MissingName { name: "#b".into() }),
+            parse("#b = :n")
+        );
+        assert_eq!(
+            Err(ParseError::MissingValue { value: ":x".into() }),
+            parse("#a = :x")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidValue { value: ":s".into() }),
+            parse("attribute_type(#a, :s)")
+        );
+        assert_eq!(
+            "attribute_exists(a) AND b.a[3] = \"X\"",
+            parse("attribute_exists(#a) and b.#a[3] = :s")
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn update() {
+        let update = Update::from(Path::new_name("a").assign("x"))
+            .and(Path::new_name("b").math().add(1))
+            .and(Path::new_name("c").math().src(Path::new_name("d")).sub(2))
+            .and(Path::new_name("e").list_append().list(["y"]))
+            .and(
+                Path::new_name("f")
+                    .list_append()
+                    .src(Path::new_name("g"))
+                    .before()
+                    .list(["z"]),
+            )
+            .and(Path::new_name("h").if_not_exists().assign(Num::new(0)))
+            .and(Path::new_name("i").remove())
+            .and(Path::new_name("j").add(Num::new(3)))
+            .and(Path::new_name("k").delete(StringSet::from(["w"])));
+
+        let expression = Expression::builder().with_update(update.clone()).build();
+        let parsed = parse_update(
+            expression.update_expression.as_deref().unwrap(),
+            expression.expression_attribute_names.as_ref(),
+            expression.expression_attribute_values.as_ref(),
+        )
+        .unwrap();
+        assert_eq!(update, parsed);
+
+        let values = HashMap::from([(":n".to_string(), AttributeValue::N("1".into()))]);
+        assert_eq!(
+            Err(ParseError::DuplicateClause {
+                clause: "SET".into()
+            }),
+            parse_update("SET a = :n set b = :n", None, Some(&values))
+        );
+        assert_eq!(
+            Err(ParseError::Unexpected {
+                offset: 10,
+                found: ":n".into(),
+                expected: "`+` or `-`",
+            }),
+            parse_update("SET a = b :n", None, Some(&values))
+        );
+    }
+
+    #[test]
+    fn projection() {
+        let names = HashMap::from([("#a".to_string(), "a".to_string())]);
+        assert_eq!(
+            Ok(vec![
+                "a".parse::<Path>().unwrap(),
+                "b[1].a".parse().unwrap(),
+            ]),
+            parse_projection("#a, b[1].#a", Some(&names))
+        );
+    }
+}
@@ -0,0 +1,591 @@
+use core::fmt;
+use std::{collections::BTreeMap, error::Error};
+
+use super::{
+    lexer::{tokenize, TokenKind},
+    Expression,
+};
+use crate::{
+    condition::{attribute_type::Type, BeginsWith, Between, Comparator, Comparison, Condition, In},
+    operand::{Operand, OperandType},
+    update::{Add, Delete, SetAction, Update},
+    value::{Ref, Value, ValueOrRef},
+    visit::{self, Visit},
+};
+
+/// An [`Expression`] with named parameter slots, compiled once and then bound
+/// to values as many times as needed.
+///
+/// Create one with [`Builder::prepare`]. Every [`Ref`] used in the builder
+/// that wasn't supplied with [`Builder::with_value`] becomes a parameter. The
+/// expression strings and attribute names are fixed when it's prepared, so
+/// binding only fills in the expression attribute values.
+///
+/// Where the type of a parameter can be inferred from how it's used, binding a
+/// value of another type fails. Types are inferred from:
+///
+/// * `begins_with`, which requires a string.
+/// * Comparisons with the result of `size()`, which require a number.
+/// * `<`, `<=`, `>`, `>=`, and `BETWEEN`, which require a string, number, or
+///   binary.
+/// * Math in a `SET` action (`a + :p`), which requires a number.
+/// * `list_append` in a `SET` action, which requires a list.
+/// * `ADD`, which requires a number or a set.
+/// * `DELETE`, which requires a set.
+///
+/// Anything else, such as `=` or a plain `SET a = :p`, accepts any type. Use
+/// [`Prepared::with_param_type`] to declare the type of those parameters.
+///
+/// ```
+/// use dynamodb_expression::{
+///     condition::attribute_type::Type, expression::BindError, value::Ref, Expression, Num,
+///     Path,
+/// };
+/// # use pretty_assertions::assert_eq;
+///
+/// let prepared = Expression::builder()
+///     .with_filter(
+///         Path::new_name("age")
+///             .greater_than_or_equal(Ref::new("min_age"))
+///             .and(Path::new_name("name").begins_with(Ref::new("prefix"))),
+///     )
+///     .prepare()
+///     .with_param_type("min_age", Type::Number);
+///
+/// let expression = prepared
+///     .bind([("min_age", Num::new(21).into()), ("prefix", "Jo".into())])
+///     .unwrap();
+/// assert_eq!(
+///     Some(String::from("#0 >= :min_age AND begins_with(#1, :prefix)")),
+///     expression.filter_expression
+/// );
+///
+/// assert_eq!(
+///     Err(BindError::Missing {
+///         param: String::from("prefix")
+///     }),
+///     prepared.bind([("min_age", Num::new(21).into())])
+/// );
+///
+/// assert_eq!(
+///     Err(BindError::WrongType {
+///         param: String::from("prefix"),
+///         expected: Type::String,
+///         actual: Type::Number,
+///     }),
+///     prepared.bind([("min_age", Num::new(21).into()), ("prefix", Num::new(7).into())])
+/// );
+/// ```
+///
+/// [`Builder::prepare`]: super::Builder::prepare
+/// [`Builder::with_value`]: super::Builder::with_value
+#[must_use = "Use `.bind()` to create an `Expression`"]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prepared {
+    expression: Expression,
+    /// The parameters, by name (without the leading `:`), and the types they
+    /// accept. Empty if any type is accepted.
+    params: BTreeMap<String, Vec<Type>>,
+}
+
+impl Prepared {
+    pub(super) fn new(expression: Expression, mut types: BTreeMap<String, Vec<Type>>) -> Self {
+        let params = expression
+            .expressions()
+            .flat_map(|(_kind, expression)| tokenize(expression))
+            .filter(|token| token.kind == TokenKind::Value)
+            .map(|token| &token.text[1..])
+            .filter(|param| {
+                !expression
+                    .expression_attribute_values
+                    .as_ref()
+                    .is_some_and(|values| values.contains_key(&format!(":{param}")))
+            })
+            .map(|param| (param.to_owned(), types.remove(param).unwrap_or_default()))
+            .collect();
+
+        Self { expression, params }
+    }
+
+    /// Declares the type of a parameter. Binding a value of any other type to
+    /// it will fail. Has no effect if there's no parameter with that name.
+    pub fn with_param_type<R>(mut self, param: R, param_type: Type) -> Self
+    where
+        R: Into<Ref>,
+    {
+        let param = String::from(param.into());
+        if let Some(existing) = self.params.get_mut(&param[1..]) {
+            *existing = vec![param_type];
+        }
+
+        self
+    }
+
+    /// The names (without the leading `:`) of the parameters and their types,
+    /// where known, in order by name. A parameter that accepts more than one
+    /// type (such as the value for `ADD`) has no single type.
+    pub fn params(&self) -> impl Iterator<Item = (&str, Option<Type>)> {
+        self.params
+            .iter()
+            .map(|(param, types)| match types.as_slice() {
+                [param_type] => (param.as_str(), Some(*param_type)),
+                _ => (param.as_str(), None),
+            })
+    }
+
+    /// Creates an [`Expression`] with the given values for the parameters.
+    ///
+    /// Fails if a parameter is missing, a value has the wrong type, or a value
+    /// is given for a parameter that doesn't exist. Types are only checked
+    /// where they could be inferred or were declared. See [`Prepared`] for
+    /// which uses of a parameter determine its type.
+    pub fn bind<I, R>(&self, params: I) -> Result<Expression, BindError>
+    where
+        I: IntoIterator<Item = (R, Value)>,
+        R: Into<Ref>,
+    {
+        let mut values = params
+            .into_iter()
+            .map(|(param, value)| {
+                let mut param = String::from(param.into());
+                param.remove(0);
+
+                (param, value)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let mut expression = self.expression.clone();
+
+        for (param, expected) in &self.params {
+            let value = values.remove(param).ok_or_else(|| BindError::Missing {
+                param: param.clone(),
+            })?;
+
+            let actual = Type::of(&value);
+            match expected.as_slice() {
+                [] => {}
+                [expected] => {
+                    if actual != *expected {
+                        return Err(BindError::WrongType {
+                            param: param.clone(),
+                            expected: *expected,
+                            actual,
+                        });
+                    }
+                }
+                expected => {
+                    if !expected.contains(&actual) {
+                        return Err(BindError::NotOneOf {
+                            param: param.clone(),
+                            expected: expected.to_vec(),
+                            actual,
+                        });
+                    }
+                }
+            }
+
+            // Only created when there's a value for it, because DynamoDB
+            // doesn't allow empty expression attribute values.
+            expression
+                .expression_attribute_values
+                .get_or_insert_with(Default::default)
+                .insert(format!(":{param}"), value.into_attribute_value());
+        }
+
+        if let Some((param, _value)) = values.into_iter().next() {
+            return Err(BindError::Unknown { param });
+        }
+
+        Ok(expression)
+    }
+}
+
+/// An error binding values to a [`Prepared`] expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindError {
+    /// No value was given for the parameter.
+    Missing { param: String },
+
+    /// The value given for the parameter is not of the type it requires.
+    WrongType {
+        param: String,
+        expected: Type,
+        actual: Type,
+    },
+
+    /// The value given for the parameter is not one of the types it accepts.
+    NotOneOf {
+        param: String,
+        expected: Vec<Type>,
+        actual: Type,
+    },
+
+    /// A value was given for a parameter that isn't in the expression.
+    Unknown { param: String },
+}
+
+impl fmt::Display for BindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { param } => write!(f, "missing value for parameter :{param}"),
+            Self::WrongType {
+                param,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "parameter :{param} requires a value of type {expected}, got {actual}"
+            ),
+            Self::NotOneOf {
+                param,
+                expected,
+                actual,
+            } => {
+                write!(f, "parameter :{param} requires a value of type ")?;
+                for (i, param_type) in expected.iter().enumerate() {
+                    match i {
+                        0 => {}
+                        i if i + 1 == expected.len() => f.write_str(" or ")?,
+                        _ => f.write_str(", ")?,
+                    }
+                    write!(f, "{param_type}")?;
+                }
+                write!(f, ", got {actual}")
+            }
+            Self::Unknown { param } => write!(f, "unknown parameter :{param}"),
+        }
+    }
+}
+
+impl Error for BindError {}
+
+/// Infers the types of the [`Ref`]s used in expressions, from how they're used.
+#[derive(Debug, Default)]
+pub(super) struct InferTypes {
+    /// The types each parameter accepts.
+    pub types: BTreeMap<String, Vec<Type>>,
+}
+
+/// The types that can be compared with `<`, `<=`, `>`, `>=`, and `BETWEEN`.
+const ORDERED: &[Type] = &[Type::String, Type::Number, Type::Binary];
+
+/// The types that can be used with `DELETE`.
+const SETS: &[Type] = &[Type::StringSet, Type::NumberSet, Type::BinarySet];
+
+/// The types that can be used with `ADD`.
+const ADDABLE: &[Type] = &[
+    Type::Number,
+    Type::StringSet,
+    Type::NumberSet,
+    Type::BinarySet,
+];
+
+impl InferTypes {
+    /// Narrows the types the value accepts, if it's a parameter. A use that
+    /// would rule out every type is ignored, and left for DynamoDB to reject.
+    fn value(&mut self, value: &ValueOrRef, value_types: &[Type]) {
+        if let ValueOrRef::Ref(value_ref) = value {
+            let mut param = String::from(value_ref.clone());
+            param.remove(0);
+
+            let types = self
+                .types
+                .entry(param)
+                .or_insert_with(|| value_types.to_vec());
+            let narrowed = types
+                .iter()
+                .copied()
+                .filter(|value_type| value_types.contains(value_type))
+                .collect::<Vec<_>>();
+            if !narrowed.is_empty() {
+                *types = narrowed;
+            }
+        }
+    }
+
+    /// Narrows the types of any operands that are values.
+    fn operands(&mut self, operands: &[&Operand], value_types: &[Type]) {
+        for operand in operands {
+            if let OperandType::Scalar(value) = &operand.op {
+                self.value(value, value_types);
+            }
+        }
+    }
+
+    /// When compared to the result of `size()`, the other side must be a number.
+    fn sized(&mut self, operands: &[&Operand]) {
+        if operands
+            .iter()
+            .any(|operand| matches!(operand.op, OperandType::Size(_)))
+        {
+            self.operands(operands, &[Type::Number]);
+        }
+    }
+}
+
+/// Infers types from each condition and update action, then walks into any
+/// nested in operands.
+impl Visit for InferTypes {
+    fn visit_condition(&mut self, condition: &Condition) {
+        match condition {
+            Condition::BeginsWith(BeginsWith { substr, .. }) => self.value(substr, &[Type::String]),
+            Condition::Between(Between { op, lower, upper }) => {
+                self.sized(&[op, lower, upper]);
+                self.operands(&[op, lower, upper], ORDERED);
+            }
+            Condition::In(In { op, items }) => {
+                self.sized(&[op].into_iter().chain(items).collect::<Vec<_>>())
+            }
+            Condition::Comparison(Comparison { left, cmp, right }) => {
+                self.sized(&[left, right]);
+                if !matches!(cmp, Comparator::Eq | Comparator::Ne) {
+                    self.operands(&[left, right], ORDERED);
+                }
+            }
+            _ => {}
+        }
+
+        visit::visit_condition(self, condition)
+    }
+
+    fn visit_update(&mut self, update: &Update) {
+        update
+            .add
+            .iter()
+            .for_each(|Add { value, .. }| self.value(value, ADDABLE));
+        update
+            .delete
+            .iter()
+            .for_each(|Delete { subset, .. }| self.value(subset, SETS));
+
+        visit::visit_update(self, update)
+    }
+
+    fn visit_set_action(&mut self, action: &SetAction) {
+        match action {
+            SetAction::Math(action) => self.value(&action.num, &[Type::Number]),
+            SetAction::ListAppend(action) => self.value(&action.list, &[Type::List]),
+            SetAction::Assign(_) | SetAction::IfNotExists(_) => {}
+        }
+
+        visit::visit_set_action(self, action)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        condition::{attribute_type::Type, Condition},
+        fold::Fold,
+        update::Update,
+        value::{List, Num, NumSet, Ref, StringSet, Value, ValueOrRef},
+        Expression, Path,
+    };
+
+    use super::BindError;
+
+    #[test]
+    fn params() {
+        let prepared = Expression::builder()
+            .with_condition(
+                Condition::from(
+                    Path::new_name("tags")
+                        .size()
+                        .greater_than(Ref::new("min_tags")),
+                )
+                .and(Path::new_name("status").equal(Ref::new("status")))
+                .and(Path::new_name("count").between(Ref::new("low"), Num::new(100))),
+            )
+            .with_value("status", "active")
+            .prepare();
+
+        assert_eq!(
+            vec![("low", None), ("min_tags", Some(Type::Number))],
+            prepared.params().collect::<Vec<_>>()
+        );
+
+        let expression = prepared
+            .bind([
+                ("low", Num::new(1).into()),
+                ("min_tags", Num::new(3).into()),
+            ])
+            .unwrap();
+        assert_eq!(Ok(()), expression.check_limits());
+        assert_eq!(
+            4,
+            expression.expression_attribute_values.unwrap().len(),
+            "`:status` was fixed when prepared"
+        );
+
+        assert_eq!(
+            Err(BindError::Unknown {
+                param: String::from("other")
+            }),
+            prepared.bind([
+                ("low", Num::new(1).into()),
+                ("min_tags", Num::new(3).into()),
+                ("other", Num::new(3).into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn update_params() {
+        /// Swaps each value for the next of these parameters.
+        struct Params(std::vec::IntoIter<&'static str>);
+
+        impl Fold for Params {
+            fn fold_value(&mut self, _value: ValueOrRef) -> ValueOrRef {
+                Ref::new(self.0.next().unwrap()).into()
+            }
+        }
+
+        let update = Params(vec!["step", "entries", "name", "added", "removed"].into_iter())
+            .fold_update(
+                Update::from(
+                    Path::new_name("count")
+                        .math()
+                        .add(1)
+                        .and(Path::new_name("log").list_append().list(["x"]))
+                        .and(Path::new_name("name").assign("x")),
+                )
+                .and(Path::new_name("total").add(Num::new(1)))
+                .and(Path::new_name("tags").delete(StringSet::from(["x"]))),
+            );
+        assert_eq!(
+            "SET #0 = #0 + :step, #1 = list_append(#1, :entries), #2 = :name \
+            ADD #3 :added \
+            DELETE #4 :removed",
+            Expression::builder()
+                .with_update(update.clone())
+                .build()
+                .update_expression
+                .unwrap()
+        );
+
+        let prepared = Expression::builder()
+            .with_update(update)
+            .with_condition(Path::new_name("count").less_than(Ref::new("max")))
+            .prepare();
+
+        assert_eq!(
+            vec![
+                ("added", None),
+                ("entries", Some(Type::List)),
+                ("max", None),
+                ("name", None),
+                ("removed", None),
+                ("step", Some(Type::Number)),
+            ],
+            prepared.params().collect::<Vec<_>>()
+        );
+
+        let params = |added: Value, removed: Value, max: Value| {
+            prepared.bind([
+                ("added", added),
+                ("entries", List::from(["x"]).into()),
+                ("max", max),
+                ("name", Num::new(1).into()),
+                ("removed", removed),
+                ("step", Num::new(1).into()),
+            ])
+        };
+
+        assert!(params(
+            Num::new(1).into(),
+            StringSet::from(["a"]).into(),
+            "m".into()
+        )
+        .is_ok());
+        assert!(params(
+            StringSet::from(["a"]).into(),
+            NumSet::from([1]).into(),
+            Num::new(1).into()
+        )
+        .is_ok());
+
+        let error = params("a".into(), StringSet::from(["a"]).into(), "m".into()).unwrap_err();
+        assert_eq!(
+            BindError::NotOneOf {
+                param: String::from("added"),
+                expected: vec![
+                    Type::Number,
+                    Type::StringSet,
+                    Type::NumberSet,
+                    Type::BinarySet
+                ],
+                actual: Type::String,
+            },
+            error
+        );
+        assert_eq!(
+            "parameter :added requires a value of type N, SS, NS or BS, got S",
+            error.to_string()
+        );
+
+        assert_eq!(
+            Err(BindError::NotOneOf {
+                param: String::from("removed"),
+                expected: vec![Type::StringSet, Type::NumberSet, Type::BinarySet],
+                actual: Type::Number,
+            }),
+            params(Num::new(1).into(), Num::new(1).into(), "m".into())
+        );
+
+        assert_eq!(
+            Err(BindError::NotOneOf {
+                param: String::from("max"),
+                expected: vec![Type::String, Type::Number, Type::Binary],
+                actual: Type::Boolean,
+            }),
+            params(
+                Num::new(1).into(),
+                StringSet::from(["a"]).into(),
+                true.into()
+            )
+        );
+    }
+
+    #[test]
+    fn untyped_params() {
+        let prepared = Expression::builder()
+            .with_filter(Path::new_name("status").equal(Ref::new("status")))
+            .prepare();
+
+        assert_eq!(
+            vec![("status", None)],
+            prepared.params().collect::<Vec<_>>()
+        );
+
+        // Any type is accepted.
+        assert!(prepared.bind([("status", Num::new(1).into())]).is_ok());
+        assert!(prepared.bind([("status", "active".into())]).is_ok());
+
+        let prepared = prepared.with_param_type("status", Type::String);
+        assert_eq!(
+            Err(BindError::WrongType {
+                param: String::from("status"),
+                expected: Type::String,
+                actual: Type::Number,
+            }),
+            prepared.bind([("status", Num::new(1).into())])
+        );
+    }
+
+    #[test]
+    fn no_params() {
+        let prepared = Expression::builder()
+            .with_filter(Path::new_name("status").attribute_exists())
+            .prepare();
+        assert_eq!(0, prepared.params().count());
+
+        let expression = prepared.bind(Vec::<(Ref, Value)>::new()).unwrap();
+        assert_eq!(
+            Some("attribute_exists(#0)"),
+            expression.filter_expression.as_deref()
+        );
+        assert_eq!(None, expression.expression_attribute_values);
+    }
+}
@@ -0,0 +1,55 @@
+use core::fmt;
+use std::error::Error;
+
+use super::Item;
+
+/// An error from an operation on a [`Table`], matching the error DynamoDB
+/// would return.
+///
+/// [`Table`]: super::Table
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableError {
+    /// The condition expression evaluated to false. This is DynamoDB's
+    /// `ConditionalCheckFailedException`.
+    ///
+    /// The item the condition was checked against is included, as DynamoDB
+    /// does with [`ReturnValuesOnConditionCheckFailure::AllOld`][1]. Pass it
+    /// to [`Expression::explain`] to see why the condition failed.
+    ///
+    /// [1]: aws_sdk_dynamodb::types::ReturnValuesOnConditionCheckFailure::AllOld
+    /// [`Expression::explain`]: crate::Expression::explain
+    ConditionalCheckFailed { item: Option<Item> },
+
+    /// The request is invalid. This is DynamoDB's `ValidationException`.
+    Validation { message: String },
+}
+
+impl TableError {
+    pub(super) fn validation<T>(message: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::Validation {
+            message: message.into(),
+        }
+    }
+
+    /// The name of the DynamoDB exception this error corresponds to.
+    pub fn exception_name(&self) -> &'static str {
+        match self {
+            Self::ConditionalCheckFailed { .. } => "ConditionalCheckFailedException",
+            Self::Validation { .. } => "ValidationException",
+        }
+    }
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ConditionalCheckFailed { .. } => f.write_str("The conditional request failed"),
+            Self::Validation { message } => f.write_str(message),
+        }
+    }
+}
+
+impl Error for TableError {}
@@ -0,0 +1,158 @@
+//! The key schema of a [`Table`][super::Table], and the keys items are stored
+//! under.
+
+use core::cmp::Ordering;
+
+use crate::{
+    condition::attribute_type::Type,
+    path::Name,
+    value::{decimal, Map, Num, Scalar, Value},
+};
+
+use super::TableError;
+
+/// A key attribute's name and type.
+#[derive(Debug, Clone)]
+pub(super) struct KeyAttribute {
+    pub(super) name: Name,
+    pub(super) key_type: Type,
+}
+
+impl KeyAttribute {
+    /// # Panics
+    ///
+    /// If the type isn't one a key attribute can have: string, number, or
+    /// binary.
+    pub(super) fn new(name: Name, key_type: Type) -> Self {
+        assert!(
+            matches!(key_type, Type::String | Type::Number | Type::Binary),
+            "a key attribute must be a string, number, or binary, not {key_type}"
+        );
+
+        Self { name, key_type }
+    }
+
+    /// The value of this key attribute, for an item or a key.
+    ///
+    /// `missing` makes the error for when the item doesn't have the attribute.
+    pub(super) fn value<F>(&self, item: &Map, missing: F) -> Result<KeyValue, TableError>
+    where
+        F: FnOnce() -> TableError,
+    {
+        let value = item.map.get(&self.name).ok_or_else(missing)?;
+        let actual = Type::of(value);
+        if actual != self.key_type {
+            return Err(TableError::validation(format!(
+                "One or more parameter values were invalid: \
+                Type mismatch for key {} expected: {} actual: {actual}",
+                self.name, self.key_type,
+            )));
+        }
+
+        Ok(match value {
+            Value::Scalar(Scalar::String(s)) if s.is_empty() => {
+                return Err(TableError::validation(format!(
+                    "One or more parameter values are not valid. \
+                    The AttributeValue for a key attribute cannot contain \
+                    an empty string value. Key: {}",
+                    self.name
+                )))
+            }
+            Value::Scalar(Scalar::String(s)) => KeyValue::String(s.clone()),
+            Value::Scalar(Scalar::Num(n)) => KeyValue::Num(n.clone()),
+            Value::Scalar(Scalar::Binary(b)) => KeyValue::Binary(b.clone()),
+            _ => unreachable!("the type was checked above"),
+        })
+    }
+}
+
+/// The value of a key attribute, ordered the way DynamoDB orders them.
+#[derive(Debug, Clone)]
+pub(super) enum KeyValue {
+    String(String),
+    Num(Num),
+    Binary(Vec<u8>),
+}
+
+impl Ord for KeyValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            // Strings are ordered by their UTF-8 bytes, which is how Rust
+            // orders them too.
+            (Self::String(a), Self::String(b)) => a.cmp(b),
+            // Numbers are validated before they're stored.
+            (Self::Num(a), Self::Num(b)) => decimal::compare(a, b).unwrap_or(Ordering::Equal),
+            (Self::Binary(a), Self::Binary(b)) => a.cmp(b),
+            // A key attribute only ever has one type, so this is just to be
+            // a total order.
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+impl PartialOrd for KeyValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Consistent with `Ord`, so `1` and `1.0` are the same key, as in DynamoDB.
+impl PartialEq for KeyValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for KeyValue {}
+
+impl KeyValue {
+    fn rank(&self) -> u8 {
+        match self {
+            Self::String(_) => 0,
+            Self::Num(_) => 1,
+            Self::Binary(_) => 2,
+        }
+    }
+}
+
+/// The key an item is stored under: its partition key, then its sort key if
+/// the table has one.
+pub(super) type StoredKey = (KeyValue, Option<KeyValue>);
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::Num;
+
+    use super::KeyValue;
+
+    #[test]
+    fn key_order() {
+        let nums = |nums: &[&str]| {
+            nums.iter()
+                .map(|n| KeyValue::Num(Num { n: n.to_string() }))
+                .collect::<Vec<_>>()
+        };
+
+        let mut keys = nums(&["10", "-2", "9.5", "1e1", "0"]);
+        keys.sort();
+        assert_eq!(nums(&["-2", "0", "9.5", "10", "1e1"]), keys);
+        assert_eq!(keys[3], keys[4]);
+
+        let mut keys = vec![
+            KeyValue::String("b".into()),
+            KeyValue::String("B".into()),
+            KeyValue::String("a".into()),
+        ];
+        keys.sort();
+        assert_eq!(
+            vec![
+                KeyValue::String("B".into()),
+                KeyValue::String("a".into()),
+                KeyValue::String("b".into()),
+            ],
+            keys
+        );
+    }
+}
@@ -0,0 +1,732 @@
+//! An in-memory DynamoDB table, for unit tests that would otherwise need
+//! DynamoDB Local.
+//!
+//! Requires the `fake` feature.
+//!
+//! A [`Table`] has a key schema, and supports the same operations as
+//! DynamoDB: put, get, update, and delete an item, and [query] and [scan]
+//! items. Each operation takes an [`Expression`], and uses the parts of it the
+//! DynamoDB operation would: the condition, key condition, filter, update, and
+//! projection expressions, along with the expression attribute names and
+//! values. Results and errors are the ones DynamoDB would return.
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use aws_sdk_dynamodb::types::{AttributeValue, ReturnValue};
+//! use dynamodb_expression::{
+//!     condition::attribute_type::Type,
+//!     fake::{Query, Table, TableError},
+//!     Expression, Num, Path,
+//! };
+//! # use pretty_assertions::assert_eq;
+//!
+//! let mut table = Table::new("pk", Type::String).with_sort_key("sk", Type::Number);
+//! for sk in 1..=3 {
+//!     let item = HashMap::from([
+//!         ("pk".to_string(), AttributeValue::S("user".into())),
+//!         ("sk".to_string(), AttributeValue::N(sk.to_string())),
+//!     ]);
+//!     table
+//!         .put_item(item, &Expression::builder().build())
+//!         .unwrap();
+//! }
+//!
+//! let key = HashMap::from([
+//!     ("pk".to_string(), AttributeValue::S("user".into())),
+//!     ("sk".to_string(), AttributeValue::N("2".into())),
+//! ]);
+//! let updated = table
+//!     .update_item(
+//!         key.clone(),
+//!         &Expression::builder()
+//!             .with_condition(Path::new_name("sk").attribute_exists())
+//!             .with_update(Path::new_name("count").add(Num::new(1)))
+//!             .build(),
+//!         ReturnValue::UpdatedNew,
+//!     )
+//!     .unwrap();
+//! assert_eq!(
+//!     Some(HashMap::from([("count".to_string(), AttributeValue::N("1".into()))])),
+//!     updated
+//! );
+//!
+//! let page = table
+//!     .query(
+//!         &Expression::builder()
+//!             .with_key_condition(
+//!                 Path::new_name("pk")
+//!                     .key()
+//!                     .equal("user")
+//!                     .and(Path::new_name("sk").key().greater_than(Num::new(1))),
+//!             )
+//!             .with_filter(Path::new_name("count").attribute_not_exists())
+//!             .build(),
+//!         Query::new().with_scan_index_forward(false),
+//!     )
+//!     .unwrap();
+//! assert_eq!(1, page.count());
+//! assert_eq!(2, page.scanned_count());
+//! assert_eq!(Some(&AttributeValue::N("3".into())), page.items()[0].get("sk"));
+//!
+//! let result = table.delete_item(
+//!     key,
+//!     &Expression::builder()
+//!         .with_condition(Path::new_name("count").greater_than(Num::new(5)))
+//!         .build(),
+//! );
+//! assert!(matches!(
+//!     result,
+//!     Err(TableError::ConditionalCheckFailed { item: Some(_) })
+//! ));
+//! ```
+//!
+//! [query]: Table::query
+//! [scan]: Table::scan
+
+mod error;
+mod key;
+mod projection;
+mod query;
+#[cfg(feature = "server")]
+pub mod server;
+
+use std::collections::{BTreeMap, HashMap};
+
+use aws_sdk_dynamodb::types::{AttributeValue, ReturnValue};
+
+pub use self::{
+    error::TableError,
+    query::{Page, Query, Scan},
+};
+
+use self::{
+    key::{KeyAttribute, StoredKey},
+    projection::project,
+};
+use crate::{
+    condition::{attribute_type::Type, Condition},
+    expression::{Dependencies, ExpressionKind, ParseError},
+    path::{Element, FieldName, IndexedField, Name},
+    value::{decimal, Map, Scalar, Set, Value},
+    Expression,
+};
+
+/// An item, as the AWS SDK represents it.
+pub type Item = HashMap<String, AttributeValue>;
+
+/// An in-memory DynamoDB table.
+///
+/// See the [module docs](self) for an example.
+#[derive(Debug, Clone)]
+pub struct Table {
+    partition_key: KeyAttribute,
+    sort_key: Option<KeyAttribute>,
+    items: BTreeMap<StoredKey, Map>,
+}
+
+impl Table {
+    /// A table with the partition key, of the given type, and no sort key.
+    ///
+    /// # Panics
+    ///
+    /// If the type isn't [`Type::String`], [`Type::Number`], or
+    /// [`Type::Binary`].
+    pub fn new<N>(partition_key: N, key_type: Type) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            partition_key: KeyAttribute::new(partition_key.into().into(), key_type),
+            sort_key: None,
+            items: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the sort key, and its type.
+    ///
+    /// # Panics
+    ///
+    /// If the type isn't [`Type::String`], [`Type::Number`], or
+    /// [`Type::Binary`].
+    #[must_use]
+    pub fn with_sort_key<N>(mut self, sort_key: N, key_type: Type) -> Self
+    where
+        N: Into<String>,
+    {
+        self.sort_key = Some(KeyAttribute::new(sort_key.into().into(), key_type));
+
+        self
+    }
+
+    /// The name and type of the partition key.
+    pub fn partition_key(&self) -> (&str, Type) {
+        (&self.partition_key.name.name, self.partition_key.key_type)
+    }
+
+    /// The name and type of the sort key, if the table has one.
+    pub fn sort_key(&self) -> Option<(&str, Type)> {
+        self.sort_key
+            .as_ref()
+            .map(|sort_key| (sort_key.name.name.as_str(), sort_key.key_type))
+    }
+
+    /// The number of items in the table.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether the table has no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Writes the item, replacing any item with the same key, like
+    /// [`PutItem`][1]. The item that was replaced is returned, as DynamoDB
+    /// does with [`ReturnValue::AllOld`].
+    ///
+    /// Uses the condition expression.
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_PutItem.html
+    pub fn put_item(
+        &mut self,
+        item: Item,
+        expression: &Expression,
+    ) -> Result<Option<Item>, TableError> {
+        check_placeholders(expression, &[ExpressionKind::Condition])?;
+        let condition = parsed(expression.parse_condition(), "ConditionExpression")?;
+
+        let item = to_map(item)?;
+        let key = self.stored_key(&item)?;
+        check_condition(condition.as_ref(), self.items.get(&key))?;
+
+        Ok(self.items.insert(key, item).map(to_item))
+    }
+
+    /// Reads the item with the key, like [`GetItem`][1].
+    ///
+    /// Uses the projection expression.
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_GetItem.html
+    pub fn get_item(&self, key: Item, expression: &Expression) -> Result<Option<Item>, TableError> {
+        check_placeholders(expression, &[ExpressionKind::Projection])?;
+        let projection = parsed(expression.parse_projection(), "ProjectionExpression")?;
+
+        let (key, _key) = self.key(key)?;
+
+        Ok(self.items.get(&key).map(|item| match &projection {
+            Some(paths) => to_item(project(item, paths)),
+            None => to_item(item.clone()),
+        }))
+    }
+
+    /// Updates the item with the key, creating it if it doesn't exist, like
+    /// [`UpdateItem`][1]. What's returned depends on the [`ReturnValue`].
+    ///
+    /// Uses the condition and update expressions.
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_UpdateItem.html
+    pub fn update_item(
+        &mut self,
+        key: Item,
+        expression: &Expression,
+        return_values: ReturnValue,
+    ) -> Result<Option<Item>, TableError> {
+        check_placeholders(
+            expression,
+            &[ExpressionKind::Condition, ExpressionKind::Update],
+        )?;
+        let condition = parsed(expression.parse_condition(), "ConditionExpression")?;
+        let update = parsed(expression.parse_update(), "UpdateExpression")?;
+
+        let (key, key_item) = self.key(key)?;
+
+        let dependencies = update.as_ref().map(Dependencies::from).unwrap_or_default();
+        let written = dependencies.writes();
+        for path in &written {
+            let name = match path.elements.first() {
+                Some(Element::Name(name)) => name,
+                Some(Element::IndexedField(IndexedField {
+                    name: FieldName::Name(name),
+                    ..
+                })) => name,
+                _ => continue,
+            };
+
+            if self.key_attributes().any(|key| key.name == *name) {
+                return Err(TableError::validation(format!(
+                    "One or more parameter values were invalid: \
+                    Cannot update attribute {name}. This attribute is part of the key"
+                )));
+            }
+        }
+
+        let old = self.items.get(&key);
+        check_condition(condition.as_ref(), old)?;
+
+        let mut new = old.cloned().unwrap_or(key_item);
+        if let Some(update) = &update {
+            update.apply(&mut new).map_err(|err| {
+                TableError::validation(format!("Invalid UpdateExpression: {err}"))
+            })?;
+        }
+        new.map.values().try_for_each(validate)?;
+
+        let old = self.items.insert(key, new.clone());
+
+        let updated = |item: &Map| {
+            let updated = project(item, written.iter().copied());
+            (!updated.map.is_empty()).then(|| to_item(updated))
+        };
+
+        Ok(match return_values {
+            ReturnValue::None => None,
+            ReturnValue::AllOld => old.map(to_item),
+            ReturnValue::AllNew => Some(to_item(new)),
+            ReturnValue::UpdatedOld => old.as_ref().and_then(updated),
+            ReturnValue::UpdatedNew => updated(&new),
+            _ => {
+                return Err(TableError::validation(format!(
+                    "Unsupported ReturnValues: {return_values}"
+                )))
+            }
+        })
+    }
+
+    /// Deletes the item with the key, like [`DeleteItem`][1]. The item that
+    /// was deleted is returned, as DynamoDB does with
+    /// [`ReturnValue::AllOld`].
+    ///
+    /// Uses the condition expression.
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_DeleteItem.html
+    pub fn delete_item(
+        &mut self,
+        key: Item,
+        expression: &Expression,
+    ) -> Result<Option<Item>, TableError> {
+        check_placeholders(expression, &[ExpressionKind::Condition])?;
+        let condition = parsed(expression.parse_condition(), "ConditionExpression")?;
+
+        let (key, _key) = self.key(key)?;
+        check_condition(condition.as_ref(), self.items.get(&key))?;
+
+        Ok(self.items.remove(&key).map(to_item))
+    }
+
+    /// Checks the condition against the item with the key, without writing
+    /// anything, like a `ConditionCheck` in [`TransactWriteItems`][1].
+    ///
+    /// Uses the condition expression.
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_TransactWriteItems.html
+    pub fn condition_check(&self, key: Item, expression: &Expression) -> Result<(), TableError> {
+        check_placeholders(expression, &[ExpressionKind::Condition])?;
+        let condition = parsed(expression.parse_condition(), "ConditionExpression")?
+            .ok_or_else(|| TableError::validation("The ConditionExpression must be specified"))?;
+
+        let (key, _key) = self.key(key)?;
+
+        check_condition(Some(&condition), self.items.get(&key))
+    }
+
+    /// The key a write in a transaction goes to. `item` is the whole item for
+    /// a put, and just the key attributes otherwise.
+    #[cfg(feature = "server")]
+    fn transact_key(&self, item: Item, is_put: bool) -> Result<StoredKey, TableError> {
+        if is_put {
+            self.stored_key(&to_map(item)?)
+        } else {
+            Ok(self.key(item)?.0)
+        }
+    }
+
+    /// Checks the condition of a write in a transaction against the item
+    /// stored under the key, without writing anything.
+    #[cfg(feature = "server")]
+    fn transact_check(&self, key: &StoredKey, expression: &Expression) -> Result<(), TableError> {
+        let condition = parsed(expression.parse_condition(), "ConditionExpression")?;
+
+        check_condition(condition.as_ref(), self.items.get(key))
+    }
+
+    fn key_attributes(&self) -> impl Iterator<Item = &KeyAttribute> {
+        core::iter::once(&self.partition_key).chain(&self.sort_key)
+    }
+
+    /// The key a whole item is stored under.
+    fn stored_key(&self, item: &Map) -> Result<StoredKey, TableError> {
+        let value = |key: &KeyAttribute| {
+            key.value(item, || {
+                TableError::validation(format!(
+                    "One or more parameter values were invalid: \
+                    Missing the key {} in the item",
+                    key.name
+                ))
+            })
+        };
+
+        Ok((
+            value(&self.partition_key)?,
+            self.sort_key.as_ref().map(value).transpose()?,
+        ))
+    }
+
+    /// The key from a request that has just the key attributes, and those
+    /// attributes as an item.
+    fn key(&self, key: Item) -> Result<(StoredKey, Map), TableError> {
+        let key = to_map(key)?;
+        let matches_schema = key.map.len() == self.key_attributes().count()
+            && self.key_attributes().all(|attribute| {
+                key.map.get(&attribute.name).map(Type::of) == Some(attribute.key_type)
+            });
+        if !matches_schema {
+            return Err(TableError::validation(
+                "The provided key element does not match the schema",
+            ));
+        }
+
+        Ok((self.stored_key(&key)?, key))
+    }
+
+    /// Just the key attributes of an item.
+    fn key_item(&self, item: &Map) -> Item {
+        self.key_attributes()
+            .filter_map(|key| {
+                item.map
+                    .get(&key.name)
+                    .map(|value| (key.name.name.clone(), value.clone().into_attribute_value()))
+            })
+            .collect()
+    }
+}
+
+/// DynamoDB rejects expression attribute names and values that aren't used by
+/// any of the expressions the operation uses.
+fn check_placeholders(expression: &Expression, kinds: &[ExpressionKind]) -> Result<(), TableError> {
+    let unused = expression.unused_placeholders(kinds);
+    for (prefix, parameter) in [
+        ('#', "ExpressionAttributeNames"),
+        (':', "ExpressionAttributeValues"),
+    ] {
+        let unused = unused
+            .iter()
+            .filter(|placeholder| placeholder.starts_with(prefix))
+            .copied()
+            .collect::<Vec<_>>();
+        if !unused.is_empty() {
+            return Err(TableError::validation(format!(
+                "Value provided in {parameter} unused in expressions: keys: {{{}}}",
+                unused.join(", ")
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Turns an error parsing one of the expressions into the error DynamoDB
+/// would return.
+fn parsed<T>(result: Result<T, ParseError>, parameter: &str) -> Result<T, TableError> {
+    result.map_err(|err| TableError::validation(format!("Invalid {parameter}: {err}")))
+}
+
+/// Checks the condition against the item, or against an empty item if there
+/// isn't one.
+fn check_condition(condition: Option<&Condition>, item: Option<&Map>) -> Result<(), TableError> {
+    let Some(condition) = condition else {
+        return Ok(());
+    };
+
+    let empty = Map::default();
+    let passed = condition
+        .evaluate(item.unwrap_or(&empty))
+        .map_err(|err| TableError::validation(err.to_string()))?;
+    if passed {
+        Ok(())
+    } else {
+        Err(TableError::ConditionalCheckFailed {
+            item: item.cloned().map(to_item),
+        })
+    }
+}
+
+fn to_map(item: Item) -> Result<Map, TableError> {
+    let item = item
+        .into_iter()
+        .map(|(name, value)| Value::try_from(value).map(|value| (Name::from(name), value)))
+        .collect::<Result<Map, _>>()
+        .map_err(|err| TableError::validation(err.to_string()))?;
+    item.map.values().try_for_each(validate)?;
+
+    Ok(item)
+}
+
+fn to_item(item: Map) -> Item {
+    item.map
+        .into_iter()
+        .map(|(name, value)| (name.name, value.into_attribute_value()))
+        .collect()
+}
+
+/// Checks for values DynamoDB won't store: empty sets, and numbers that
+/// aren't valid.
+fn validate(value: &Value) -> Result<(), TableError> {
+    let invalid_number = |n: &str| {
+        TableError::validation(format!(
+            "The parameter cannot be converted to a numeric value: {n}"
+        ))
+    };
+    let empty_set = || {
+        TableError::validation(
+            "One or more parameter values were invalid: An empty set is not allowed",
+        )
+    };
+
+    match value {
+        Value::Scalar(Scalar::Num(num)) if !decimal::is_valid(num) => Err(invalid_number(&num.n)),
+        Value::Scalar(_) => Ok(()),
+        Value::Set(Set::StringSet(set)) if set.0.is_empty() => Err(empty_set()),
+        Value::Set(Set::BinarySet(set)) if set.0.is_empty() => Err(empty_set()),
+        Value::Set(Set::NumSet(set)) if set.0.is_empty() => Err(empty_set()),
+        Value::Set(Set::NumSet(set)) => match set.0.iter().find(|num| !decimal::is_valid(num)) {
+            Some(num) => Err(invalid_number(&num.n)),
+            None => Ok(()),
+        },
+        Value::Set(_) => Ok(()),
+        Value::Map(map) => map.map.values().try_for_each(validate),
+        Value::List(list) => list.list.iter().try_for_each(validate),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use aws_sdk_dynamodb::types::{AttributeValue, ReturnValue};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        condition::attribute_type::Type, update::Update, value::Set, Expression, Num, Path,
+    };
+
+    use super::{Item, Table, TableError};
+
+    fn item<const N: usize>(attributes: [(&str, AttributeValue); N]) -> Item {
+        attributes
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect()
+    }
+
+    fn s(s: &str) -> AttributeValue {
+        AttributeValue::S(s.into())
+    }
+
+    fn n(n: i32) -> AttributeValue {
+        AttributeValue::N(n.to_string())
+    }
+
+    fn validation(message: &str) -> TableError {
+        TableError::Validation {
+            message: message.into(),
+        }
+    }
+
+    #[test]
+    fn put_get_delete() {
+        let mut table = Table::new("id", Type::String);
+        let none = Expression::builder().build();
+
+        let original = item([("id", s("a")), ("n", n(1)), ("name", s("first"))]);
+        assert_eq!(None, table.put_item(original.clone(), &none).unwrap());
+        assert_eq!(
+            Some(original.clone()),
+            table.get_item(item([("id", s("a"))]), &none).unwrap()
+        );
+
+        let not_exists = Expression::builder()
+            .with_condition(Path::new_name("id").attribute_not_exists())
+            .build();
+        assert_eq!(
+            Err(TableError::ConditionalCheckFailed {
+                item: Some(original.clone())
+            }),
+            table.put_item(item([("id", s("a"))]), &not_exists)
+        );
+        assert_eq!(
+            Some(original.clone()),
+            table
+                .put_item(item([("id", s("a")), ("n", n(2))]), &none)
+                .unwrap()
+        );
+
+        assert_eq!(
+            Some(item([("n", n(2))])),
+            table
+                .get_item(
+                    item([("id", s("a"))]),
+                    &Expression::builder().with_projection(["n", "name"]).build()
+                )
+                .unwrap()
+        );
+        assert_eq!(None, table.get_item(item([("id", s("b"))]), &none).unwrap());
+
+        assert_eq!(
+            Err(TableError::ConditionalCheckFailed {
+                item: Some(item([("id", s("a")), ("n", n(2))]))
+            }),
+            table.delete_item(
+                item([("id", s("a"))]),
+                &Expression::builder()
+                    .with_condition(Path::new_name("n").equal(Num::new(1)))
+                    .build()
+            )
+        );
+        assert_eq!(
+            Some(item([("id", s("a")), ("n", n(2))])),
+            table.delete_item(item([("id", s("a"))]), &none).unwrap()
+        );
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn update() {
+        let mut table = Table::new("pk", Type::String).with_sort_key("sk", Type::Number);
+        let key = || item([("pk", s("a")), ("sk", n(1))]);
+
+        // Creates the item, with its key.
+        let set_count = Expression::builder()
+            .with_update(Path::new_name("count").if_not_exists().assign(Num::new(0)))
+            .build();
+        assert_eq!(
+            Ok(Some(item([("pk", s("a")), ("sk", n(1)), ("count", n(0))]))),
+            table.update_item(key(), &set_count, ReturnValue::AllNew)
+        );
+
+        let increment = Expression::builder()
+            .with_condition(Path::new_name("count").less_than(Num::new(2)))
+            .with_update(
+                Update::from(Path::new_name("count").math().add(1))
+                    .and(Path::new_name("tags").add(Set::new_string_set(["x"]))),
+            )
+            .build();
+        assert_eq!(
+            Ok(Some(item([("count", n(0))]))),
+            table.update_item(key(), &increment, ReturnValue::UpdatedOld)
+        );
+        assert_eq!(
+            Ok(Some(item([
+                ("count", n(2)),
+                ("tags", AttributeValue::Ss(vec!["x".into()]))
+            ]))),
+            table.update_item(key(), &increment, ReturnValue::UpdatedNew)
+        );
+        assert!(matches!(
+            table.update_item(key(), &increment, ReturnValue::None),
+            Err(TableError::ConditionalCheckFailed { item: Some(_) })
+        ));
+
+        assert_eq!(
+            Err(validation(
+                "One or more parameter values were invalid: \
+                Cannot update attribute sk. This attribute is part of the key"
+            )),
+            table.update_item(
+                key(),
+                &Expression::builder()
+                    .with_update(Path::new_name("sk").assign(Num::new(2)))
+                    .build(),
+                ReturnValue::None,
+            )
+        );
+
+        assert_eq!(
+            Err(validation(
+                "Invalid UpdateExpression: an operand in the update expression \
+                has an incorrect data type: tags"
+            )),
+            table.update_item(
+                key(),
+                &Expression::builder()
+                    .with_update(Path::new_name("tags").math().add(1))
+                    .build(),
+                ReturnValue::None,
+            )
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut table = Table::new("pk", Type::String).with_sort_key("sk", Type::Number);
+        let none = Expression::builder().build();
+
+        assert_eq!(
+            Err(validation(
+                "One or more parameter values were invalid: Missing the key sk in the item"
+            )),
+            table.put_item(item([("pk", s("a"))]), &none)
+        );
+        assert_eq!(
+            Err(validation(
+                "One or more parameter values were invalid: \
+                Type mismatch for key sk expected: N actual: S"
+            )),
+            table.put_item(item([("pk", s("a")), ("sk", s("1"))]), &none)
+        );
+        assert_eq!(
+            Err(validation(
+                "One or more parameter values were invalid: An empty set is not allowed"
+            )),
+            table.put_item(
+                item([
+                    ("pk", s("a")),
+                    ("sk", n(1)),
+                    ("ss", AttributeValue::Ss(vec![]))
+                ]),
+                &none
+            )
+        );
+        assert_eq!(
+            Err(validation(
+                "The parameter cannot be converted to a numeric value: one"
+            )),
+            table.put_item(
+                item([("pk", s("a")), ("sk", AttributeValue::N("one".into()))]),
+                &none
+            )
+        );
+        assert_eq!(
+            Err(validation(
+                "The provided key element does not match the schema"
+            )),
+            table.get_item(item([("pk", s("a"))]), &none)
+        );
+        assert_eq!(
+            Err(validation(
+                "The provided key element does not match the schema"
+            )),
+            table.delete_item(item([("pk", s("a")), ("sk", n(1)), ("x", n(1))]), &none)
+        );
+
+        let unused = Expression {
+            expression_attribute_values: Some(HashMap::from([(":unused".to_string(), n(1))])),
+            ..none.clone()
+        };
+        assert_eq!(
+            Err(validation(
+                "Value provided in ExpressionAttributeValues unused in expressions: keys: {:unused}"
+            )),
+            table.put_item(item([("pk", s("a")), ("sk", n(1))]), &unused)
+        );
+
+        let invalid = Expression {
+            condition_expression: Some("a = ".into()),
+            ..none
+        };
+        assert!(matches!(
+            table.put_item(item([("pk", s("a")), ("sk", n(1))]), &invalid),
+            Err(TableError::Validation { message }) if message.starts_with("Invalid ConditionExpression: ")
+        ));
+    }
+}
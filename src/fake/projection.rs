@@ -0,0 +1,231 @@
+//! Applying a projection expression to an item.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    path::{Element, FieldName, IndexedField, Name, Path},
+    value::{List, Map, Value},
+};
+
+/// The parts of an item selected by a projection, before they're put back
+/// together. Elements projected out of a list are kept in index order, and
+/// the list is compacted, as DynamoDB does.
+enum Projected {
+    Whole(Value),
+    Map(BTreeMap<Name, Projected>),
+    List(BTreeMap<usize, Projected>),
+}
+
+/// One step into a value.
+#[derive(Clone, Copy)]
+enum Step<'a> {
+    Name(&'a Name),
+    Index(usize),
+}
+
+/// The attributes of the item at the paths. Paths that don't exist in the
+/// item are left out.
+pub(super) fn project<'a, I>(item: &Map, paths: I) -> Map
+where
+    I: IntoIterator<Item = &'a Path>,
+{
+    let mut projected = BTreeMap::new();
+    for path in paths {
+        let Some(steps) = steps(path) else {
+            continue;
+        };
+
+        if let Some((Step::Name(name), rest)) = steps.split_first() {
+            if let Some(value) = item.map.get(*name) {
+                insert_child(&mut projected, (*name).clone(), value, rest);
+            }
+        }
+    }
+
+    Map {
+        map: projected
+            .into_iter()
+            .map(|(name, projected)| (name, projected.into_value()))
+            .collect(),
+    }
+}
+
+/// The steps into a value a path takes. `None` if the path has names that
+/// weren't resolved.
+fn steps(path: &Path) -> Option<Vec<Step<'_>>> {
+    let mut steps = Vec::new();
+    for element in &path.elements {
+        match element {
+            Element::Name(name) => steps.push(Step::Name(name)),
+            Element::IndexedField(IndexedField {
+                name: FieldName::Name(name),
+                indexes,
+            }) => {
+                steps.push(Step::Name(name));
+                steps.extend(indexes.iter().copied().map(Step::Index));
+            }
+            Element::IndexedField(IndexedField {
+                name: FieldName::NameRef(_),
+                ..
+            })
+            | Element::NameRef(_) => return None,
+        }
+    }
+
+    Some(steps)
+}
+
+/// Projects what's at the steps into the value. Returns whether the steps
+/// exist in the value.
+fn insert(projected: &mut Projected, value: &Value, steps: &[Step<'_>]) -> bool {
+    let Some((&step, rest)) = steps.split_first() else {
+        *projected = Projected::Whole(value.clone());
+        return true;
+    };
+
+    match (projected, step, value) {
+        // Something containing this was already projected.
+        (Projected::Whole(_), _, _) => true,
+        (Projected::Map(projected), Step::Name(name), Value::Map(map)) => map
+            .map
+            .get(name)
+            .is_some_and(|value| insert_child(projected, name.clone(), value, rest)),
+        (Projected::List(projected), Step::Index(index), Value::List(list)) => list
+            .list
+            .get(index)
+            .is_some_and(|value| insert_child(projected, index, value, rest)),
+        _ => false,
+    }
+}
+
+/// Projects what's at the steps into the child of a map or list, keeping the
+/// child only if something was projected into it.
+fn insert_child<K>(
+    projected: &mut BTreeMap<K, Projected>,
+    key: K,
+    value: &Value,
+    steps: &[Step<'_>],
+) -> bool
+where
+    K: Ord,
+{
+    let mut child = projected
+        .remove(&key)
+        .unwrap_or_else(|| Projected::new(steps));
+    let found = insert(&mut child, value, steps);
+    if found || !child.is_empty() {
+        projected.insert(key, child);
+    }
+
+    found
+}
+
+impl Projected {
+    /// An empty projection for a value the steps will go into.
+    fn new(steps: &[Step<'_>]) -> Self {
+        match steps.first() {
+            Some(Step::Index(_)) => Self::List(BTreeMap::new()),
+            // Replaced when it's inserted into.
+            Some(Step::Name(_)) | None => Self::Map(BTreeMap::new()),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Whole(_) => false,
+            Self::Map(map) => map.is_empty(),
+            Self::List(list) => list.is_empty(),
+        }
+    }
+
+    fn into_value(self) -> Value {
+        match self {
+            Self::Whole(value) => value,
+            Self::Map(map) => Map {
+                map: map
+                    .into_iter()
+                    .map(|(name, projected)| (name, projected.into_value()))
+                    .collect(),
+            }
+            .into(),
+            Self::List(list) => list
+                .into_values()
+                .map(Self::into_value)
+                .collect::<List>()
+                .into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        value::{Map, Value},
+        Path,
+    };
+
+    use super::project;
+
+    #[test]
+    fn projection() {
+        let item = Map::from([
+            ("id", Value::new_string("a")),
+            ("n", Value::new_num(1)),
+            (
+                "list",
+                Value::new_list([
+                    Value::from(Map::from([
+                        ("x", Value::new_num(1)),
+                        ("y", Value::new_num(2)),
+                    ])),
+                    Value::new_string("b"),
+                    Value::from(Map::from([
+                        ("x", Value::new_num(3)),
+                        ("y", Value::new_num(4)),
+                    ])),
+                ]),
+            ),
+            (
+                "map",
+                Map::from([("a", Value::new_num(1)), ("b", Value::new_num(2))]).into(),
+            ),
+        ]);
+
+        let paths = [
+            "id",
+            "list[0].x",
+            "list[2].y",
+            "list[0].y",
+            "map.b",
+            "missing",
+            "map.c",
+        ]
+        .map(|path| path.parse::<Path>().unwrap());
+        assert_eq!(
+            Map::from([
+                ("id", Value::new_string("a")),
+                (
+                    "list",
+                    Value::new_list([
+                        Value::from(Map::from([
+                            ("x", Value::new_num(1)),
+                            ("y", Value::new_num(2))
+                        ])),
+                        Value::from(Map::from([("y", Value::new_num(4))])),
+                    ]),
+                ),
+                ("map", Map::from([("b", Value::new_num(2))]).into()),
+            ]),
+            project(&item, &paths)
+        );
+
+        let paths =
+            ["list[1].x", "n[0]", "list", "list[5]"].map(|path| path.parse::<Path>().unwrap());
+        assert_eq!(
+            Map::from([("list", item.map[&"list".into()].clone())]),
+            project(&item, &paths)
+        );
+    }
+}
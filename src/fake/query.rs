@@ -0,0 +1,528 @@
+use crate::{
+    condition::{attribute_type::Type, BeginsWith, Between, Comparator, Comparison, Condition},
+    expression::ExpressionKind,
+    operand::{Operand, OperandType},
+    path::{Element, Path},
+    value::{Map, Value, ValueOrRef},
+    Expression,
+};
+
+use super::{
+    check_condition, check_placeholders,
+    key::{KeyAttribute, KeyValue, StoredKey},
+    parsed, project, to_item, Item, Table, TableError,
+};
+
+/// The options for [`Table::query`], other than the [`Expression`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    limit: Option<usize>,
+    exclusive_start_key: Option<Item>,
+    scan_index_forward: bool,
+}
+
+impl Query {
+    /// Query in sort key order, with no limit, from the start.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The most items to evaluate. It's applied before the filter, so fewer
+    /// items may be returned.
+    #[must_use]
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+
+        self
+    }
+
+    /// Start after this key, which is usually the
+    /// [`Page::last_evaluated_key`] of the previous page.
+    #[must_use]
+    pub fn with_exclusive_start_key(mut self, key: Item) -> Self {
+        self.exclusive_start_key = Some(key);
+
+        self
+    }
+
+    /// `true` (the default) to return items in ascending sort key order,
+    /// `false` for descending.
+    #[must_use]
+    pub fn with_scan_index_forward(mut self, scan_index_forward: bool) -> Self {
+        self.scan_index_forward = scan_index_forward;
+
+        self
+    }
+}
+
+impl Default for Query {
+    fn default() -> Self {
+        Self {
+            limit: None,
+            exclusive_start_key: None,
+            scan_index_forward: true,
+        }
+    }
+}
+
+/// The options for [`Table::scan`], other than the [`Expression`].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Scan {
+    limit: Option<usize>,
+    exclusive_start_key: Option<Item>,
+}
+
+impl Scan {
+    /// Scan the whole table, from the start.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The most items to evaluate. It's applied before the filter, so fewer
+    /// items may be returned.
+    #[must_use]
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+
+        self
+    }
+
+    /// Start after this key, which is usually the
+    /// [`Page::last_evaluated_key`] of the previous page.
+    #[must_use]
+    pub fn with_exclusive_start_key(mut self, key: Item) -> Self {
+        self.exclusive_start_key = Some(key);
+
+        self
+    }
+}
+
+/// A page of results from [`Table::query`] or [`Table::scan`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page {
+    items: Vec<Item>,
+    scanned_count: usize,
+    last_evaluated_key: Option<Item>,
+}
+
+impl Page {
+    /// The items that matched the filter, with the projection applied.
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    pub fn into_items(self) -> Vec<Item> {
+        self.items
+    }
+
+    /// The number of items returned.
+    pub fn count(&self) -> usize {
+        self.items.len()
+    }
+
+    /// The number of items evaluated, before the filter was applied.
+    pub fn scanned_count(&self) -> usize {
+        self.scanned_count
+    }
+
+    /// The key of the last item evaluated, if the limit was reached. Pass it
+    /// to `with_exclusive_start_key` to get the next page.
+    ///
+    /// As with DynamoDB, this is set whenever the limit is reached, even if
+    /// there are no more items, so the next page may be empty.
+    pub fn last_evaluated_key(&self) -> Option<&Item> {
+        self.last_evaluated_key.as_ref()
+    }
+}
+
+impl Table {
+    /// Reads the items in a partition, in sort key order, like [`Query`][1].
+    ///
+    /// Uses the key condition, filter, and projection expressions. The key
+    /// condition must check the partition key for equality, and may have one
+    /// condition on the sort key.
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_Query.html
+    pub fn query(&self, expression: &Expression, query: Query) -> Result<Page, TableError> {
+        check_placeholders(
+            expression,
+            &[
+                ExpressionKind::KeyCondition,
+                ExpressionKind::Filter,
+                ExpressionKind::Projection,
+            ],
+        )?;
+        let key_condition = parsed(expression.parse_key_condition(), "KeyConditionExpression")?
+            .ok_or_else(|| {
+                TableError::validation(
+                    "Either the KeyConditions or KeyConditionExpression parameter \
+                    must be specified in the request.",
+                )
+            })?;
+        let filter = parsed(expression.parse_filter(), "FilterExpression")?;
+        let projection = parsed(expression.parse_projection(), "ProjectionExpression")?;
+
+        let partition = self.partition(&key_condition)?;
+        let start = self.start_key(query.exclusive_start_key)?;
+
+        let mut items = Vec::new();
+        for (key, item) in self.items.range((partition.clone(), None)..) {
+            if key.0 != partition {
+                break;
+            }
+
+            if check_condition(Some(&key_condition), Some(item)).is_ok() {
+                items.push((key, item));
+            }
+        }
+
+        if !query.scan_index_forward {
+            items.reverse();
+        }
+
+        let items = items.into_iter().filter(|(key, _item)| match &start {
+            None => true,
+            Some(start) if query.scan_index_forward => *key > start,
+            Some(start) => *key < start,
+        });
+
+        self.page(items, query.limit, filter.as_ref(), projection.as_deref())
+    }
+
+    /// Reads every item in the table, like [`Scan`][1].
+    ///
+    /// Uses the filter and projection expressions.
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_Scan.html
+    pub fn scan(&self, expression: &Expression, scan: Scan) -> Result<Page, TableError> {
+        check_placeholders(
+            expression,
+            &[ExpressionKind::Filter, ExpressionKind::Projection],
+        )?;
+        let filter = parsed(expression.parse_filter(), "FilterExpression")?;
+        let projection = parsed(expression.parse_projection(), "ProjectionExpression")?;
+
+        let start = self.start_key(scan.exclusive_start_key)?;
+        let items = self.items.iter().filter(|(key, _item)| match &start {
+            None => true,
+            Some(start) => *key > start,
+        });
+
+        self.page(items, scan.limit, filter.as_ref(), projection.as_deref())
+    }
+
+    /// Evaluates items up to the limit, then applies the filter and
+    /// projection, as DynamoDB does.
+    fn page<'a, I>(
+        &self,
+        items: I,
+        limit: Option<usize>,
+        filter: Option<&Condition>,
+        projection: Option<&[Path]>,
+    ) -> Result<Page, TableError>
+    where
+        I: Iterator<Item = (&'a StoredKey, &'a Map)>,
+    {
+        if limit == Some(0) {
+            return Err(TableError::validation(
+                "1 validation error detected: Value '0' at 'limit' failed to satisfy \
+                constraint: Member must have value greater than or equal to 1",
+            ));
+        }
+
+        let mut page = Page {
+            items: Vec::new(),
+            scanned_count: 0,
+            last_evaluated_key: None,
+        };
+        for (_key, item) in items {
+            page.scanned_count += 1;
+
+            if check_condition(filter, Some(item)).is_ok() {
+                page.items.push(match projection {
+                    Some(paths) => to_item(project(item, paths)),
+                    None => to_item(item.clone()),
+                });
+            }
+
+            if limit == Some(page.scanned_count) {
+                page.last_evaluated_key = Some(self.key_item(item));
+                break;
+            }
+        }
+
+        Ok(page)
+    }
+
+    fn start_key(&self, key: Option<Item>) -> Result<Option<StoredKey>, TableError> {
+        key.map(|key| {
+            self.key(key)
+                .map(|(key, _key)| key)
+                .map_err(|err| match err {
+                    TableError::Validation { message } => TableError::validation(format!(
+                        "The provided starting key is invalid: {message}"
+                    )),
+                    err => err,
+                })
+        })
+        .transpose()
+    }
+
+    /// The partition a key condition selects. The rest of the key condition
+    /// may only have one condition on the sort key.
+    fn partition(&self, key_condition: &Condition) -> Result<KeyValue, TableError> {
+        let not_supported = || TableError::validation("Query key condition not supported");
+        let is_key = |path: &Path, key: &KeyAttribute| matches!(path.elements.as_slice(), [Element::Name(name)] if *name == key.name);
+        let is_sort_key = |path: &Path| {
+            self.sort_key
+                .as_ref()
+                .is_some_and(|sort_key| is_key(path, sort_key))
+        };
+
+        let mut conditions = Vec::new();
+        flatten_and(key_condition, &mut conditions);
+
+        let mut partition = None;
+        let mut sort_key_conditions = 0;
+        for condition in conditions {
+            match condition {
+                Condition::Comparison(Comparison { left, cmp, right }) => {
+                    let (path, value) = path_and_value(left, right).ok_or_else(not_supported)?;
+                    if is_key(path, &self.partition_key) && *cmp == Comparator::Eq {
+                        if partition.replace(value).is_some() {
+                            return Err(not_supported());
+                        }
+                    } else if is_sort_key(path) && *cmp != Comparator::Ne {
+                        sort_key_conditions += 1;
+                    } else {
+                        return Err(not_supported());
+                    }
+                }
+                Condition::Between(Between { op, .. }) => match &op.op {
+                    OperandType::Path(path) if is_sort_key(path) => sort_key_conditions += 1,
+                    _ => return Err(not_supported()),
+                },
+                Condition::BeginsWith(BeginsWith { path, .. }) if is_sort_key(path) => {
+                    sort_key_conditions += 1
+                }
+                _ => return Err(not_supported()),
+            }
+        }
+
+        if sort_key_conditions > 1 {
+            return Err(not_supported());
+        }
+
+        let value = partition.ok_or_else(|| {
+            TableError::validation(format!(
+                "Query condition missed key schema element: {}",
+                self.partition_key.name
+            ))
+        })?;
+        if Type::of(value) != self.partition_key.key_type {
+            return Err(TableError::validation(
+                "One or more parameter values were invalid: \
+                Condition parameter type does not match schema type",
+            ));
+        }
+
+        self.partition_key.value(
+            &Map::from([(self.partition_key.name.clone(), value.clone())]),
+            not_supported,
+        )
+    }
+}
+
+/// The conditions `AND`ed together.
+fn flatten_and<'a>(condition: &'a Condition, conditions: &mut Vec<&'a Condition>) {
+    match condition {
+        Condition::And(and) => {
+            flatten_and(&and.left, conditions);
+            flatten_and(&and.right, conditions);
+        }
+        Condition::Parenthetical(parenthetical) => {
+            flatten_and(&parenthetical.condition, conditions);
+        }
+        condition => conditions.push(condition),
+    }
+}
+
+/// The path and value being compared, whichever side they're on.
+fn path_and_value<'a>(left: &'a Operand, right: &'a Operand) -> Option<(&'a Path, &'a Value)> {
+    match (&left.op, &right.op) {
+        (OperandType::Path(path), OperandType::Scalar(ValueOrRef::Value(value)))
+        | (OperandType::Scalar(ValueOrRef::Value(value)), OperandType::Path(path)) => {
+            Some((path, value))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        condition::attribute_type::Type,
+        fake::{Item, Table, TableError},
+        Expression, Num, Path,
+    };
+
+    use super::{Query, Scan};
+
+    fn table() -> Table {
+        let mut table = Table::new("pk", Type::String).with_sort_key("sk", Type::Number);
+        for (pk, sk) in [("a", 3), ("a", 10), ("a", 1), ("b", 2), ("a", 2)] {
+            table
+                .put_item(key(pk, sk), &Expression::builder().build())
+                .unwrap();
+        }
+
+        table
+    }
+
+    fn key(pk: &str, sk: i32) -> Item {
+        [
+            ("pk".to_string(), AttributeValue::S(pk.into())),
+            ("sk".to_string(), AttributeValue::N(sk.to_string())),
+        ]
+        .into()
+    }
+
+    fn sort_keys(items: &[Item]) -> Vec<&str> {
+        items
+            .iter()
+            .map(|item| item["sk"].as_n().unwrap().as_str())
+            .collect()
+    }
+
+    #[test]
+    fn query() {
+        let table = table();
+        let partition = |pk: &str| Path::new_name("pk").key().equal(pk);
+
+        let page = table
+            .query(
+                &Expression::builder()
+                    .with_key_condition(partition("a"))
+                    .build(),
+                Query::new(),
+            )
+            .unwrap();
+        assert_eq!(vec!["1", "2", "3", "10"], sort_keys(page.items()));
+        assert_eq!(None, page.last_evaluated_key());
+
+        // The limit is applied before the filter.
+        let expression = Expression::builder()
+            .with_key_condition(
+                partition("a").and(Path::new_name("sk").key().greater_than(Num::new(1))),
+            )
+            .with_filter(Path::new_name("sk").not_equal(Num::new(3)))
+            .with_projection(["sk"])
+            .build();
+        let page = table
+            .query(
+                &expression,
+                Query::new().with_scan_index_forward(false).with_limit(2),
+            )
+            .unwrap();
+        assert_eq!(vec!["10"], sort_keys(page.items()));
+        assert_eq!(1, page.count());
+        assert_eq!(2, page.scanned_count());
+        assert_eq!(Some(&key("a", 3)), page.last_evaluated_key());
+        assert_eq!(
+            vec![Item::from([(
+                "sk".to_string(),
+                AttributeValue::N("10".into())
+            )])],
+            page.items()
+        );
+
+        let page = table
+            .query(
+                &expression,
+                Query::new()
+                    .with_scan_index_forward(false)
+                    .with_limit(2)
+                    .with_exclusive_start_key(page.last_evaluated_key().unwrap().clone()),
+            )
+            .unwrap();
+        assert_eq!(vec!["2"], sort_keys(page.items()));
+        assert_eq!(None, page.last_evaluated_key());
+
+        for (expression, message) in [
+            (
+                Expression::builder()
+                    .with_key_condition(Path::new_name("sk").key().equal(Num::new(1)))
+                    .build(),
+                "Query condition missed key schema element: pk",
+            ),
+            (
+                Expression::builder()
+                    .with_key_condition(
+                        partition("a").and(Path::new_name("other").key().equal(Num::new(1))),
+                    )
+                    .build(),
+                "Query key condition not supported",
+            ),
+            (
+                Expression::builder()
+                    .with_key_condition(Path::new_name("pk").key().equal(Num::new(1)))
+                    .build(),
+                "One or more parameter values were invalid: \
+                Condition parameter type does not match schema type",
+            ),
+            (
+                Expression::builder().build(),
+                "Either the KeyConditions or KeyConditionExpression parameter \
+                must be specified in the request.",
+            ),
+        ] {
+            assert_eq!(
+                Err(TableError::Validation {
+                    message: message.into()
+                }),
+                table.query(&expression, Query::new())
+            );
+        }
+    }
+
+    #[test]
+    fn scan() {
+        let table = table();
+        let expression = Expression::builder()
+            .with_filter(Path::new_name("sk").greater_than(Num::new(1)))
+            .build();
+
+        let page = table.scan(&expression, Scan::new()).unwrap();
+        assert_eq!(vec!["2", "3", "10", "2"], sort_keys(page.items()));
+        assert_eq!(5, page.scanned_count());
+
+        let mut pages = Vec::new();
+        let mut scan = Scan::new().with_limit(2);
+        loop {
+            let page = table.scan(&expression, scan.clone()).unwrap();
+            pages.push(sort_keys(page.items()).join(","));
+            match page.last_evaluated_key() {
+                Some(key) => scan = scan.with_exclusive_start_key(key.clone()),
+                None => break,
+            }
+        }
+        assert_eq!(vec!["2", "3,10", "2"], pages);
+
+        assert_eq!(
+            Err(TableError::Validation {
+                message: "The provided starting key is invalid: \
+                    The provided key element does not match the schema"
+                    .into()
+            }),
+            table.scan(
+                &expression,
+                Scan::new().with_exclusive_start_key(
+                    [("pk".to_string(), AttributeValue::S("a".into()))].into()
+                )
+            )
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! DynamoDB's JSON representation of attribute values.
+
+use aws_sdk_dynamodb::{primitives::Blob, types::AttributeValue};
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Map as JsonMap, Value as Json};
+
+use super::{ApiError, Item};
+
+pub(super) fn to_json(value: &AttributeValue) -> Json {
+    let base64 = |blob: &Blob| general_purpose::STANDARD.encode(blob.as_ref());
+
+    match value {
+        AttributeValue::S(s) => json!({ "S": s }),
+        AttributeValue::N(n) => json!({ "N": n }),
+        AttributeValue::B(b) => json!({ "B": base64(b) }),
+        AttributeValue::Bool(b) => json!({ "BOOL": b }),
+        AttributeValue::Null(_) => json!({ "NULL": true }),
+        AttributeValue::Ss(ss) => json!({ "SS": ss }),
+        AttributeValue::Ns(ns) => json!({ "NS": ns }),
+        AttributeValue::Bs(bs) => json!({ "BS": bs.iter().map(base64).collect::<Vec<_>>() }),
+        AttributeValue::L(list) => json!({ "L": list.iter().map(to_json).collect::<Vec<_>>() }),
+        AttributeValue::M(map) => json!({ "M": item_to_json(map) }),
+        _ => json!({}),
+    }
+}
+
+pub(super) fn from_json(value: &Json) -> Result<AttributeValue, ApiError> {
+    let invalid = || ApiError::serialization(format!("Unexpected attribute value: {value}"));
+    let string = |value: &Json| value.as_str().map(str::to_owned).ok_or_else(invalid);
+    let strings = |value: &Json| {
+        value
+            .as_array()
+            .ok_or_else(invalid)?
+            .iter()
+            .map(string)
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let blob = |value: &Json| {
+        general_purpose::STANDARD
+            .decode(value.as_str().ok_or_else(invalid)?)
+            .map(Blob::new)
+            .map_err(|_| invalid())
+    };
+
+    let object = value.as_object().ok_or_else(invalid)?;
+    let mut fields = object.iter();
+    let (Some((kind, value)), None) = (fields.next(), fields.next()) else {
+        return Err(ApiError::validation(
+            "Supplied AttributeValue has more than one datatypes set, \
+            must contain exactly one of the supported datatypes",
+        ));
+    };
+
+    Ok(match kind.as_str() {
+        "S" => AttributeValue::S(string(value)?),
+        "N" => AttributeValue::N(string(value)?),
+        "B" => AttributeValue::B(blob(value)?),
+        "BOOL" => AttributeValue::Bool(value.as_bool().ok_or_else(invalid)?),
+        "NULL" => AttributeValue::Null(value.as_bool().ok_or_else(invalid)?),
+        "SS" => AttributeValue::Ss(strings(value)?),
+        "NS" => AttributeValue::Ns(strings(value)?),
+        "BS" => AttributeValue::Bs(
+            value
+                .as_array()
+                .ok_or_else(invalid)?
+                .iter()
+                .map(blob)
+                .collect::<Result<_, _>>()?,
+        ),
+        "L" => AttributeValue::L(
+            value
+                .as_array()
+                .ok_or_else(invalid)?
+                .iter()
+                .map(from_json)
+                .collect::<Result<_, _>>()?,
+        ),
+        "M" => AttributeValue::M(item_from_json(value)?),
+        _ => return Err(invalid()),
+    })
+}
+
+pub(super) fn item_to_json(item: &Item) -> Json {
+    Json::Object(
+        item.iter()
+            .map(|(name, value)| (name.clone(), to_json(value)))
+            .collect::<JsonMap<_, _>>(),
+    )
+}
+
+pub(super) fn item_from_json(value: &Json) -> Result<Item, ApiError> {
+    value
+        .as_object()
+        .ok_or_else(|| ApiError::serialization(format!("Expected an item: {value}")))?
+        .iter()
+        .map(|(name, value)| from_json(value).map(|value| (name.clone(), value)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use aws_sdk_dynamodb::{primitives::Blob, types::AttributeValue};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::{from_json, to_json};
+
+    #[test]
+    fn round_trip() {
+        let value = AttributeValue::M(
+            [
+                ("s".to_string(), AttributeValue::S("a".into())),
+                ("n".to_string(), AttributeValue::N("1.5".into())),
+                ("b".to_string(), AttributeValue::B(Blob::new("hi"))),
+                ("bool".to_string(), AttributeValue::Bool(true)),
+                ("null".to_string(), AttributeValue::Null(true)),
+                ("ss".to_string(), AttributeValue::Ss(vec!["x".into()])),
+                ("ns".to_string(), AttributeValue::Ns(vec!["2".into()])),
+                ("bs".to_string(), AttributeValue::Bs(vec![Blob::new("yo")])),
+                (
+                    "l".to_string(),
+                    AttributeValue::L(vec![AttributeValue::S("c".into())]),
+                ),
+            ]
+            .into(),
+        );
+
+        let json = to_json(&value);
+        assert_eq!(
+            json!({
+                "M": {
+                    "s": { "S": "a" },
+                    "n": { "N": "1.5" },
+                    "b": { "B": "aGk=" },
+                    "bool": { "BOOL": true },
+                    "null": { "NULL": true },
+                    "ss": { "SS": ["x"] },
+                    "ns": { "NS": ["2"] },
+                    "bs": { "BS": ["eW8="] },
+                    "l": { "L": [{ "S": "c" }] },
+                }
+            }),
+            json
+        );
+        assert_eq!(value, from_json(&json).unwrap());
+
+        assert!(from_json(&json!({ "S": "a", "N": "1" })).is_err());
+        assert!(from_json(&json!({ "X": "a" })).is_err());
+    }
+}
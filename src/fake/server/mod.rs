@@ -0,0 +1,504 @@
+//! A local HTTP server that speaks the DynamoDB JSON protocol, backed by
+//! [`Table`]s, so an [`aws_sdk_dynamodb::Client`] can be used against it in
+//! tests, instead of DynamoDB Local.
+//!
+//! Requires the `server` feature.
+//!
+//! It supports `CreateTable`, `DescribeTable`, `DeleteTable`, `ListTables`,
+//! `PutItem`, `GetItem`, `UpdateItem`, `DeleteItem`, `Query`, `Scan`,
+//! `BatchGetItem`, `BatchWriteItem`, and `TransactWriteItems`. Secondary
+//! indexes, streams, and capacity aren't supported, and requests aren't
+//! authenticated.
+//!
+//! ```no_run
+//! use aws_sdk_dynamodb::{
+//!     config::{BehaviorVersion, Credentials, Region},
+//!     Client,
+//! };
+//! use dynamodb_expression::fake::server::Server;
+//!
+//! # async fn example() -> std::io::Result<()> {
+//! let server = Server::bind("127.0.0.1:0")?;
+//! let endpoint_url = server.endpoint_url()?;
+//! server.spawn();
+//!
+//! let client = Client::from_conf(
+//!     aws_sdk_dynamodb::Config::builder()
+//!         .behavior_version(BehaviorVersion::latest())
+//!         .endpoint_url(endpoint_url)
+//!         .region(Region::new("us-local-1"))
+//!         .credentials_provider(Credentials::new("local", "local", None, None, "local"))
+//!         .build(),
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+mod json;
+mod operations;
+
+use core::fmt;
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    thread::{self, JoinHandle},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::Value as Json;
+
+use super::{Item, Table, TableError};
+
+/// A local DynamoDB stand-in, listening for HTTP requests.
+///
+/// See the [module docs](self) for an example.
+#[derive(Debug)]
+pub struct Server {
+    listener: TcpListener,
+    state: Arc<State>,
+}
+
+impl Server {
+    /// Listens on the address. Use port `0` to have the OS pick a free port,
+    /// then get it with [`Server::local_addr`] or [`Server::endpoint_url`].
+    pub fn bind<A>(addr: A) -> io::Result<Self>
+    where
+        A: ToSocketAddrs,
+    {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            state: Arc::default(),
+        })
+    }
+
+    /// Adds a table, as if it were created with `CreateTable`.
+    #[must_use]
+    pub fn with_table<N>(self, name: N, table: Table) -> Self
+    where
+        N: Into<String>,
+    {
+        self.state.tables().insert(name.into(), Entry::new(table));
+
+        self
+    }
+
+    /// The address the server is listening on.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// The URL to give to the AWS SDK as the endpoint URL.
+    pub fn endpoint_url(&self) -> io::Result<String> {
+        self.local_addr().map(|addr| format!("http://{addr}"))
+    }
+
+    /// Handles requests on the current thread. Each connection gets its own
+    /// thread. Only returns if accepting a connection fails.
+    pub fn serve(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let state = Arc::clone(&self.state);
+            thread::spawn(move || {
+                // The client hanging up isn't the server's problem.
+                let _ = state.connection(stream);
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Handles requests on a background thread.
+    pub fn spawn(self) -> JoinHandle<io::Result<()>> {
+        thread::spawn(move || self.serve())
+    }
+}
+
+/// The tables, shared between connections.
+#[derive(Debug, Default)]
+struct State {
+    tables: Mutex<Tables>,
+    request_id: AtomicU64,
+}
+
+type Tables = BTreeMap<String, Entry>;
+
+/// A table, and what's needed to describe it.
+#[derive(Debug, Clone)]
+struct Entry {
+    table: Table,
+    created: f64,
+}
+
+impl Entry {
+    fn new(table: Table) -> Self {
+        Self {
+            table,
+            created: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|created| created.as_secs_f64())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl State {
+    fn tables(&self) -> std::sync::MutexGuard<'_, Tables> {
+        // A panic while handling a request doesn't leave a table half-written,
+        // since every write replaces a whole item.
+        self.tables.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Handles requests on the connection until the client closes it.
+    fn connection(&self, stream: TcpStream) -> io::Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut writer = stream;
+
+        while let Some(request) = read_request(&mut reader)? {
+            let (status, body) = match self.request(&request) {
+                Ok(body) => ("200 OK", body),
+                Err(err) if request.too_large => ("413 Payload Too Large", err.into_json()),
+                Err(err) => ("400 Bad Request", err.into_json()),
+            };
+
+            let body = body.to_string();
+            write!(
+                writer,
+                "HTTP/1.1 {status}\r\n\
+                Content-Type: application/x-amz-json-1.0\r\n\
+                Content-Length: {}\r\n\
+                x-amzn-RequestId: {}\r\n\
+                \r\n\
+                {body}",
+                body.len(),
+                self.request_id.fetch_add(1, Ordering::Relaxed),
+            )?;
+            writer.flush()?;
+        }
+
+        Ok(())
+    }
+
+    fn request(&self, request: &Request) -> Result<Json, ApiError> {
+        if request.too_large {
+            return Err(ApiError::validation(format!(
+                "Request size exceeds {MAX_BODY} bytes"
+            )));
+        }
+
+        let operation = request
+            .target
+            .as_deref()
+            .and_then(|target| target.strip_prefix("DynamoDB_20120810."))
+            .ok_or_else(|| {
+                ApiError::new(
+                    "UnknownOperationException",
+                    format!("Unknown target: {:?}", request.target),
+                )
+            })?;
+        let body = serde_json::from_slice(&request.body)
+            .map_err(|err| ApiError::serialization(err.to_string()))?;
+
+        operations::handle(&mut self.tables(), operation, &body)
+    }
+}
+
+/// The largest request body DynamoDB accepts, 16 MB.
+const MAX_BODY: usize = 16 * 1024 * 1024;
+
+/// The parts of an HTTP request that matter here.
+struct Request {
+    target: Option<String>,
+    body: Vec<u8>,
+    /// The body was over [`MAX_BODY`], and was skipped rather than read.
+    too_large: bool,
+}
+
+/// Reads the next HTTP request. `None` if the connection was closed.
+fn read_request<R>(reader: &mut R) -> io::Result<Option<Request>>
+where
+    R: BufRead,
+{
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+
+    let mut target = None;
+    let mut length = 0;
+    let mut chunked = false;
+    loop {
+        line.clear();
+        reader.read_line(&mut line)?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        let (name, value) = header
+            .split_once(':')
+            .ok_or_else(|| invalid("invalid header"))?;
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "x-amz-target" => target = Some(value.to_owned()),
+            "content-length" => {
+                length = value
+                    .parse()
+                    .map_err(|_| invalid("invalid content length"))?
+            }
+            "transfer-encoding" => chunked = value.eq_ignore_ascii_case("chunked"),
+            _ => {}
+        }
+    }
+
+    let mut body = Vec::new();
+    let mut too_large = false;
+    // Reads the next `size` bytes of the body, unless that takes it over the
+    // limit, in which case they're skipped.
+    let mut read_body = |reader: &mut R, size: usize| -> io::Result<()> {
+        too_large = too_large || size > MAX_BODY - body.len();
+        if too_large {
+            body.clear();
+            let skipped = io::copy(&mut reader.take(size as u64), &mut io::sink())?;
+            if skipped < size as u64 {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+        } else {
+            let start = body.len();
+            body.resize(start + size, 0);
+            reader.read_exact(&mut body[start..])?;
+        }
+
+        Ok(())
+    };
+
+    if chunked {
+        loop {
+            line.clear();
+            reader.read_line(&mut line)?;
+            let size = line.trim_end().split(';').next().unwrap_or_default();
+            let size =
+                usize::from_str_radix(size, 16).map_err(|_| invalid("invalid chunk size"))?;
+            if size == 0 {
+                // Skip any trailers, up to the empty line that ends the body.
+                loop {
+                    line.clear();
+                    if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+                        break;
+                    }
+                }
+                break;
+            }
+
+            read_body(reader, size)?;
+            reader.read_line(&mut line)?;
+        }
+    } else {
+        read_body(reader, length)?;
+    }
+
+    Ok(Some(Request {
+        target,
+        body,
+        too_large,
+    }))
+}
+
+/// An error response, in the form the AWS SDK expects.
+#[derive(Debug)]
+struct ApiError {
+    kind: &'static str,
+    message: String,
+    fields: serde_json::Map<String, Json>,
+}
+
+impl ApiError {
+    fn new<T>(kind: &'static str, message: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            kind,
+            message: message.into(),
+            fields: serde_json::Map::new(),
+        }
+    }
+
+    fn validation<T>(message: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::new("ValidationException", message)
+    }
+
+    fn serialization<T>(message: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::new("SerializationException", message)
+    }
+
+    fn resource_not_found(table_name: &str) -> Self {
+        Self::new(
+            "ResourceNotFoundException",
+            format!("Requested resource not found: Table: {table_name} not found"),
+        )
+    }
+
+    /// The error for a failed operation on a table. `return_item` is whether
+    /// the request asked for the item when a condition fails.
+    fn table(err: TableError, return_item: bool) -> Self {
+        let message = err.to_string();
+        let kind = err.exception_name();
+        let mut api_error = Self::new(kind, message);
+        if let TableError::ConditionalCheckFailed { item: Some(item) } = err {
+            if return_item {
+                api_error = api_error.with_field("Item", json::item_to_json(&item));
+            }
+        }
+
+        api_error
+    }
+
+    fn with_field(mut self, name: &str, value: Json) -> Self {
+        self.fields.insert(name.to_owned(), value);
+
+        self
+    }
+
+    fn into_json(self) -> Json {
+        let mut body = self.fields;
+        body.insert(
+            "__type".into(),
+            format!("com.amazonaws.dynamodb.v20120810#{}", self.kind).into(),
+        );
+        body.insert("message".into(), self.message.into());
+
+        Json::Object(body)
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.message)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::{read_request, ApiError, State, MAX_BODY};
+
+    #[test]
+    fn requests() {
+        let mut input = Cursor::new(
+            "POST / HTTP/1.1\r\n\
+            X-Amz-Target: DynamoDB_20120810.ListTables\r\n\
+            content-length: 2\r\n\
+            \r\n\
+            {}\
+            POST / HTTP/1.1\r\n\
+            Transfer-Encoding: chunked\r\n\
+            \r\n\
+            3\r\n{\"a\r\n\
+            5\r\n\":1}\n\r\n\
+            0\r\n\
+            Trailer: x\r\n\
+            \r\n\
+            POST / HTTP/1.1\r\n\
+            Transfer-Encoding: chunked\r\n\
+            \r\n\
+            2\r\n{}\r\n\
+            0\r\n\
+            \r\n",
+        );
+
+        let request = read_request(&mut input).unwrap().unwrap();
+        assert_eq!(
+            Some("DynamoDB_20120810.ListTables"),
+            request.target.as_deref()
+        );
+        assert_eq!(b"{}", request.body.as_slice());
+
+        let request = read_request(&mut input).unwrap().unwrap();
+        assert_eq!(None, request.target);
+        assert_eq!(b"{\"a\":1}\n", request.body.as_slice());
+
+        // The trailers, and the line after them, aren't part of the next
+        // request.
+        let request = read_request(&mut input).unwrap().unwrap();
+        assert_eq!(b"{}", request.body.as_slice());
+
+        assert!(read_request(&mut input).unwrap().is_none());
+    }
+
+    #[test]
+    fn too_large() {
+        let mut input = Cursor::new(format!(
+            "POST / HTTP/1.1\r\n\
+            Content-Length: {}\r\n\
+            \r\n\
+            {}\
+            POST / HTTP/1.1\r\n\
+            Content-Length: 2\r\n\
+            \r\n\
+            {{}}",
+            MAX_BODY + 1,
+            " ".repeat(MAX_BODY + 1),
+        ));
+        let mut chunked = Cursor::new(format!(
+            "POST / HTTP/1.1\r\n\
+            Transfer-Encoding: chunked\r\n\
+            \r\n\
+            {MAX_BODY:x}\r\n{}\r\n\
+            1\r\n \r\n\
+            0\r\n\
+            \r\n",
+            " ".repeat(MAX_BODY),
+        ));
+
+        let request = read_request(&mut input).unwrap().unwrap();
+        assert!(request.too_large);
+        assert!(request.body.is_empty());
+        assert_eq!(
+            json!({
+                "__type": "com.amazonaws.dynamodb.v20120810#ValidationException",
+                "message": "Request size exceeds 16777216 bytes",
+            }),
+            State::default().request(&request).unwrap_err().into_json()
+        );
+
+        // The rest of the connection is unaffected.
+        let request = read_request(&mut input).unwrap().unwrap();
+        assert!(!request.too_large);
+        assert_eq!(b"{}", request.body.as_slice());
+
+        let request = read_request(&mut chunked).unwrap().unwrap();
+        assert!(request.too_large);
+        assert!(read_request(&mut chunked).unwrap().is_none());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            json!({
+                "__type": "com.amazonaws.dynamodb.v20120810#ValidationException",
+                "message": "nope",
+                "Extra": 1,
+            }),
+            ApiError::validation("nope")
+                .with_field("Extra", json!(1))
+                .into_json()
+        );
+    }
+}
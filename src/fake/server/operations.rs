@@ -0,0 +1,789 @@
+//! The DynamoDB operations, as JSON requests and responses.
+
+use std::collections::{BTreeSet, HashMap};
+
+use aws_sdk_dynamodb::types::ReturnValue;
+use serde_json::{json, Map as JsonMap, Value as Json};
+
+use crate::{
+    condition::attribute_type::Type,
+    fake::{Page, Query, Scan},
+    Expression,
+};
+
+use super::{
+    json::{item_from_json, item_to_json},
+    ApiError, Entry, Item, Table, TableError, Tables,
+};
+
+/// Handles a request for the operation, named as in the `X-Amz-Target`
+/// header, e.g., `PutItem`.
+pub(super) fn handle(tables: &mut Tables, operation: &str, body: &Json) -> Result<Json, ApiError> {
+    let request = Fields::new(body)?;
+
+    match operation {
+        "CreateTable" => create_table(tables, &request),
+        "DescribeTable" => {
+            let name = request.required_str("TableName")?;
+            let entry = tables
+                .get(name)
+                .ok_or_else(|| ApiError::resource_not_found(name))?;
+
+            Ok(json!({ "Table": describe(name, entry) }))
+        }
+        "DeleteTable" => {
+            let name = request.required_str("TableName")?;
+            let entry = tables
+                .remove(name)
+                .ok_or_else(|| ApiError::resource_not_found(name))?;
+
+            Ok(json!({ "TableDescription": describe(name, &entry) }))
+        }
+        "ListTables" => Ok(json!({ "TableNames": tables.keys().collect::<Vec<_>>() })),
+        "PutItem" => {
+            let return_old = request.return_old()?;
+            let old = request
+                .table(tables)?
+                .put_item(request.required_item("Item")?, &request.expression()?)
+                .map_err(|err| request.table_error(err))?;
+
+            Ok(attributes(old.filter(|_| return_old)))
+        }
+        "GetItem" => {
+            let item = request
+                .table(tables)?
+                .get_item(request.required_item("Key")?, &request.expression()?)
+                .map_err(|err| request.table_error(err))?;
+
+            Ok(match item {
+                Some(item) => json!({ "Item": item_to_json(&item) }),
+                None => json!({}),
+            })
+        }
+        "UpdateItem" => {
+            let return_values = request
+                .str("ReturnValues")?
+                .map_or(ReturnValue::None, ReturnValue::from);
+            let updated = request
+                .table(tables)?
+                .update_item(
+                    request.required_item("Key")?,
+                    &request.expression()?,
+                    return_values,
+                )
+                .map_err(|err| request.table_error(err))?;
+
+            Ok(attributes(updated))
+        }
+        "DeleteItem" => {
+            let return_old = request.return_old()?;
+            let old = request
+                .table(tables)?
+                .delete_item(request.required_item("Key")?, &request.expression()?)
+                .map_err(|err| request.table_error(err))?;
+
+            Ok(attributes(old.filter(|_| return_old)))
+        }
+        "Query" => {
+            let mut query = Query::new();
+            if let Some(limit) = request.limit()? {
+                query = query.with_limit(limit);
+            }
+            if let Some(key) = request.item("ExclusiveStartKey")? {
+                query = query.with_exclusive_start_key(key);
+            }
+            if let Some(forward) = request.bool("ScanIndexForward")? {
+                query = query.with_scan_index_forward(forward);
+            }
+
+            let page = request
+                .table(tables)?
+                .query(&request.expression()?, query)
+                .map_err(|err| request.table_error(err))?;
+
+            Ok(page_json(&request, page)?)
+        }
+        "Scan" => {
+            let mut scan = Scan::new();
+            if let Some(limit) = request.limit()? {
+                scan = scan.with_limit(limit);
+            }
+            if let Some(key) = request.item("ExclusiveStartKey")? {
+                scan = scan.with_exclusive_start_key(key);
+            }
+
+            let page = request
+                .table(tables)?
+                .scan(&request.expression()?, scan)
+                .map_err(|err| request.table_error(err))?;
+
+            Ok(page_json(&request, page)?)
+        }
+        "BatchGetItem" => batch_get_item(tables, &request),
+        "BatchWriteItem" => batch_write_item(tables, &request),
+        "TransactWriteItems" => transact_write_items(tables, &request),
+        _ => Err(ApiError::new(
+            "UnknownOperationException",
+            format!("Unknown operation: {operation}"),
+        )),
+    }
+}
+
+fn create_table(tables: &mut Tables, request: &Fields) -> Result<Json, ApiError> {
+    let name = request.required_str("TableName")?;
+    if tables.contains_key(name) {
+        return Err(ApiError::new(
+            "ResourceInUseException",
+            format!("Table already exists: {name}"),
+        ));
+    }
+
+    let mut definitions = HashMap::new();
+    for definition in request.required_array("AttributeDefinitions")? {
+        let definition = Fields::new(definition)?;
+        let key_type = match definition.required_str("AttributeType")? {
+            "S" => Type::String,
+            "N" => Type::Number,
+            "B" => Type::Binary,
+            other => {
+                return Err(ApiError::validation(format!(
+                    "Member must satisfy enum value set: [B, N, S], not {other}"
+                )))
+            }
+        };
+        definitions.insert(definition.required_str("AttributeName")?, key_type);
+    }
+
+    let mut hash = None;
+    let mut range = None;
+    for element in request.required_array("KeySchema")? {
+        let element = Fields::new(element)?;
+        let attribute = element.required_str("AttributeName")?;
+        let key_type = *definitions.get(attribute).ok_or_else(|| {
+            ApiError::validation(format!(
+                "One or more parameter values were invalid: \
+                Some index key attributes are not defined in AttributeDefinitions. \
+                Keys: [{attribute}]"
+            ))
+        })?;
+
+        let slot = match element.required_str("KeyType")? {
+            "HASH" => &mut hash,
+            "RANGE" => &mut range,
+            other => {
+                return Err(ApiError::validation(format!(
+                    "Member must satisfy enum value set: [HASH, RANGE], not {other}"
+                )))
+            }
+        };
+        if slot.replace((attribute, key_type)).is_some() {
+            return Err(ApiError::validation("Too many key schema elements"));
+        }
+    }
+
+    let (partition_key, key_type) = hash.ok_or_else(|| {
+        ApiError::validation("1 validation error detected: No Hash Key specified in schema")
+    })?;
+    let mut table = Table::new(partition_key, key_type);
+    if let Some((sort_key, key_type)) = range {
+        table = table.with_sort_key(sort_key, key_type);
+    }
+
+    let entry = Entry::new(table);
+    let description = describe(name, &entry);
+    tables.insert(name.to_owned(), entry);
+
+    Ok(json!({ "TableDescription": description }))
+}
+
+fn describe(name: &str, entry: &Entry) -> Json {
+    let keys = [
+        Some((entry.table.partition_key(), "HASH")),
+        entry.table.sort_key().map(|key| (key, "RANGE")),
+    ];
+    let keys = keys.iter().flatten();
+
+    json!({
+        "TableName": name,
+        "TableArn": format!("arn:aws:dynamodb:local:000000000000:table/{name}"),
+        "TableStatus": "ACTIVE",
+        "CreationDateTime": entry.created,
+        "ItemCount": entry.table.len(),
+        "KeySchema": keys
+            .clone()
+            .map(|((name, _type), key_type)| json!({ "AttributeName": name, "KeyType": key_type }))
+            .collect::<Vec<_>>(),
+        "AttributeDefinitions": keys
+            .map(|((name, key_type), _key_type)| {
+                json!({ "AttributeName": name, "AttributeType": key_type.as_str() })
+            })
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn batch_get_item(tables: &mut Tables, request: &Fields) -> Result<Json, ApiError> {
+    let mut responses = JsonMap::new();
+    for (name, table_request) in request.required_object("RequestItems")? {
+        let table = tables
+            .get(name)
+            .map(|entry| &entry.table)
+            .ok_or_else(|| ApiError::resource_not_found(name))?;
+        let table_request = Fields::new(table_request)?;
+        let expression = table_request.expression()?;
+
+        let mut items = Vec::new();
+        for key in table_request.required_array("Keys")? {
+            if let Some(item) = table
+                .get_item(item_from_json(key)?, &expression)
+                .map_err(|err| ApiError::table(err, false))?
+            {
+                items.push(item_to_json(&item));
+            }
+        }
+
+        responses.insert(name.clone(), items.into());
+    }
+
+    Ok(json!({ "Responses": responses, "UnprocessedKeys": {} }))
+}
+
+/// The writes are all made, or none are if any is invalid.
+fn batch_write_item(tables: &mut Tables, request: &Fields) -> Result<Json, ApiError> {
+    let none = Expression::builder().build();
+    let mut staged = tables.clone();
+
+    for (name, writes) in request.required_object("RequestItems")? {
+        let table = staged
+            .get_mut(name)
+            .map(|entry| &mut entry.table)
+            .ok_or_else(|| ApiError::resource_not_found(name))?;
+        let writes = writes.as_array().ok_or_else(|| {
+            ApiError::serialization(format!("Expected a list of write requests for {name}"))
+        })?;
+
+        for write in writes {
+            let write = Fields::new(write)?;
+            let result = match (write.object("PutRequest")?, write.object("DeleteRequest")?) {
+                (Some(put), None) => table.put_item(Fields(put).required_item("Item")?, &none),
+                (None, Some(delete)) => {
+                    table.delete_item(Fields(delete).required_item("Key")?, &none)
+                }
+                _ => {
+                    return Err(ApiError::validation(
+                        "Supplied WriteRequest must contain exactly one of \
+                        PutRequest or DeleteRequest",
+                    ))
+                }
+            };
+
+            result.map_err(|err| ApiError::table(err, false))?;
+        }
+    }
+
+    *tables = staged;
+
+    Ok(json!({ "UnprocessedItems": {} }))
+}
+
+/// Every condition is checked against the items as they were before the
+/// transaction, and then the writes are all made, or none are.
+fn transact_write_items(tables: &mut Tables, request: &Fields) -> Result<Json, ApiError> {
+    let mut writes = Vec::new();
+    let mut keys = BTreeSet::new();
+    let mut reasons = Vec::new();
+    let mut cancelled = false;
+
+    for transact_item in request.required_array("TransactItems")? {
+        let transact_item = Fields::new(transact_item)?;
+        let mut actions = ["Put", "Update", "Delete", "ConditionCheck"]
+            .into_iter()
+            .filter_map(|action| {
+                transact_item
+                    .object(action)
+                    .map(|fields| fields.map(|fields| (action, Fields(fields))))
+                    .transpose()
+            });
+        let (Some(action), None) = (actions.next(), actions.next()) else {
+            return Err(ApiError::validation(
+                "TransactItems can only contain one of Check, Put, Update or Delete",
+            ));
+        };
+        let (action, fields) = action?;
+
+        let table = fields.table(tables)?;
+        let expression = fields.expression()?;
+        let item = match action {
+            "Put" => fields.required_item("Item")?,
+            _ => fields.required_item("Key")?,
+        };
+        let key = table
+            .transact_key(item, action == "Put")
+            .map_err(|err| ApiError::table(err, false))?;
+        let result = table.transact_check(&key, &expression);
+        if !keys.insert((fields.required_str("TableName")?, key)) {
+            return Err(ApiError::validation(
+                "Transaction request cannot include multiple operations on one item",
+            ));
+        }
+
+        reasons.push(match result {
+            Ok(()) => json!({ "Code": "None" }),
+            Err(TableError::ConditionalCheckFailed { item }) => {
+                cancelled = true;
+
+                let mut reason = json!({
+                    "Code": "ConditionalCheckFailed",
+                    "Message": "The conditional request failed",
+                });
+                if let Some(item) = item.filter(|_| fields.return_item_on_failure()) {
+                    reason["Item"] = item_to_json(&item);
+                }
+
+                reason
+            }
+            Err(err) => return Err(ApiError::table(err, false)),
+        });
+        writes.push((action, fields, expression));
+    }
+
+    if cancelled {
+        let codes = reasons
+            .iter()
+            .map(|reason| reason["Code"].as_str().unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        return Err(ApiError::new(
+            "TransactionCanceledException",
+            format!(
+                "Transaction cancelled, please refer cancellation reasons for specific reasons [{codes}]"
+            ),
+        )
+        .with_field("CancellationReasons", reasons.into()));
+    }
+
+    // Each write is to a different item, so the conditions hold as they did
+    // when they were checked.
+    let mut staged = tables.clone();
+    for (action, fields, expression) in writes {
+        let table = fields.table(&mut staged)?;
+        let result = match action {
+            "Put" => table
+                .put_item(fields.required_item("Item")?, &expression)
+                .map(drop),
+            "Update" => table
+                .update_item(fields.required_item("Key")?, &expression, ReturnValue::None)
+                .map(drop),
+            "Delete" => table
+                .delete_item(fields.required_item("Key")?, &expression)
+                .map(drop),
+            _ => table.condition_check(fields.required_item("Key")?, &expression),
+        };
+
+        result.map_err(|err| ApiError::table(err, false))?;
+    }
+
+    *tables = staged;
+
+    Ok(json!({}))
+}
+
+fn attributes(item: Option<Item>) -> Json {
+    match item {
+        Some(item) => json!({ "Attributes": item_to_json(&item) }),
+        None => json!({}),
+    }
+}
+
+fn page_json(request: &Fields, page: Page) -> Result<Json, ApiError> {
+    let mut response = json!({
+        "Count": page.count(),
+        "ScannedCount": page.scanned_count(),
+    });
+    if let Some(key) = page.last_evaluated_key() {
+        response["LastEvaluatedKey"] = item_to_json(key);
+    }
+
+    match request.str("Select")? {
+        Some("COUNT") => {}
+        None | Some("ALL_ATTRIBUTES" | "SPECIFIC_ATTRIBUTES" | "ALL_PROJECTED_ATTRIBUTES") => {
+            response["Items"] = page.items().iter().map(item_to_json).collect();
+        }
+        Some(select) => {
+            return Err(ApiError::validation(format!(
+                "Member must satisfy enum value set: \
+                [SPECIFIC_ATTRIBUTES, COUNT, ALL_ATTRIBUTES, ALL_PROJECTED_ATTRIBUTES], \
+                not {select}"
+            )))
+        }
+    }
+
+    Ok(response)
+}
+
+/// The fields of a JSON request object.
+struct Fields<'a>(&'a JsonMap<String, Json>);
+
+impl<'a> Fields<'a> {
+    fn new(value: &'a Json) -> Result<Self, ApiError> {
+        value
+            .as_object()
+            .map(Self)
+            .ok_or_else(|| ApiError::serialization(format!("Expected an object: {value}")))
+    }
+
+    fn wrong_type(field: &str) -> ApiError {
+        ApiError::serialization(format!("Unexpected value type for {field}"))
+    }
+
+    /// DynamoDB's error when a required field is missing.
+    fn missing(field: &str) -> ApiError {
+        let mut chars = field.chars();
+        let field = chars
+            .next()
+            .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+            .unwrap_or_default();
+
+        ApiError::validation(format!(
+            "1 validation error detected: Value null at '{field}' failed to \
+            satisfy constraint: Member must not be null"
+        ))
+    }
+
+    fn str(&self, field: &str) -> Result<Option<&'a str>, ApiError> {
+        self.0
+            .get(field)
+            .map(|value| value.as_str().ok_or_else(|| Self::wrong_type(field)))
+            .transpose()
+    }
+
+    fn required_str(&self, field: &str) -> Result<&'a str, ApiError> {
+        self.str(field)?.ok_or_else(|| Self::missing(field))
+    }
+
+    fn bool(&self, field: &str) -> Result<Option<bool>, ApiError> {
+        self.0
+            .get(field)
+            .map(|value| value.as_bool().ok_or_else(|| Self::wrong_type(field)))
+            .transpose()
+    }
+
+    fn limit(&self) -> Result<Option<usize>, ApiError> {
+        self.0
+            .get("Limit")
+            .map(|value| {
+                value
+                    .as_u64()
+                    .and_then(|limit| usize::try_from(limit).ok())
+                    .ok_or_else(|| Self::wrong_type("Limit"))
+            })
+            .transpose()
+    }
+
+    fn object(&self, field: &str) -> Result<Option<&'a JsonMap<String, Json>>, ApiError> {
+        self.0
+            .get(field)
+            .map(|value| value.as_object().ok_or_else(|| Self::wrong_type(field)))
+            .transpose()
+    }
+
+    fn required_object(&self, field: &str) -> Result<&'a JsonMap<String, Json>, ApiError> {
+        self.object(field)?.ok_or_else(|| Self::missing(field))
+    }
+
+    fn required_array(&self, field: &str) -> Result<&'a [Json], ApiError> {
+        self.0
+            .get(field)
+            .ok_or_else(|| Self::missing(field))?
+            .as_array()
+            .map(Vec::as_slice)
+            .ok_or_else(|| Self::wrong_type(field))
+    }
+
+    fn item(&self, field: &str) -> Result<Option<Item>, ApiError> {
+        self.0.get(field).map(item_from_json).transpose()
+    }
+
+    fn required_item(&self, field: &str) -> Result<Item, ApiError> {
+        self.item(field)?.ok_or_else(|| Self::missing(field))
+    }
+
+    /// The table named in the `TableName` field.
+    fn table<'t>(&self, tables: &'t mut Tables) -> Result<&'t mut Table, ApiError> {
+        let name = self.required_str("TableName")?;
+        tables
+            .get_mut(name)
+            .map(|entry| &mut entry.table)
+            .ok_or_else(|| ApiError::resource_not_found(name))
+    }
+
+    /// The expressions, and the expression attribute names and values.
+    fn expression(&self) -> Result<Expression, ApiError> {
+        let names = self
+            .object("ExpressionAttributeNames")?
+            .map(|names| {
+                names
+                    .iter()
+                    .map(|(placeholder, name)| {
+                        name.as_str()
+                            .map(|name| (placeholder.clone(), name.to_owned()))
+                            .ok_or_else(|| Self::wrong_type("ExpressionAttributeNames"))
+                    })
+                    .collect::<Result<_, _>>()
+            })
+            .transpose()?;
+
+        Ok(Expression {
+            condition_expression: self.str("ConditionExpression")?.map(Into::into),
+            key_condition_expression: self.str("KeyConditionExpression")?.map(Into::into),
+            update_expression: self.str("UpdateExpression")?.map(Into::into),
+            filter_expression: self.str("FilterExpression")?.map(Into::into),
+            projection_expression: self.str("ProjectionExpression")?.map(Into::into),
+            expression_attribute_names: names,
+            expression_attribute_values: self.item("ExpressionAttributeValues")?,
+        })
+    }
+
+    /// Whether `ReturnValues` asks for the old item from `PutItem` or
+    /// `DeleteItem`.
+    fn return_old(&self) -> Result<bool, ApiError> {
+        match self.str("ReturnValues")? {
+            None | Some("NONE") => Ok(false),
+            Some("ALL_OLD") => Ok(true),
+            Some(_) => Err(ApiError::validation("Return values set to invalid value")),
+        }
+    }
+
+    /// Whether `ReturnValuesOnConditionCheckFailure` asks for the item.
+    fn return_item_on_failure(&self) -> bool {
+        matches!(
+            self.str("ReturnValuesOnConditionCheckFailure"),
+            Ok(Some("ALL_OLD"))
+        )
+    }
+
+    fn table_error(&self, err: TableError) -> ApiError {
+        ApiError::table(err, self.return_item_on_failure())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::{json, Value as Json};
+
+    use super::{handle, Tables};
+
+    #[test]
+    fn operations() {
+        let mut tables = Tables::new();
+        let mut call = |operation: &str, body: Json| {
+            handle(&mut tables, operation, &body).map_err(|err| err.into_json())
+        };
+
+        let created = call(
+            "CreateTable",
+            json!({
+                "TableName": "t",
+                "KeySchema": [
+                    { "AttributeName": "pk", "KeyType": "HASH" },
+                    { "AttributeName": "sk", "KeyType": "RANGE" },
+                ],
+                "AttributeDefinitions": [
+                    { "AttributeName": "pk", "AttributeType": "S" },
+                    { "AttributeName": "sk", "AttributeType": "N" },
+                ],
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            json!([
+                { "AttributeName": "pk", "KeyType": "HASH" },
+                { "AttributeName": "sk", "KeyType": "RANGE" },
+            ]),
+            created["TableDescription"]["KeySchema"]
+        );
+
+        assert_eq!(
+            Ok(json!({ "UnprocessedItems": {} })),
+            call(
+                "BatchWriteItem",
+                json!({
+                    "RequestItems": {
+                        "t": [
+                            { "PutRequest": { "Item": { "pk": { "S": "a" }, "sk": { "N": "1" } } } },
+                            { "PutRequest": { "Item": { "pk": { "S": "a" }, "sk": { "N": "2" } } } },
+                        ],
+                    },
+                }),
+            )
+        );
+
+        assert_eq!(
+            Ok(json!({
+                "Attributes": { "n": { "N": "5" } },
+            })),
+            call(
+                "UpdateItem",
+                json!({
+                    "TableName": "t",
+                    "Key": { "pk": { "S": "a" }, "sk": { "N": "1" } },
+                    "UpdateExpression": "SET #n = :n",
+                    "ExpressionAttributeNames": { "#n": "n" },
+                    "ExpressionAttributeValues": { ":n": { "N": "5" } },
+                    "ReturnValues": "UPDATED_NEW",
+                }),
+            )
+        );
+
+        assert_eq!(
+            Ok(json!({
+                "Count": 1,
+                "ScannedCount": 2,
+                "Items": [{ "sk": { "N": "1" } }],
+            })),
+            call(
+                "Query",
+                json!({
+                    "TableName": "t",
+                    "KeyConditionExpression": "pk = :pk",
+                    "FilterExpression": "attribute_exists(n)",
+                    "ProjectionExpression": "sk",
+                    "ExpressionAttributeValues": { ":pk": { "S": "a" } },
+                }),
+            )
+        );
+
+        let err = call(
+            "TransactWriteItems",
+            json!({
+                "TransactItems": [
+                    {
+                        "Put": {
+                            "TableName": "t",
+                            "Item": { "pk": { "S": "b" }, "sk": { "N": "1" } },
+                        },
+                    },
+                    {
+                        "ConditionCheck": {
+                            "TableName": "t",
+                            "Key": { "pk": { "S": "a" }, "sk": { "N": "1" } },
+                            "ConditionExpression": "n > :n",
+                            "ExpressionAttributeValues": { ":n": { "N": "5" } },
+                            "ReturnValuesOnConditionCheckFailure": "ALL_OLD",
+                        },
+                    },
+                ],
+            }),
+        )
+        .unwrap_err();
+        assert_eq!(
+            json!({
+                "__type": "com.amazonaws.dynamodb.v20120810#TransactionCanceledException",
+                "message": "Transaction cancelled, please refer cancellation reasons \
+                    for specific reasons [None, ConditionalCheckFailed]",
+                "CancellationReasons": [
+                    { "Code": "None" },
+                    {
+                        "Code": "ConditionalCheckFailed",
+                        "Message": "The conditional request failed",
+                        "Item": { "pk": { "S": "a" }, "sk": { "N": "1" }, "n": { "N": "5" } },
+                    },
+                ],
+            }),
+            err
+        );
+
+        // Nothing was written.
+        assert_eq!(
+            Ok(json!({ "Count": 2, "ScannedCount": 2 })),
+            call("Scan", json!({ "TableName": "t", "Select": "COUNT" }))
+        );
+
+        assert_eq!(
+            Err(json!({
+                "__type": "com.amazonaws.dynamodb.v20120810#ValidationException",
+                "message": "Transaction request cannot include multiple operations on one item",
+            })),
+            call(
+                "TransactWriteItems",
+                json!({
+                    "TransactItems": [
+                        {
+                            "Put": {
+                                "TableName": "t",
+                                "Item": { "pk": { "S": "b" }, "sk": { "N": "1" } },
+                            },
+                        },
+                        {
+                            "Delete": {
+                                "TableName": "t",
+                                "Key": { "pk": { "S": "b" }, "sk": { "N": "1" } },
+                                "ConditionExpression": "attribute_exists(pk)",
+                            },
+                        },
+                    ],
+                }),
+            )
+        );
+        assert_eq!(
+            Ok(json!({ "Count": 2, "ScannedCount": 2 })),
+            call("Scan", json!({ "TableName": "t", "Select": "COUNT" }))
+        );
+
+        assert_eq!(
+            Ok(json!({
+                "Responses": { "t": [{ "pk": { "S": "a" }, "sk": { "N": "2" } }] },
+                "UnprocessedKeys": {},
+            })),
+            call(
+                "BatchGetItem",
+                json!({
+                    "RequestItems": {
+                        "t": {
+                            "Keys": [
+                                { "pk": { "S": "a" }, "sk": { "N": "2" } },
+                                { "pk": { "S": "z" }, "sk": { "N": "2" } },
+                            ],
+                        },
+                    },
+                }),
+            )
+        );
+
+        assert_eq!(
+            Err(json!({
+                "__type": "com.amazonaws.dynamodb.v20120810#ConditionalCheckFailedException",
+                "message": "The conditional request failed",
+            })),
+            call(
+                "DeleteItem",
+                json!({
+                    "TableName": "t",
+                    "Key": { "pk": { "S": "a" }, "sk": { "N": "2" } },
+                    "ConditionExpression": "attribute_exists(n)",
+                }),
+            )
+        );
+
+        assert_eq!(
+            Err(json!({
+                "__type": "com.amazonaws.dynamodb.v20120810#ResourceNotFoundException",
+                "message": "Requested resource not found: Table: missing not found",
+            })),
+            call(
+                "GetItem",
+                json!({ "TableName": "missing", "Key": { "pk": { "S": "a" } } }),
+            )
+        );
+
+        assert_eq!(
+            Err(json!({
+                "__type": "com.amazonaws.dynamodb.v20120810#ValidationException",
+                "message": "1 validation error detected: Value null at 'tableName' \
+                    failed to satisfy constraint: Member must not be null",
+            })),
+            call("PutItem", json!({ "Item": {} }))
+        );
+    }
+}
@@ -0,0 +1,411 @@
+//! Traversal of the expression types by value, with [`Fold`], to build new
+//! ones from them.
+//!
+//! Override the methods for the parts you're interested in. Each method
+//! defaults to calling the function of the same name in this module, which
+//! folds the children of that node. Call it from your own implementation to
+//! keep walking the tree.
+//!
+//! See also: [`visit`], [`visit_mut`]
+//!
+//! ```
+//! use dynamodb_expression::{
+//!     fold::Fold,
+//!     value::{Ref, Value, ValueOrRef},
+//!     Num, Path,
+//! };
+//! # use pretty_assertions::assert_eq;
+//!
+//! /// Swaps every value for a reference, keeping the values to supply later.
+//! #[derive(Default)]
+//! struct Params(Vec<(String, Value)>);
+//!
+//! impl Fold for Params {
+//!     fn fold_value(&mut self, value: ValueOrRef) -> ValueOrRef {
+//!         match value {
+//!             ValueOrRef::Value(value) => {
+//!                 let name = format!("p{}", self.0.len());
+//!                 self.0.push((name.clone(), value));
+//!
+//!                 Ref::new(name).into()
+//!             }
+//!             value_ref => value_ref,
+//!         }
+//!     }
+//! }
+//!
+//! let mut params = Params::default();
+//! let condition = params.fold_condition(
+//!     Path::new_name("a")
+//!         .greater_than(Num::new(1))
+//!         .and(Path::new_name("b").begins_with("x")),
+//! );
+//! assert_eq!("a > :p0 AND begins_with(b, :p1)", condition.to_string());
+//! assert_eq!(
+//!     vec![
+//!         (String::from("p0"), Num::new(1).into()),
+//!         (String::from("p1"), "x".into()),
+//!     ],
+//!     params.0
+//! );
+//! ```
+//!
+//! [`visit`]: crate::visit
+//! [`visit_mut`]: crate::visit_mut
+
+use crate::{
+    condition::{
+        And, AttributeExists, AttributeNotExists, AttributeType, BeginsWith, Between, Comparison,
+        Condition, Contains, In, Not, Or, Parenthetical,
+    },
+    key::KeyCondition,
+    operand::{Operand, OperandType, Size},
+    path::{Element, FieldName, Name, NameRef, Path},
+    update::{SetAction, Update},
+    value::ValueOrRef,
+};
+
+/// Folds the expression types by value. See the [module docs](self).
+pub trait Fold {
+    /// Folds a [`Condition`]. Defaults to [`fold_condition`].
+    fn fold_condition(&mut self, condition: Condition) -> Condition {
+        fold_condition(self, condition)
+    }
+
+    /// Folds a [`KeyCondition`]. Defaults to [`fold_key_condition`].
+    fn fold_key_condition(&mut self, key_condition: KeyCondition) -> KeyCondition {
+        fold_key_condition(self, key_condition)
+    }
+
+    /// Folds an [`Operand`]. Defaults to [`fold_operand`].
+    fn fold_operand(&mut self, operand: Operand) -> Operand {
+        fold_operand(self, operand)
+    }
+
+    /// Folds an [`Update`]. Defaults to [`fold_update`].
+    fn fold_update(&mut self, update: Update) -> Update {
+        fold_update(self, update)
+    }
+
+    /// Folds a [`SetAction`]. Defaults to [`fold_set_action`].
+    fn fold_set_action(&mut self, action: SetAction) -> SetAction {
+        fold_set_action(self, action)
+    }
+
+    /// Folds a [`Path`]. Defaults to [`fold_path`].
+    fn fold_path(&mut self, path: Path) -> Path {
+        fold_path(self, path)
+    }
+
+    /// Folds an attribute name in a [`Path`]. Returns it unchanged by
+    /// default.
+    fn fold_name(&mut self, name: Name) -> Name {
+        name
+    }
+
+    /// Folds a reference to an expression attribute name in a [`Path`].
+    /// Returns it unchanged by default.
+    fn fold_name_ref(&mut self, name_ref: NameRef) -> NameRef {
+        name_ref
+    }
+
+    /// Folds a value, or a reference to an expression attribute value.
+    /// Returns it unchanged by default.
+    fn fold_value(&mut self, value: ValueOrRef) -> ValueOrRef {
+        value
+    }
+}
+
+/// Folds the children of a [`Condition`].
+pub fn fold_condition<F>(folder: &mut F, condition: Condition) -> Condition
+where
+    F: Fold + ?Sized,
+{
+    match condition {
+        Condition::AttributeExists(AttributeExists { path }) => AttributeExists {
+            path: folder.fold_path(path),
+        }
+        .into(),
+        Condition::AttributeNotExists(AttributeNotExists { path }) => AttributeNotExists {
+            path: folder.fold_path(path),
+        }
+        .into(),
+        Condition::AttributeType(AttributeType {
+            path,
+            attribute_type,
+        }) => AttributeType {
+            path: folder.fold_path(path),
+            attribute_type,
+        }
+        .into(),
+        Condition::Contains(Contains { path, operand }) => Contains {
+            path: folder.fold_path(path),
+            operand: folder.fold_value(operand),
+        }
+        .into(),
+        Condition::BeginsWith(BeginsWith { path, substr }) => BeginsWith {
+            path: folder.fold_path(path),
+            substr: folder.fold_value(substr),
+        }
+        .into(),
+        Condition::Between(Between { op, lower, upper }) => Between {
+            op: folder.fold_operand(op),
+            lower: folder.fold_operand(lower),
+            upper: folder.fold_operand(upper),
+        }
+        .into(),
+        Condition::In(In { op, items }) => In {
+            op: folder.fold_operand(op),
+            items: items
+                .into_iter()
+                .map(|item| folder.fold_operand(item))
+                .collect(),
+        }
+        .into(),
+        Condition::Comparison(Comparison { left, cmp, right }) => Comparison {
+            left: folder.fold_operand(left),
+            cmp,
+            right: folder.fold_operand(right),
+        }
+        .into(),
+        Condition::And(And { left, right }) => And {
+            left: folder.fold_condition(*left).into(),
+            right: folder.fold_condition(*right).into(),
+        }
+        .into(),
+        Condition::Or(Or { left, right }) => Or {
+            left: folder.fold_condition(*left).into(),
+            right: folder.fold_condition(*right).into(),
+        }
+        .into(),
+        Condition::Not(Not { condition }) => Not {
+            condition: folder.fold_condition(*condition).into(),
+        }
+        .into(),
+        Condition::Parenthetical(Parenthetical { condition }) => Parenthetical {
+            condition: folder.fold_condition(*condition).into(),
+        }
+        .into(),
+    }
+}
+
+/// Folds the children of a [`KeyCondition`].
+pub fn fold_key_condition<F>(folder: &mut F, key_condition: KeyCondition) -> KeyCondition
+where
+    F: Fold + ?Sized,
+{
+    KeyCondition {
+        condition: folder.fold_condition(key_condition.condition),
+    }
+}
+
+/// Folds the children of an [`Operand`].
+pub fn fold_operand<F>(folder: &mut F, operand: Operand) -> Operand
+where
+    F: Fold + ?Sized,
+{
+    match operand.op {
+        OperandType::Path(path) => folder.fold_path(path).into(),
+        OperandType::Size(Size { path }) => Size {
+            path: folder.fold_path(path),
+        }
+        .into(),
+        OperandType::Scalar(value) => Operand {
+            op: OperandType::Scalar(folder.fold_value(value)),
+        },
+        OperandType::Condition(condition) => folder.fold_condition(*condition).into(),
+    }
+}
+
+/// Folds the children of an [`Update`].
+pub fn fold_update<F>(folder: &mut F, update: Update) -> Update
+where
+    F: Fold + ?Sized,
+{
+    let Update {
+        set,
+        remove,
+        add,
+        delete,
+    } = update;
+
+    Update {
+        set: set.map(|mut set| {
+            set.actions = set
+                .actions
+                .into_iter()
+                .map(|action| folder.fold_set_action(action))
+                .collect();
+
+            set
+        }),
+        remove: remove.map(|mut remove| {
+            remove.paths = remove
+                .paths
+                .into_iter()
+                .map(|path| folder.fold_path(path))
+                .collect();
+
+            remove
+        }),
+        add: add
+            .into_iter()
+            .map(|mut add| {
+                add.path = folder.fold_path(add.path);
+                add.value = folder.fold_value(add.value);
+
+                add
+            })
+            .collect(),
+        delete: delete
+            .into_iter()
+            .map(|mut delete| {
+                delete.path = folder.fold_path(delete.path);
+                delete.subset = folder.fold_value(delete.subset);
+
+                delete
+            })
+            .collect(),
+    }
+}
+
+/// Folds the children of a [`SetAction`].
+pub fn fold_set_action<F>(folder: &mut F, action: SetAction) -> SetAction
+where
+    F: Fold + ?Sized,
+{
+    match action {
+        SetAction::Assign(mut action) => {
+            action.path = folder.fold_path(action.path);
+            action.value = folder.fold_value(action.value);
+
+            action.into()
+        }
+        SetAction::Math(mut action) => {
+            action.dst = folder.fold_path(action.dst);
+            action.src = action.src.map(|src| folder.fold_path(src));
+            action.num = folder.fold_value(action.num);
+
+            action.into()
+        }
+        SetAction::ListAppend(mut action) => {
+            action.dst = folder.fold_path(action.dst);
+            action.src = action.src.map(|src| folder.fold_path(src));
+            action.list = folder.fold_value(action.list);
+
+            action.into()
+        }
+        SetAction::IfNotExists(mut action) => {
+            action.dst = folder.fold_path(action.dst);
+            action.src = action.src.map(|src| folder.fold_path(src));
+            action.value = folder.fold_value(action.value);
+
+            action.into()
+        }
+    }
+}
+
+/// Folds the parts of a [`Path`].
+pub fn fold_path<F>(folder: &mut F, mut path: Path) -> Path
+where
+    F: Fold + ?Sized,
+{
+    path.elements = path
+        .elements
+        .into_iter()
+        .map(|elem| match elem {
+            Element::Name(name) => folder.fold_name(name).into(),
+            Element::IndexedField(mut new_indexed_field) => {
+                new_indexed_field.name = match new_indexed_field.name {
+                    FieldName::Name(name) => folder.fold_name(name).into(),
+                    FieldName::NameRef(name_ref) => folder.fold_name_ref(name_ref).into(),
+                };
+
+                new_indexed_field.into()
+            }
+            Element::NameRef(name_ref) => folder.fold_name_ref(name_ref).into(),
+        })
+        .collect();
+
+    path
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        path::{Name, NameRef},
+        update::Update,
+        value::{Num, ValueOrRef},
+        Path,
+    };
+
+    use super::Fold;
+
+    /// Upper-cases names and doubles numbers.
+    struct Shout;
+
+    impl Fold for Shout {
+        fn fold_name(&mut self, name: Name) -> Name {
+            Name::from(name.to_string().to_uppercase())
+        }
+
+        fn fold_name_ref(&mut self, name_ref: NameRef) -> NameRef {
+            NameRef::new(name_ref.as_str().to_uppercase())
+        }
+
+        fn fold_value(&mut self, value: ValueOrRef) -> ValueOrRef {
+            match value {
+                ValueOrRef::Value(value) => match value.to_string().parse::<i64>() {
+                    Ok(n) => Num::new(n * 2).into(),
+                    Err(_) => value.into(),
+                },
+                value => value,
+            }
+        }
+    }
+
+    #[test]
+    fn fold() {
+        assert_eq!(
+            "A[1].B BETWEEN 2 AND size(C) OR NOT (D <> 4)",
+            Shout
+                .fold_condition(
+                    "a[1].b"
+                        .parse::<Path>()
+                        .unwrap()
+                        .between(Num::new(1), Path::new_name("c").size())
+                        .or(Path::new_name("d")
+                            .not_equal(Num::new(2))
+                            .parenthesize()
+                            .not())
+                )
+                .to_string()
+        );
+
+        assert_eq!(
+            "SET A = A + 6, B = if_not_exists(B, \"x\")",
+            Shout
+                .fold_update(Update::from(
+                    Path::new_name("a")
+                        .math()
+                        .add(3)
+                        .and(Path::new_name("b").if_not_exists().assign("x"))
+                ))
+                .to_string()
+        );
+
+        assert_eq!(
+            "K = 10 AND #J = 12",
+            Shout
+                .fold_key_condition(
+                    Path::new_name("k")
+                        .key()
+                        .equal(Num::new(5))
+                        .and(Path::from(NameRef::new("j")).key().equal(Num::new(6)))
+                )
+                .to_string()
+        );
+    }
+}
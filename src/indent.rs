@@ -0,0 +1,66 @@
+//! Indenting nested, multi-line output for the alternate (`{:#}`) forms of
+//! [`Display`].
+//!
+//! [`Display`]: core::fmt::Display
+
+use core::fmt::{self, Write};
+
+/// The indentation for each level of nesting.
+pub(crate) const INDENT: &str = "  ";
+
+/// Writes to the inner writer, indenting every line after the first by
+/// [`INDENT`].
+pub(crate) struct Indented<'a, W>
+where
+    W: ?Sized,
+{
+    inner: &'a mut W,
+}
+
+impl<'a, W> Indented<'a, W>
+where
+    W: Write + ?Sized,
+{
+    pub(crate) fn new(inner: &'a mut W) -> Self {
+        Self { inner }
+    }
+}
+
+impl<W> Write for Indented<'_, W>
+where
+    W: Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut lines = s.split('\n');
+        if let Some(first) = lines.next() {
+            self.inner.write_str(first)?;
+        }
+
+        lines.try_for_each(|line| {
+            self.inner.write_char('\n')?;
+            self.inner.write_str(INDENT)?;
+            self.inner.write_str(line)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::fmt::Write;
+
+    use pretty_assertions::assert_eq;
+
+    use super::Indented;
+
+    #[test]
+    fn indented() {
+        let mut out = String::new();
+        Indented::new(&mut out).write_str("a (\n").unwrap();
+        Indented::new(&mut Indented::new(&mut out))
+            .write_str("b\nc")
+            .unwrap();
+        Indented::new(&mut out).write_str("\n)").unwrap();
+
+        assert_eq!("a (\n  b\n    c\n  )", out);
+    }
+}
@@ -26,6 +26,7 @@ use crate::{
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Query.KeyConditionExpressions.html
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Key {
     // TODO: Is `Path` the right thing, here?
     //       Probably not. Looks like it should be `Name`
@@ -166,6 +167,8 @@ where
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Query.KeyConditionExpressions.html
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct KeyCondition {
     pub(crate) condition: Condition,
 }
@@ -176,6 +179,11 @@ impl KeyCondition {
             condition: self.condition.and(right.condition),
         }
     }
+
+    /// The condition this key condition wraps.
+    pub fn condition(&self) -> &Condition {
+        &self.condition
+    }
 }
 
 impl fmt::Display for KeyCondition {
@@ -40,6 +40,55 @@ let query_output = Expression::builder()
 
 From here, see [`Expression`] and [`Path`] for more docs and examples.
 
+# Optional features
+
+* `serde`: Implements `serde::Serialize` and `serde::Deserialize` for the
+  types used to build expressions, so conditions, updates, etc., can be stored
+  and loaded again. See the `versioned` module. Also adds `value::to_value`
+  and `value::from_value` to convert your own types to and from a [`Value`].
+* `derive`: Adds `#[derive(DynamoPaths)]`, which generates typed paths for the
+  fields of a struct. See the [`path::typed`] module.
+* `macros`: Adds the `condition!` and `update!` macros, which build a
+  [`Condition`][condition::Condition] or an [`Update`][update::Update] from DynamoDB-like syntax, checked at compile
+  time.
+* `fake`: Adds an in-memory DynamoDB table that takes [`Expression`]s, for
+  unit tests that would otherwise need DynamoDB Local. See the `fake` module.
+* `server`: Adds `fake::server`, a local HTTP server that speaks the DynamoDB
+  protocol, so the AWS SDK's client can be tested against the `fake` tables.
+* `testing`: Adds the `testing` module, for comparing DynamoDB items in tests
+  while ignoring the order of sets, with the `assert_item_eq!` and
+  `assert_items_eq!` macros, and for building items from a [`Map`].
+* `dynamodbstreams`: Converts records from the `aws-sdk-dynamodbstreams` crate
+  into a `stream::StreamRecord`, to evaluate conditions against their images.
+
+*/
+#![cfg_attr(feature = "macros", doc = "```")]
+#![cfg_attr(not(feature = "macros"), doc = "```ignore")]
+/*!
+use dynamodb_expression::{condition, update, Num, Path};
+# use pretty_assertions::assert_eq;
+
+let min_age = Num::new(21);
+let prefix = "J";
+
+let condition = condition!(
+    age >= {min_age} AND begins_with(name, {prefix}) AND attribute_exists(email)
+);
+assert_eq!(
+    Path::new_name("age")
+        .greater_than_or_equal(Num::new(21))
+        .and(Path::new_name("name").begins_with("J"))
+        .and(Path::new_name("email").attribute_exists()),
+    condition
+);
+
+let update = update!(SET visits = visits + 1, last_seen = {Num::new(1700000000)});
+assert_eq!(
+    "SET visits = visits + 1, last_seen = 1700000000",
+    update.to_string()
+);
+```
+
 # What about Rusoto?
 
 [Rusoto][5] is intentionally not supported.
@@ -127,16 +176,33 @@ fn convert_av(av: AwsAv) -> RusotoAv {
 // Re-export the crates publicly exposed in our API
 pub use ::aws_sdk_dynamodb;
 pub use ::num;
+#[cfg(feature = "serde")]
+pub use ::serde;
 pub use ::serde_json;
 
 pub mod condition;
 pub mod expression;
+#[cfg(feature = "fake")]
+pub mod fake;
+pub mod fold;
+mod indent;
 pub mod key;
 pub mod operand;
 pub mod path;
+pub mod stream;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod update;
 pub mod value;
-
+#[cfg(feature = "serde")]
+pub mod versioned;
+pub mod visit;
+pub mod visit_mut;
+
+#[cfg(feature = "derive")]
+pub use dynamodb_expression_derive::DynamoPaths;
+#[cfg(feature = "macros")]
+pub use dynamodb_expression_derive::{condition, update};
 pub use expression::Expression;
 pub use path::Path;
 pub use value::{Map, Num, Scalar, Set, Value};
@@ -1,7 +1,7 @@
 mod operand_type;
 mod size;
 
-pub(crate) use self::operand_type::OperandType;
+pub use self::operand_type::OperandType;
 pub use self::size::Size;
 
 use core::fmt;
@@ -31,11 +31,34 @@ use crate::condition::{
 /// [`Scalar`]: crate::value::Scalar
 /// [`Ref`]: crate::value::Ref
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Operand {
     pub(crate) op: OperandType,
 }
 
 impl Operand {
+    /// The kind of operand this is, for inspecting it.
+    ///
+    /// ```
+    /// use dynamodb_expression::{operand::{Operand, OperandType}, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let operand = Operand::from(Path::new_name("foo").size());
+    /// let OperandType::Size(size) = operand.operand_type() else {
+    ///     panic!("expected a size: {operand}");
+    /// };
+    /// assert_eq!(&Path::new_name("foo"), size.path());
+    /// ```
+    pub fn operand_type(&self) -> &OperandType {
+        &self.op
+    }
+
+    /// Consumes the operand, returning the kind of operand it was.
+    pub fn into_operand_type(self) -> OperandType {
+        self.op
+    }
+
     /// Check if the value of this operand is equal to the given value.
     ///
     /// [DynamoDB documentation.](https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.Comparators)
@@ -3,15 +3,28 @@ use core::fmt;
 use crate::{
     condition::Condition,
     operand::Size,
-    path::{Element, IndexedField, Name, Path},
+    path::{Element, IndexedField, Name, NameRef, Path},
     value::{Num, Ref, Scalar, ValueOrRef},
 };
 
+/// The kinds of [`Operand`] there are.
+///
+/// See also: [`Operand::operand_type`], [`Operand::into_operand_type`]
+///
+/// [`Operand`]: crate::operand::Operand
+/// [`Operand::operand_type`]: crate::operand::Operand::operand_type
+/// [`Operand::into_operand_type`]: crate::operand::Operand::into_operand_type
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) enum OperandType {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum OperandType {
+    /// The value at a [`Path`].
     Path(Path),
+    /// A value, or a reference to one.
     Scalar(ValueOrRef),
+    /// The result of a [`Condition`].
     Condition(Box<Condition>),
+    /// The [`Size`] of the value at a path.
     Size(Size),
 }
 
@@ -44,6 +57,12 @@ impl From<Name> for OperandType {
     }
 }
 
+impl From<NameRef> for OperandType {
+    fn from(name_ref: NameRef) -> Self {
+        Self::Path(name_ref.into())
+    }
+}
+
 impl From<IndexedField> for OperandType {
     fn from(field: IndexedField) -> Self {
         Self::Path(field.into())
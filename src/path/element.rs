@@ -6,7 +6,7 @@ use core::{
     str::FromStr,
 };
 
-use super::{Name, PathParseError};
+use super::{Name, NameRef, PathParseError};
 
 /// Represents a single element of a DynamoDB document [`Path`]. For example,
 /// in `foo[3][7].bar[2].baz`, the `Element`s would be `foo[3][7]`, `bar[2]`,
@@ -16,9 +16,15 @@ use super::{Name, PathParseError};
 ///
 /// [`Path`]: crate::path::Path
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Element {
     Name(Name),
     IndexedField(IndexedField),
+    /// A reference to an attribute name in the expression attribute names.
+    ///
+    /// See also: [`NameRef`]
+    NameRef(NameRef),
 }
 
 impl Element {
@@ -37,6 +43,7 @@ impl Element {
     /// An indexed field element of a document path. For example, `foo[3]` or
     /// `foo[7][4]`
     ///
+    /// `name` here can be a [`Name`], or a [`NameRef`] for `#foo[3]`.
     /// `indexes` here can be an array, slice, `Vec` of, or single `usize`.
     /// ```
     /// # use dynamodb_expression::path::Element;
@@ -54,20 +61,23 @@ impl Element {
     /// assert_eq!("foo", Element::new_indexed_field("foo", []).to_string());
     /// assert_eq!("foo", Element::new_indexed_field("foo", &[]).to_string());
     /// assert_eq!("foo", Element::new_indexed_field("foo", vec![]).to_string());
+    ///
+    /// # use dynamodb_expression::path::NameRef;
+    /// assert_eq!("#foo[3]", Element::new_indexed_field(NameRef::new("foo"), 3).to_string());
     /// ```
     ///
-    /// See also: [`IndexedField`], [`Path`], [`Path::new_indexed_field`]
+    /// See also: [`IndexedField`], [`FieldName`], [`Path`], [`Path::new_indexed_field`]
     ///
     /// [`Path`]: crate::path::Path
     /// [`Path::new_indexed_field`]: crate::path::Path::new_indexed_field
     pub fn new_indexed_field<N, I>(name: N, indexes: I) -> Self
     where
-        N: Into<Name>,
+        N: Into<FieldName>,
         I: Indexes,
     {
         let indexes = indexes.into_indexes();
         if indexes.is_empty() {
-            Self::new_name(name)
+            name.into().into()
         } else {
             Self::IndexedField(IndexedField {
                 name: name.into(),
@@ -82,6 +92,7 @@ impl fmt::Display for Element {
         match self {
             Element::Name(name) => name.fmt(f),
             Element::IndexedField(field_index) => field_index.fmt(f),
+            Element::NameRef(name_ref) => name_ref.fmt(f),
         }
     }
 }
@@ -91,6 +102,7 @@ impl From<Element> for String {
         match element {
             Element::Name(name) => name.into(),
             Element::IndexedField(new_indexed_field) => new_indexed_field.to_string(),
+            Element::NameRef(name_ref) => name_ref.into(),
         }
     }
 }
@@ -98,7 +110,7 @@ impl From<Element> for String {
 impl From<IndexedField> for Element {
     fn from(value: IndexedField) -> Self {
         if value.indexes.is_empty() {
-            Self::Name(value.name)
+            value.name.into()
         } else {
             Self::IndexedField(value)
         }
@@ -107,7 +119,7 @@ impl From<IndexedField> for Element {
 
 impl<N, I> From<(N, I)> for Element
 where
-    N: Into<Name>,
+    N: Into<FieldName>,
     I: Indexes,
 {
     fn from((name, indexes): (N, I)) -> Self {
@@ -121,6 +133,21 @@ impl From<Name> for Element {
     }
 }
 
+impl From<NameRef> for Element {
+    fn from(name_ref: NameRef) -> Self {
+        Self::NameRef(name_ref)
+    }
+}
+
+impl From<FieldName> for Element {
+    fn from(name: FieldName) -> Self {
+        match name {
+            FieldName::Name(name) => Self::Name(name),
+            FieldName::NameRef(name_ref) => Self::NameRef(name_ref),
+        }
+    }
+}
+
 // Intentionally not implementing `From` string-types for `Element` to force
 // users to intentionally use a `Name` if that's what they want. Should help
 // avoid surprises when they have an indexed field, or sub-attribute.
@@ -181,7 +208,7 @@ impl FromStr for Element {
         }
 
         Ok(if indexes.is_empty() {
-            Self::Name(input.into())
+            FieldName::from_str(input)?.into()
         } else {
             if !remaining.is_empty() {
                 // Shouldn't be able to get there.
@@ -194,7 +221,7 @@ impl FromStr for Element {
             indexes.shrink_to_fit();
 
             Self::IndexedField(IndexedField {
-                name: name.into(),
+                name: name.parse()?,
                 indexes,
             })
         })
@@ -202,9 +229,9 @@ impl FromStr for Element {
 }
 
 /// Represents a type of [`Element`] of a DynamoDB document [`Path`] that is a
-/// [`Name`] with one or more indexes. For example, in `foo[3][7].bar[2].baz`,
-/// the elements `foo[3][7]` and `bar[2]` would both be represented as an
-/// `IndexedField`.
+/// [`Name`] or [`NameRef`] with one or more indexes. For example, in
+/// `foo[3][7].#bar[2].baz`, the elements `foo[3][7]` and `#bar[2]` would both be
+/// represented as an `IndexedField`.
 ///
 /// Created via `Element::from`, [`Element::new_indexed_field`], and
 /// [`Path::new_indexed_field`].
@@ -212,9 +239,22 @@ impl FromStr for Element {
 /// [`Path::new_indexed_field`]: crate::path::Path::new_indexed_field
 /// [`Path`]: crate::path::Path
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IndexedField {
-    pub(crate) name: Name,
-    indexes: Vec<usize>,
+    pub(crate) name: FieldName,
+    pub(crate) indexes: Vec<usize>,
+}
+
+impl IndexedField {
+    /// The name of the field.
+    pub fn name(&self) -> &FieldName {
+        &self.name
+    }
+
+    /// The indexes into the field, in order.
+    pub fn indexes(&self) -> &[usize] {
+        &self.indexes
+    }
 }
 
 impl fmt::Display for IndexedField {
@@ -228,6 +268,89 @@ impl fmt::Display for IndexedField {
     }
 }
 
+/// The name of an [`IndexedField`]. Either an attribute [`Name`], or a
+/// [`NameRef`] to one in the expression attribute names.
+///
+/// ```
+/// use dynamodb_expression::path::{Element, FieldName, Name, NameRef};
+/// # use pretty_assertions::assert_eq;
+///
+/// let Ok(Element::IndexedField(field)) = "#foo[3]".parse() else {
+///     unreachable!()
+/// };
+/// assert_eq!(&FieldName::from(NameRef::new("foo")), field.name());
+///
+/// let Ok(Element::IndexedField(field)) = "foo[3]".parse() else {
+///     unreachable!()
+/// };
+/// assert_eq!(&FieldName::from(Name::new("foo")), field.name());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FieldName {
+    Name(Name),
+    NameRef(NameRef),
+}
+
+impl fmt::Display for FieldName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldName::Name(name) => name.fmt(f),
+            FieldName::NameRef(name_ref) => name_ref.fmt(f),
+        }
+    }
+}
+
+impl From<Name> for FieldName {
+    fn from(name: Name) -> Self {
+        Self::Name(name)
+    }
+}
+
+impl From<NameRef> for FieldName {
+    fn from(name_ref: NameRef) -> Self {
+        Self::NameRef(name_ref)
+    }
+}
+
+impl From<String> for FieldName {
+    fn from(name: String) -> Self {
+        Self::Name(name.into())
+    }
+}
+
+impl From<&String> for FieldName {
+    fn from(name: &String) -> Self {
+        Self::Name(name.into())
+    }
+}
+
+impl From<&str> for FieldName {
+    fn from(name: &str) -> Self {
+        Self::Name(name.into())
+    }
+}
+
+impl From<&&str> for FieldName {
+    fn from(name: &&str) -> Self {
+        Self::Name(name.into())
+    }
+}
+
+/// A leading `#` makes it a [`NameRef`].
+impl FromStr for FieldName {
+    type Err = PathParseError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Ok(match name.strip_prefix('#') {
+            Some("") => return Err(PathParseError),
+            Some(name_ref) => Self::NameRef(name_ref.into()),
+            None => Self::Name(name.into()),
+        })
+    }
+}
+
 /// Used for [`IndexedField`]. An array, slice, `Vec` of, or single `usize`.
 ///
 /// See also: [`Element::new_indexed_field`], [`Path::new_indexed_field`]
@@ -271,9 +394,9 @@ impl<const N: usize> Indexes for &[usize; N] {
 mod test {
     use pretty_assertions::assert_eq;
 
-    use crate::{Num, Path};
+    use crate::{path::PathParseError, Num, Path};
 
-    use super::{Element, Name};
+    use super::{Element, FieldName, IndexedField, Name, NameRef};
 
     #[test]
     fn display_name() {
@@ -319,6 +442,42 @@ mod test {
         assert_eq!("foo[42].bar", path.to_string());
     }
 
+    #[test]
+    fn name_refs() {
+        let field = Element::new_indexed_field(NameRef::new("foo"), [3, 7]);
+        assert_eq!("#foo[3][7]", field.to_string());
+        assert_eq!(
+            Element::IndexedField(IndexedField {
+                name: FieldName::NameRef(NameRef::new("foo")),
+                indexes: vec![3, 7],
+            }),
+            field
+        );
+        assert_eq!(Ok(field), "#foo[3][7]".parse());
+
+        // Without indexes, it's just the `NameRef`.
+        let element = Element::new_indexed_field(NameRef::new("foo"), []);
+        assert_eq!(Element::NameRef(NameRef::new("foo")), element);
+        assert_eq!(Ok(element), "#foo".parse());
+
+        assert_eq!(Err(PathParseError), "#".parse::<Element>());
+        assert_eq!(Err(PathParseError), "#[0]".parse::<Element>());
+
+        for input in ["#ref[0]", "#ref[0].x", "a.#ref[2][1].#b", "#a.b[0]"] {
+            let path: Path = input.parse().unwrap();
+            assert_eq!(input, path.to_string());
+        }
+
+        let path: Path = "#ref[0].x".parse().unwrap();
+        assert_eq!(
+            Path::from_iter([
+                Element::new_indexed_field(NameRef::new("ref"), 0),
+                Element::new_name("x"),
+            ]),
+            path
+        );
+    }
+
     #[test]
     fn size() {
         assert_eq!(
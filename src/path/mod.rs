@@ -1,9 +1,12 @@
 mod element;
 mod name;
+mod name_ref;
+pub mod typed;
 
 pub use self::{
-    element::{Element, IndexedField, Indexes},
+    element::{Element, FieldName, IndexedField, Indexes},
     name::Name,
+    name_ref::NameRef,
 };
 
 use core::{
@@ -82,6 +85,22 @@ use crate::{
 /// );
 /// ```
 ///
+/// An element that starts with `#` is parsed as a [`NameRef`], which may also
+/// be indexed.
+/// ```
+/// use dynamodb_expression::{path::{Element, NameRef}, Path};
+/// # use pretty_assertions::assert_eq;
+///
+/// let path: Path = "#foo[3].bar".parse().unwrap();
+/// assert_eq!(
+///     Path::from_iter([
+///         Element::new_indexed_field(NameRef::new("foo"), 3),
+///         Element::new_name("bar"),
+///     ]),
+///     path,
+/// );
+/// ```
+///
 /// This makes the common assumption that each path element is separated by a
 /// period (`.`). For example, the path `foo.bar` gets treated as if `foo` is a
 /// top-level attribute, and `bar` is a sub-attribute of `foo`. However, `.` [is
@@ -209,6 +228,8 @@ use crate::{
 /// [parse]: str::parse
 /// [`Expression`]: crate::expression::Expression
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Path {
     pub(crate) elements: Vec<Element>,
 }
@@ -281,7 +302,7 @@ impl Path {
     /// See also: [`IndexedField`], [`Element::new_indexed_field`]
     pub fn new_indexed_field<N, I>(name: N, indexes: I) -> Self
     where
-        N: Into<Name>,
+        N: Into<FieldName>,
         I: Indexes,
     {
         Self {
@@ -303,6 +324,27 @@ impl Path {
     pub fn append(&mut self, mut other: Path) {
         self.elements.append(&mut other.elements);
     }
+
+    /// The [`Element`]s that make up this [`Path`].
+    ///
+    /// ```
+    /// use dynamodb_expression::path::{Element, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let path: Path = "foo[2].bar".parse().unwrap();
+    /// assert_eq!(
+    ///     &[Element::new_indexed_field("foo", 2), Element::new_name("bar")],
+    ///     path.elements()
+    /// );
+    /// ```
+    pub fn elements(&self) -> &[Element] {
+        &self.elements
+    }
+
+    /// An iterator over the [`Element`]s that make up this [`Path`].
+    pub fn iter(&self) -> core::slice::Iter<'_, Element> {
+        self.elements.iter()
+    }
 }
 
 /// Methods relating to building condition and filter expressions.
@@ -838,6 +880,15 @@ impl FromStr for Path {
     }
 }
 
+impl<'a> IntoIterator for &'a Path {
+    type Item = &'a Element;
+    type IntoIter = core::slice::Iter<'a, Element>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl From<Path> for Vec<Element> {
     fn from(path: Path) -> Self {
         path.elements
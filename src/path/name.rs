@@ -28,6 +28,8 @@ use core::fmt;
 /// [`Expression`]: crate::expression::Expression
 /// [`Path`]: crate::path::Path
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Name {
     pub(crate) name: String,
 }
@@ -39,6 +41,11 @@ impl Name {
     {
         Self { name: name.into() }
     }
+
+    /// The name of the attribute.
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
 }
 
 impl fmt::Display for Name {
@@ -0,0 +1,84 @@
+use core::fmt::{self, Write};
+
+/// A reference to a DynamoDB attribute name stored in expression attribute
+/// names. Automatically prefixed with `#`.
+///
+/// This is the name counterpart to [`Ref`]. It can be used anywhere an
+/// [`Element`] of a [`Path`] is accepted, and is left as-is by the expression
+/// [`Builder`]. Use [`Builder::with_name`] to supply the attribute name it
+/// refers to, or set it in the [`Expression`]'s
+/// `expression_attribute_names` yourself.
+///
+/// ```
+/// use dynamodb_expression::{path::NameRef, Num, Path};
+/// # use pretty_assertions::assert_eq;
+///
+/// let name_ref = NameRef::new("pk");
+/// assert_eq!("#pk", name_ref.to_string());
+///
+/// let condition = Path::from(name_ref).equal(Num::new(42));
+/// assert_eq!("#pk = 42", condition.to_string());
+/// ```
+///
+/// [`Ref`]: crate::value::Ref
+/// [`Element`]: crate::path::Element
+/// [`Path`]: crate::path::Path
+/// [`Builder`]: crate::expression::Builder
+/// [`Builder::with_name`]: crate::expression::Builder::with_name
+/// [`Expression`]: crate::expression::Expression
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NameRef(String);
+
+impl NameRef {
+    pub fn new<T>(name_ref: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self(name_ref.into())
+    }
+
+    /// The name of the expression attribute name, without the leading `#`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for NameRef {
+    fn from(name_ref: String) -> Self {
+        Self(name_ref)
+    }
+}
+
+impl From<&String> for NameRef {
+    fn from(name_ref: &String) -> Self {
+        Self(name_ref.to_owned())
+    }
+}
+
+impl From<&str> for NameRef {
+    fn from(name_ref: &str) -> Self {
+        Self(name_ref.to_owned())
+    }
+}
+
+impl From<&&str> for NameRef {
+    fn from(name_ref: &&str) -> Self {
+        Self((*name_ref).to_owned())
+    }
+}
+
+impl fmt::Display for NameRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('#')?;
+        self.0.fmt(f)
+    }
+}
+
+impl From<NameRef> for String {
+    fn from(mut name_ref: NameRef) -> Self {
+        name_ref.0.insert(0, '#');
+
+        name_ref.0
+    }
+}
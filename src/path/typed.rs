@@ -0,0 +1,564 @@
+//! Typed paths, which only offer the operations that make sense for the type
+//! of the attribute they point to.
+//!
+//! These are usually produced by `#[derive(DynamoPaths)]` (with the `derive`
+//! feature), which generates an accessor for each field of a struct. Nested
+//! structs that also derive `DynamoPaths`, `Vec` indexing, and
+//! `#[serde(rename = "...")]`/`#[serde(rename_all = "...")]` are supported.
+//! Fields serialized with [`value::string_set`], [`value::num_set`], or
+//! [`value::binary_set`] are treated as DynamoDB sets.
+//!
+//! The operations common to every type of attribute (such as
+//! [`TypedPath::attribute_exists`] and [`TypedPath::assign`]) come from the
+//! [`TypedPath`] trait. Every typed path converts into a [`Path`].
+//!
+#![cfg_attr(feature = "derive", doc = "```")]
+#![cfg_attr(not(feature = "derive"), doc = "```ignore")]
+//! use dynamodb_expression::{path::typed::TypedPath, DynamoPaths, Expression, Num, Path};
+//! # use pretty_assertions::assert_eq;
+//!
+//! #[derive(DynamoPaths)]
+//! struct Person {
+//!     name: String,
+//!     #[serde(rename = "years")]
+//!     age: u8,
+//!     addresses: Vec<Address>,
+//! }
+//!
+//! #[derive(DynamoPaths)]
+//! struct Address {
+//!     city: String,
+//! }
+//!
+//! let person = Person::paths();
+//! let expression = Expression::builder()
+//!     .with_condition(
+//!         person
+//!             .name()
+//!             .begins_with("J")
+//!             .and(person.age().greater_than(Num::new(21)))
+//!             .and(person.addresses().index(0).city().attribute_exists()),
+//!     )
+//!     .with_update(person.age().math().add(1))
+//!     .build();
+//!
+//! assert_eq!(
+//!     Some(String::from(
+//!         "begins_with(#0, :0) AND #1 > :1 AND attribute_exists(#2[0].#3)"
+//!     )),
+//!     expression.condition_expression
+//! );
+//! assert_eq!(
+//!     Path::from(person.addresses().index(0).city()),
+//!     "addresses[0].city".parse().unwrap()
+//! );
+//! ```
+//!
+//! Operations that don't make sense for the type of the attribute don't
+//! compile. There's no `begins_with` for a number:
+//!
+#![cfg_attr(feature = "derive", doc = "```compile_fail")]
+#![cfg_attr(not(feature = "derive"), doc = "```ignore")]
+//! use dynamodb_expression::DynamoPaths;
+//!
+//! #[derive(DynamoPaths)]
+//! struct Person {
+//!     age: u8,
+//! }
+//!
+//! Person::paths().age().begins_with("2");
+//! ```
+//!
+//! [`value::string_set`]: crate::value::string_set
+//! [`value::num_set`]: crate::value::num_set
+//! [`value::binary_set`]: crate::value::binary_set
+
+use core::marker::PhantomData;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+use super::{Element, Name, Path};
+use crate::{
+    condition::{attribute_type::Type, Condition},
+    key::Key,
+    operand::{Operand, Size},
+    update::{
+        set::{
+            if_not_exists::Builder as IfNotExistsBuilder,
+            list_append::Builder as ListAppendBuilder, math::Builder as MathBuilder,
+        },
+        Add, Assign, Delete, Remove,
+    },
+    value::{self, BinarySet, Num, NumSet, StringOrRef, StringSet, Value},
+};
+
+/// A [`Path`] that knows the type of the attribute it points to.
+///
+/// The methods here are the operations that make sense for any type of
+/// attribute. See the implementors for the type-specific ones.
+pub trait TypedPath: Into<Path> {
+    /// Creates the typed path from an untyped one.
+    fn from_path(path: Path) -> Self;
+
+    /// See [`Path::equal`].
+    fn equal<T>(self, right: T) -> Condition
+    where
+        T: Into<Operand>,
+    {
+        self.into().equal(right)
+    }
+
+    /// See [`Path::not_equal`].
+    fn not_equal<T>(self, right: T) -> Condition
+    where
+        T: Into<Operand>,
+    {
+        self.into().not_equal(right)
+    }
+
+    /// See [`Path::attribute_exists`].
+    fn attribute_exists(self) -> Condition {
+        self.into().attribute_exists()
+    }
+
+    /// See [`Path::attribute_not_exists`].
+    fn attribute_not_exists(self) -> Condition {
+        self.into().attribute_not_exists()
+    }
+
+    /// See [`Path::attribute_type`].
+    fn attribute_type(self, attribute_type: Type) -> Condition {
+        self.into().attribute_type(attribute_type)
+    }
+
+    /// See [`Path::assign`].
+    fn assign<T>(self, value: T) -> Assign
+    where
+        T: Into<Value>,
+    {
+        self.into().assign(value)
+    }
+
+    /// See [`Path::if_not_exists`].
+    fn if_not_exists(self) -> IfNotExistsBuilder {
+        self.into().if_not_exists()
+    }
+
+    /// See [`Path::remove`].
+    fn remove(self) -> Remove {
+        self.into().remove()
+    }
+
+    /// See [`Path::key`].
+    fn key(self) -> Key {
+        self.into().key()
+    }
+}
+
+/// Associates a Rust type with the [`TypedPath`] for an attribute of that
+/// type. Implemented by `#[derive(DynamoPaths)]`.
+pub trait PathType {
+    type Path: TypedPath;
+}
+
+/// The [`Path`] to the attribute `name` under `parent`. `parent` may be empty.
+pub fn field(parent: &Path, name: &str) -> Path {
+    let mut path = parent.clone();
+    path.elements.push(Element::new_name(name));
+
+    path
+}
+
+/// Creates the typed path for the top-level attributes of `T`, which converts
+/// to an empty [`Path`].
+pub fn root<T>() -> T::Path
+where
+    T: PathType + ?Sized,
+{
+    T::Path::from_path(Path {
+        elements: Vec::new(),
+    })
+}
+
+macro_rules! typed_path {
+    ($(#[$attr:meta])* $name:ident $(<$param:ident>)?) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name $(<$param>)? {
+            path: Path,
+            $(_item: PhantomData<fn() -> $param>,)?
+        }
+
+        impl $(<$param>)? TypedPath for $name $(<$param>)? {
+            fn from_path(path: Path) -> Self {
+                Self {
+                    path,
+                    $(_item: PhantomData::<fn() -> $param>,)?
+                }
+            }
+        }
+
+        impl $(<$param>)? From<$name $(<$param>)?> for Path {
+            fn from(path: $name $(<$param>)?) -> Self {
+                path.path
+            }
+        }
+    };
+}
+
+macro_rules! ordered {
+    () => {
+        /// See [`Path::greater_than`].
+        pub fn greater_than<T>(self, right: T) -> Condition
+        where
+            T: Into<Operand>,
+        {
+            self.path.greater_than(right)
+        }
+
+        /// See [`Path::greater_than_or_equal`].
+        pub fn greater_than_or_equal<T>(self, right: T) -> Condition
+        where
+            T: Into<Operand>,
+        {
+            self.path.greater_than_or_equal(right)
+        }
+
+        /// See [`Path::less_than`].
+        pub fn less_than<T>(self, right: T) -> Condition
+        where
+            T: Into<Operand>,
+        {
+            self.path.less_than(right)
+        }
+
+        /// See [`Path::less_than_or_equal`].
+        pub fn less_than_or_equal<T>(self, right: T) -> Condition
+        where
+            T: Into<Operand>,
+        {
+            self.path.less_than_or_equal(right)
+        }
+
+        /// See [`Path::between`].
+        pub fn between<L, U>(self, lower: L, upper: U) -> Condition
+        where
+            L: Into<Operand>,
+            U: Into<Operand>,
+        {
+            self.path.between(lower, upper)
+        }
+
+        /// See [`Path::in_`].
+        pub fn in_<I, T>(self, items: I) -> Condition
+        where
+            I: IntoIterator<Item = T>,
+            T: Into<Operand>,
+        {
+            self.path.in_(items)
+        }
+    };
+}
+
+macro_rules! sized {
+    () => {
+        /// See [`Path::size`].
+        pub fn size(self) -> Size {
+            self.path.size()
+        }
+    };
+}
+
+typed_path!(
+    /// The path to a string attribute.
+    StringPath
+);
+
+impl StringPath {
+    ordered!();
+    sized!();
+
+    /// See [`Path::begins_with`].
+    pub fn begins_with<T>(self, prefix: T) -> Condition
+    where
+        T: Into<StringOrRef>,
+    {
+        self.path.begins_with(prefix)
+    }
+
+    /// Checks if the string contains the given substring. See [`Path::contains`].
+    pub fn contains<T>(self, substring: T) -> Condition
+    where
+        T: Into<String>,
+    {
+        self.path.contains(substring.into())
+    }
+}
+
+typed_path!(
+    /// The path to a numeric attribute.
+    NumPath
+);
+
+impl NumPath {
+    ordered!();
+
+    /// See [`Path::math`].
+    pub fn math(self) -> MathBuilder {
+        self.path.math()
+    }
+
+    /// Adds to the number with an `ADD` update. See [`Path::add`].
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<T>(self, num: T) -> Add
+    where
+        T: Into<Num>,
+    {
+        self.path.add(num.into())
+    }
+}
+
+typed_path!(
+    /// The path to a boolean attribute.
+    BoolPath
+);
+
+typed_path!(
+    /// The path to a binary attribute.
+    BinaryPath
+);
+
+impl BinaryPath {
+    ordered!();
+    sized!();
+
+    /// See [`Path::begins_with`].
+    pub fn begins_with<T>(self, prefix: T) -> Condition
+    where
+        T: Into<StringOrRef>,
+    {
+        self.path.begins_with(prefix)
+    }
+}
+
+typed_path!(
+    /// The path to a string, number, or binary set attribute.
+    SetPath
+);
+
+impl SetPath {
+    sized!();
+
+    /// Checks if the set contains the given value. See [`Path::contains`].
+    pub fn contains<T>(self, value: T) -> Condition
+    where
+        T: Into<Value>,
+    {
+        self.path.contains(value)
+    }
+
+    /// Adds the elements to the set with an `ADD` update. See [`Path::add`].
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<T>(self, set: T) -> Add
+    where
+        T: Into<value::Set>,
+    {
+        self.path.add(set.into())
+    }
+
+    /// See [`Path::delete`].
+    pub fn delete<T>(self, set: T) -> Delete
+    where
+        T: Into<value::Set>,
+    {
+        self.path.delete(set)
+    }
+}
+
+typed_path!(
+    /// The path to a list attribute, with items of type `T`.
+    ListPath<T>
+);
+
+impl<T> ListPath<T>
+where
+    T: TypedPath,
+{
+    sized!();
+
+    /// The path to the item at `index` in the list.
+    pub fn index(self, index: usize) -> T {
+        let mut path = self.path;
+        match path.elements.pop() {
+            Some(Element::Name(name)) => {
+                path.elements.push(Element::new_indexed_field(name, index))
+            }
+            Some(Element::IndexedField(mut field)) => {
+                field.indexes.push(index);
+                path.elements.push(field.into());
+            }
+            Some(Element::NameRef(name_ref)) => path
+                .elements
+                .push(Element::new_indexed_field(name_ref, index)),
+            // An empty path has nothing to index.
+            None => {}
+        }
+
+        T::from_path(path)
+    }
+
+    /// Checks if the list contains the given value. See [`Path::contains`].
+    pub fn contains<V>(self, value: V) -> Condition
+    where
+        V: Into<Value>,
+    {
+        self.path.contains(value)
+    }
+
+    /// See [`Path::list_append`].
+    pub fn list_append(self) -> ListAppendBuilder {
+        self.path.list_append()
+    }
+}
+
+typed_path!(
+    /// The path to a map attribute, with values of type `T`.
+    MapPath<T>
+);
+
+impl<T> MapPath<T>
+where
+    T: TypedPath,
+{
+    sized!();
+
+    /// The path to the value at `key` in the map.
+    pub fn get<N>(self, key: N) -> T
+    where
+        N: Into<Name>,
+    {
+        let mut path = self.path;
+        path.elements.push(Element::new_name(key));
+
+        T::from_path(path)
+    }
+}
+
+macro_rules! path_type {
+    ($path:ty => $($ty:ty),+ $(,)?) => {
+        $(
+            impl PathType for $ty {
+                type Path = $path;
+            }
+        )+
+    };
+}
+
+path_type!(StringPath => String, str, char, StringOrRef);
+path_type!(NumPath => Num, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+path_type!(BoolPath => bool);
+path_type!(SetPath => value::Set, StringSet, NumSet, BinarySet);
+
+impl<T> PathType for &T
+where
+    T: PathType + ?Sized,
+{
+    type Path = T::Path;
+}
+
+impl<T> PathType for Option<T>
+where
+    T: PathType,
+{
+    type Path = T::Path;
+}
+
+impl<T> PathType for Box<T>
+where
+    T: PathType + ?Sized,
+{
+    type Path = T::Path;
+}
+
+impl<T> PathType for std::borrow::Cow<'_, T>
+where
+    T: PathType + ToOwned + ?Sized,
+{
+    type Path = T::Path;
+}
+
+impl<T> PathType for [T]
+where
+    T: PathType,
+{
+    type Path = ListPath<T::Path>;
+}
+
+impl<T, const N: usize> PathType for [T; N]
+where
+    T: PathType,
+{
+    type Path = ListPath<T::Path>;
+}
+
+macro_rules! list_type {
+    ($($ty:ident),+) => {
+        $(
+            impl<T> PathType for $ty<T>
+            where
+                T: PathType,
+            {
+                type Path = ListPath<T::Path>;
+            }
+        )+
+    };
+}
+
+// Without `#[serde(with = "...")]`, sets are serialized as lists.
+list_type!(Vec, VecDeque, BTreeSet, HashSet);
+
+impl<K, T> PathType for BTreeMap<K, T>
+where
+    T: PathType,
+{
+    type Path = MapPath<T::Path>;
+}
+
+impl<K, T, S> PathType for HashMap<K, T, S>
+where
+    T: PathType,
+{
+    type Path = MapPath<T::Path>;
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{field, root, ListPath, MapPath, NumPath, PathType, StringPath, TypedPath};
+    use crate::{value::StringSet, Num, Path};
+
+    #[test]
+    fn paths() {
+        let tags: ListPath<ListPath<StringPath>> =
+            TypedPath::from_path(field(&Path::new_name("a"), "tags"));
+        assert_eq!(
+            "a.tags[1][2]".parse::<Path>().unwrap(),
+            Path::from(tags.index(1).index(2))
+        );
+
+        let counts = root::<std::collections::HashMap<String, u32>>();
+        assert_eq!(Path::from(counts.clone()).to_string(), "");
+        let count: NumPath = MapPath::<NumPath>::from_path(Path::new_name("counts")).get("a");
+        assert_eq!("counts.a", Path::from(count.clone()).to_string());
+        assert_eq!("counts.a = 1", count.equal(Num::new(1)).to_string());
+
+        let tags = <Vec<String> as PathType>::Path::from_path(Path::new_name("tags"));
+        assert_eq!("contains(tags, \"a\")", tags.contains("a").to_string());
+
+        let set = <StringSet as PathType>::Path::from_path(Path::new_name("set"));
+        assert_eq!(
+            "ADD set [\"a\"]",
+            set.add(StringSet::from(["a"])).to_string()
+        );
+    }
+}
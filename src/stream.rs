@@ -0,0 +1,589 @@
+//! Evaluating [`Condition`]s against DynamoDB Streams records, to check
+//! business rules or detect changes in stream consumers.
+//!
+//! A [`StreamRecord`] holds the images from a record, either converted from
+//! [`AttributeValue`]s or read from the JSON of a Lambda event.
+//!
+//! ```
+//! use dynamodb_expression::{stream::StreamRecord, Path};
+//! use serde_json::json;
+//!
+//! let record = StreamRecord::from_lambda_record(&json!({
+//!     "eventName": "MODIFY",
+//!     "dynamodb": {
+//!         "Keys": { "id": { "S": "1" } },
+//!         "OldImage": { "id": { "S": "1" }, "status": { "S": "PENDING" } },
+//!         "NewImage": { "id": { "S": "1" }, "status": { "S": "SHIPPED" } },
+//!     }
+//! }))?;
+//!
+//! let shipped = Path::new_name("status").equal("SHIPPED");
+//! let transition = record.transition(&shipped)?;
+//! assert!(transition.became_true());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! # Records from `aws-sdk-dynamodbstreams`
+//!
+//! With the `dynamodbstreams` feature, a [`StreamRecord`] can be converted
+//! from a `Record` or `StreamRecord` from the `aws-sdk-dynamodbstreams` crate,
+//! with [`TryFrom`]. That crate has its own `AttributeValue` type, separate
+//! from the one in `aws-sdk-dynamodb` that [`StreamRecord::from_items`] takes.
+//!
+//! [`Condition`]: crate::condition::Condition
+
+use core::fmt;
+use std::{collections::HashMap, error::Error};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::Value as Json;
+
+use crate::{
+    condition::{Condition, EvalError},
+    path::Name,
+    value::{
+        BinarySet, List, Map, Num, NumSet, Scalar, StringSet, UnknownAttributeValueError, Value,
+    },
+};
+
+pub use crate::condition::StreamImage;
+
+/// The images from a DynamoDB Streams record.
+///
+/// Which images a record has depends on the stream's view type, and on the
+/// event: an `INSERT` has no old image, and a `REMOVE` has no new image.
+///
+/// See the [module docs](self) for an example.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreamRecord {
+    keys: Option<Map>,
+    new_image: Option<Map>,
+    old_image: Option<Map>,
+}
+
+impl StreamRecord {
+    /// A record with the given images, as [`AttributeValue`]s from
+    /// `aws-sdk-dynamodb`. See the [module docs](crate::stream#records-from-aws-sdk-dynamodbstreams)
+    /// for records from `aws-sdk-dynamodbstreams`.
+    pub fn from_items(
+        keys: Option<HashMap<String, AttributeValue>>,
+        new_image: Option<HashMap<String, AttributeValue>>,
+        old_image: Option<HashMap<String, AttributeValue>>,
+    ) -> Result<Self, StreamError> {
+        let map = |item: Option<HashMap<String, AttributeValue>>| {
+            item.map(|item| {
+                item.into_iter()
+                    .map(|(name, value)| {
+                        Value::try_from(value).map(|value| (Name::from(name), value))
+                    })
+                    .collect::<Result<Map, _>>()
+            })
+            .transpose()
+        };
+
+        Ok(Self {
+            keys: map(keys)?,
+            new_image: map(new_image)?,
+            old_image: map(old_image)?,
+        })
+    }
+
+    /// Reads a record from the JSON of a DynamoDB Streams Lambda event. Takes
+    /// either a whole record (with the `dynamodb` field), or just its
+    /// `dynamodb` field.
+    pub fn from_lambda_record(record: &Json) -> Result<Self, StreamError> {
+        let record = record.get("dynamodb").unwrap_or(record);
+        if !record.is_object() {
+            return Err(StreamError::InvalidJson(format!(
+                "expected a stream record: {record}"
+            )));
+        }
+
+        let image = |image: StreamImage| record.get(image.as_str()).map(map_from_json).transpose();
+
+        Ok(Self {
+            keys: image(StreamImage::Keys)?,
+            new_image: image(StreamImage::NewImage)?,
+            old_image: image(StreamImage::OldImage)?,
+        })
+    }
+
+    /// Reads the records from the JSON of a DynamoDB Streams Lambda event,
+    /// which has them in its `Records` field.
+    pub fn from_lambda_event(event: &Json) -> Result<Vec<Self>, StreamError> {
+        event
+            .get("Records")
+            .and_then(Json::as_array)
+            .ok_or_else(|| StreamError::InvalidJson("expected `Records` in the event".into()))?
+            .iter()
+            .map(Self::from_lambda_record)
+            .collect()
+    }
+
+    /// Sets the key attributes of the modified item.
+    #[must_use]
+    pub fn with_keys(mut self, keys: Map) -> Self {
+        self.keys = Some(keys);
+
+        self
+    }
+
+    /// Sets the item as it was after it was modified.
+    #[must_use]
+    pub fn with_new_image(mut self, new_image: Map) -> Self {
+        self.new_image = Some(new_image);
+
+        self
+    }
+
+    /// Sets the item as it was before it was modified.
+    #[must_use]
+    pub fn with_old_image(mut self, old_image: Map) -> Self {
+        self.old_image = Some(old_image);
+
+        self
+    }
+
+    /// The image, if the record has it.
+    pub fn image(&self, image: StreamImage) -> Option<&Map> {
+        match image {
+            StreamImage::NewImage => self.new_image.as_ref(),
+            StreamImage::OldImage => self.old_image.as_ref(),
+            StreamImage::Keys => self.keys.as_ref(),
+        }
+    }
+
+    /// Evaluates the condition against the image, as with
+    /// [`Condition::evaluate`]. If the record doesn't have the image, the
+    /// condition is evaluated as if the item didn't exist, the same as
+    /// DynamoDB does for a condition on a write to an item that doesn't exist.
+    pub fn evaluate(&self, condition: &Condition, image: StreamImage) -> Result<bool, EvalError> {
+        match self.image(image) {
+            Some(item) => condition.evaluate(item),
+            None => condition.evaluate(&Map::default()),
+        }
+    }
+
+    /// Evaluates the condition against the old and new images, to see how the
+    /// change to the item affected it. As with [`Condition::evaluate`], the
+    /// condition is grouped the way it's written, as DynamoDB would.
+    ///
+    /// See [`StreamRecord::evaluate`] for images the record doesn't have.
+    pub fn transition(&self, condition: &Condition) -> Result<Transition, EvalError> {
+        Ok(Transition {
+            before: self.evaluate(condition, StreamImage::OldImage)?,
+            after: self.evaluate(condition, StreamImage::NewImage)?,
+        })
+    }
+}
+
+/// Requires the `dynamodbstreams` feature.
+#[cfg(feature = "dynamodbstreams")]
+impl TryFrom<aws_sdk_dynamodbstreams::types::StreamRecord> for StreamRecord {
+    type Error = StreamError;
+
+    /// Converts the images of a record from the `aws-sdk-dynamodbstreams`
+    /// crate.
+    ///
+    /// ```
+    /// use aws_sdk_dynamodbstreams::types::{AttributeValue, StreamRecord as Record};
+    /// use dynamodb_expression::{stream::StreamRecord, Path};
+    ///
+    /// let record = Record::builder()
+    ///     .keys("id", AttributeValue::S("1".into()))
+    ///     .new_image("id", AttributeValue::S("1".into()))
+    ///     .new_image("status", AttributeValue::S("SHIPPED".into()))
+    ///     .build();
+    ///
+    /// let record = StreamRecord::try_from(record)?;
+    /// let shipped = Path::new_name("status").equal("SHIPPED");
+    /// assert!(record.transition(&shipped)?.became_true());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn try_from(record: aws_sdk_dynamodbstreams::types::StreamRecord) -> Result<Self, Self::Error> {
+        use aws_sdk_dynamodbstreams::types::AttributeValue as StreamValue;
+
+        fn convert(value: StreamValue) -> Result<AttributeValue, StreamError> {
+            Ok(match value {
+                StreamValue::B(b) => AttributeValue::B(b),
+                StreamValue::Bool(b) => AttributeValue::Bool(b),
+                StreamValue::Bs(bs) => AttributeValue::Bs(bs),
+                StreamValue::L(l) => {
+                    AttributeValue::L(l.into_iter().map(convert).collect::<Result<_, _>>()?)
+                }
+                StreamValue::M(m) => AttributeValue::M(
+                    m.into_iter()
+                        .map(|(k, v)| convert(v).map(|v| (k, v)))
+                        .collect::<Result<_, _>>()?,
+                ),
+                StreamValue::N(n) => AttributeValue::N(n),
+                StreamValue::Ns(ns) => AttributeValue::Ns(ns),
+                StreamValue::Null(null) => AttributeValue::Null(null),
+                StreamValue::S(s) => AttributeValue::S(s),
+                StreamValue::Ss(ss) => AttributeValue::Ss(ss),
+                _ => return Err(StreamError::UnsupportedValue(format!("{value:?}"))),
+            })
+        }
+
+        let item = |item: Option<HashMap<String, StreamValue>>| {
+            item.map(|item| {
+                item.into_iter()
+                    .map(|(name, value)| convert(value).map(|value| (name, value)))
+                    .collect::<Result<_, _>>()
+            })
+            .transpose()
+        };
+
+        Self::from_items(
+            item(record.keys)?,
+            item(record.new_image)?,
+            item(record.old_image)?,
+        )
+    }
+}
+
+/// Requires the `dynamodbstreams` feature.
+#[cfg(feature = "dynamodbstreams")]
+impl TryFrom<aws_sdk_dynamodbstreams::types::Record> for StreamRecord {
+    type Error = StreamError;
+
+    /// Converts the images of a record from the `aws-sdk-dynamodbstreams`
+    /// crate. A record without its `dynamodb` field has no images.
+    fn try_from(record: aws_sdk_dynamodbstreams::types::Record) -> Result<Self, Self::Error> {
+        record
+            .dynamodb
+            .map_or_else(|| Ok(Self::default()), Self::try_from)
+    }
+}
+
+/// Whether a condition was true for the old and new images of a
+/// [`StreamRecord`].
+///
+/// See also: [`StreamRecord::transition`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Transition {
+    before: bool,
+    after: bool,
+}
+
+impl Transition {
+    /// Whether the condition was true for the old image.
+    pub fn before(&self) -> bool {
+        self.before
+    }
+
+    /// Whether the condition is true for the new image.
+    pub fn after(&self) -> bool {
+        self.after
+    }
+
+    /// The condition was false for the old image and is true for the new one.
+    pub fn became_true(&self) -> bool {
+        !self.before && self.after
+    }
+
+    /// The condition was true for the old image and is false for the new one.
+    pub fn became_false(&self) -> bool {
+        self.before && !self.after
+    }
+
+    /// The condition's result is different for the two images.
+    pub fn changed(&self) -> bool {
+        self.before != self.after
+    }
+}
+
+/// Why a DynamoDB Streams record couldn't be read.
+#[derive(Debug)]
+pub enum StreamError {
+    /// The JSON isn't a DynamoDB Streams record.
+    InvalidJson(String),
+
+    /// The record has an [`AttributeValue`] variant this crate doesn't support.
+    UnknownAttributeValue(UnknownAttributeValueError),
+
+    /// The record, from `aws-sdk-dynamodbstreams`, has an attribute value
+    /// variant this crate doesn't support. Like [`UnknownAttributeValue`],
+    /// this only happens if a variant is added to that crate. Holds the
+    /// value's `Debug` form.
+    ///
+    /// [`UnknownAttributeValue`]: StreamError::UnknownAttributeValue
+    UnsupportedValue(String),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidJson(message) => f.write_str(message),
+            Self::UnknownAttributeValue(err) => err.fmt(f),
+            Self::UnsupportedValue(value) => write!(f, "unsupported attribute value: {value}"),
+        }
+    }
+}
+
+impl Error for StreamError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidJson(_) | Self::UnsupportedValue(_) => None,
+            Self::UnknownAttributeValue(err) => Some(err),
+        }
+    }
+}
+
+impl From<UnknownAttributeValueError> for StreamError {
+    fn from(err: UnknownAttributeValueError) -> Self {
+        Self::UnknownAttributeValue(err)
+    }
+}
+
+/// Reads a value in the JSON form DynamoDB uses for an [`AttributeValue`],
+/// such as `{"S": "hello"}`.
+fn value_from_json(value: &Json) -> Result<Value, StreamError> {
+    let invalid = || StreamError::InvalidJson(format!("invalid attribute value: {value}"));
+    let string = |value: &Json| value.as_str().map(str::to_owned).ok_or_else(invalid);
+    let binary = |value: &Json| {
+        general_purpose::STANDARD
+            .decode(value.as_str().ok_or_else(invalid)?)
+            .map_err(|_| invalid())
+    };
+
+    let object = value.as_object().ok_or_else(invalid)?;
+    let mut fields = object.iter();
+    let (Some((kind, value)), None) = (fields.next(), fields.next()) else {
+        return Err(invalid());
+    };
+
+    Ok(match kind.as_str() {
+        "S" => Scalar::String(string(value)?).into(),
+        "N" => Num { n: string(value)? }.into(),
+        "B" => Scalar::Binary(binary(value)?).into(),
+        "BOOL" => Scalar::Bool(value.as_bool().ok_or_else(invalid)?).into(),
+        "NULL" => Scalar::Null.into(),
+        "SS" => value
+            .as_array()
+            .ok_or_else(invalid)?
+            .iter()
+            .map(string)
+            .collect::<Result<StringSet, _>>()?
+            .into(),
+        "NS" => value
+            .as_array()
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|n| string(n).map(|n| Num { n }))
+            .collect::<Result<NumSet, _>>()?
+            .into(),
+        "BS" => value
+            .as_array()
+            .ok_or_else(invalid)?
+            .iter()
+            .map(binary)
+            .collect::<Result<BinarySet, _>>()?
+            .into(),
+        "L" => List::from(
+            value
+                .as_array()
+                .ok_or_else(invalid)?
+                .iter()
+                .map(value_from_json)
+                .collect::<Result<Vec<_>, _>>()?,
+        )
+        .into(),
+        "M" => map_from_json(value)?.into(),
+        _ => return Err(invalid()),
+    })
+}
+
+/// Reads an item, or the value of a map, in the JSON form DynamoDB uses.
+fn map_from_json(map: &Json) -> Result<Map, StreamError> {
+    map.as_object()
+        .ok_or_else(|| StreamError::InvalidJson(format!("invalid map: {map}")))?
+        .iter()
+        .map(|(name, value)| value_from_json(value).map(|value| (name.as_str(), value)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use aws_sdk_dynamodb::{primitives::Blob, types::AttributeValue};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use crate::{
+        value::{List, Map, Set, Value},
+        Num, Path,
+    };
+
+    use super::{StreamImage, StreamRecord, Transition};
+
+    #[test]
+    fn lambda_records() {
+        let records = StreamRecord::from_lambda_event(&json!({
+            "Records": [
+                {
+                    "eventName": "INSERT",
+                    "dynamodb": {
+                        "Keys": { "id": { "S": "1" } },
+                        "NewImage": {
+                            "id": { "S": "1" },
+                            "n": { "N": "2.5" },
+                            "b": { "B": "aGk=" },
+                            "ok": { "BOOL": true },
+                            "none": { "NULL": true },
+                            "ss": { "SS": ["x"] },
+                            "ns": { "NS": ["1"] },
+                            "bs": { "BS": ["eW8="] },
+                            "l": { "L": [{ "S": "y" }] },
+                            "m": { "M": { "z": { "N": "3" } } },
+                        },
+                    },
+                },
+                { "Keys": { "id": { "S": "2" } } },
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                StreamRecord::default()
+                    .with_keys(Map::from([("id", "1")]))
+                    .with_new_image(Map::from([
+                        ("id", Value::from("1")),
+                        ("n", Num::new(2.5).into()),
+                        ("b", Value::new_binary("hi")),
+                        ("ok", true.into()),
+                        ("none", Value::new_null()),
+                        ("ss", Set::new_string_set(["x"]).into()),
+                        ("ns", Set::new_num_set([1]).into()),
+                        ("bs", Set::new_binary_set(["yo"]).into()),
+                        ("l", List::from(["y"]).into()),
+                        ("m", Map::from([("z", Num::new(3))]).into()),
+                    ])),
+                StreamRecord::default().with_keys(Map::from([("id", "2")])),
+            ],
+            records
+        );
+
+        assert!(StreamRecord::from_lambda_record(&json!([])).is_err());
+        assert!(
+            StreamRecord::from_lambda_record(&json!({ "NewImage": { "a": { "X": 1 } } })).is_err()
+        );
+        assert!(StreamRecord::from_lambda_event(&json!({})).is_err());
+    }
+
+    #[test]
+    fn transitions() {
+        let record = StreamRecord::from_items(
+            None,
+            Some(HashMap::from([
+                ("status".to_string(), AttributeValue::S("SHIPPED".into())),
+                ("data".to_string(), AttributeValue::B(Blob::new("x"))),
+            ])),
+            Some(HashMap::from([(
+                "status".to_string(),
+                AttributeValue::S("PENDING".into()),
+            )])),
+        )
+        .unwrap();
+
+        let shipped = Path::new_name("status").equal("SHIPPED");
+        assert!(record.evaluate(&shipped, StreamImage::NewImage).unwrap());
+        assert!(!record.evaluate(&shipped, StreamImage::OldImage).unwrap());
+        // No keys in this record, so it's as if the item doesn't exist.
+        assert!(!record.evaluate(&shipped, StreamImage::Keys).unwrap());
+
+        let transition = record.transition(&shipped).unwrap();
+        assert_eq!((false, true), (transition.before(), transition.after()));
+        assert!(transition.became_true());
+        assert!(!transition.became_false());
+        assert!(transition.changed());
+
+        let has_status = Path::new_name("status").attribute_exists();
+        assert_eq!(
+            Transition {
+                before: true,
+                after: true
+            },
+            record.transition(&has_status).unwrap()
+        );
+
+        // An `INSERT` has no old image.
+        let record = StreamRecord::default().with_new_image(Map::from([("status", "SHIPPED")]));
+        assert!(record.transition(&shipped).unwrap().became_true());
+
+        // Written as `status = "SHIPPED" OR status = "PENDING" AND
+        // attribute_exists(data)`, so it's true for any shipped item, with or
+        // without data.
+        let condition = shipped
+            .clone()
+            .or(Path::new_name("status").equal("PENDING"))
+            .and(Path::new_name("data").attribute_exists());
+        let record = StreamRecord::default()
+            .with_old_image(Map::from([("status", "PENDING")]))
+            .with_new_image(Map::from([("status", "SHIPPED")]));
+        assert_eq!(
+            Transition {
+                before: false,
+                after: true
+            },
+            record.transition(&condition).unwrap()
+        );
+    }
+
+    #[cfg(feature = "dynamodbstreams")]
+    #[test]
+    fn streams_sdk_records() {
+        use aws_sdk_dynamodbstreams::types::{
+            AttributeValue as StreamValue, Record, StreamRecord as SdkRecord,
+        };
+
+        let record = SdkRecord::builder()
+            .keys("id", StreamValue::S("1".into()))
+            .new_image("id", StreamValue::S("1".into()))
+            .new_image("n", StreamValue::N("2.5".into()))
+            .new_image("b", StreamValue::B(Blob::new("hi")))
+            .new_image("ok", StreamValue::Bool(true))
+            .new_image("none", StreamValue::Null(true))
+            .new_image("ss", StreamValue::Ss(vec!["x".into()]))
+            .new_image("ns", StreamValue::Ns(vec!["1".into()]))
+            .new_image("bs", StreamValue::Bs(vec![Blob::new("yo")]))
+            .new_image("l", StreamValue::L(vec![StreamValue::S("y".into())]))
+            .new_image(
+                "m",
+                StreamValue::M(HashMap::from([(
+                    "z".to_string(),
+                    StreamValue::N("3".into()),
+                )])),
+            )
+            .old_image("id", StreamValue::S("1".into()))
+            .build();
+
+        assert_eq!(
+            StreamRecord::default()
+                .with_keys(Map::from([("id", "1")]))
+                .with_new_image(Map::from([
+                    ("id", Value::from("1")),
+                    ("n", Num::new(2.5).into()),
+                    ("b", Value::new_binary("hi")),
+                    ("ok", true.into()),
+                    ("none", Value::new_null()),
+                    ("ss", Set::new_string_set(["x"]).into()),
+                    ("ns", Set::new_num_set([1]).into()),
+                    ("bs", Set::new_binary_set(["yo"]).into()),
+                    ("l", List::from(["y"]).into()),
+                    ("m", Map::from([("z", Num::new(3))]).into()),
+                ]))
+                .with_old_image(Map::from([("id", "1")])),
+            StreamRecord::try_from(Record::builder().dynamodb(record).build()).unwrap()
+        );
+
+        // No `dynamodb` field, so no images.
+        assert_eq!(
+            StreamRecord::default(),
+            StreamRecord::try_from(Record::builder().build()).unwrap()
+        );
+    }
+}
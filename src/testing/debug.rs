@@ -1,4 +1,4 @@
-// TODO: Pull this out into its own crate.
+//! `Debug` and `PartialEq` for DynamoDB items that ignore the order of sets.
 
 use std::{
     borrow::Borrow,
@@ -6,17 +6,14 @@ use std::{
     fmt,
 };
 
-// Re-export the AWS SDK we're using
-pub use aws_sdk_dynamodb::types::AttributeValue;
-
 use aws_sdk_dynamodb::{
     primitives::Blob,
-    types::AttributeValue::{Bs, Ns, Ss, B, L, M},
+    types::AttributeValue::{self, Bs, Ns, Ss, B, L, M},
 };
 use itermap::IterMap;
 use itertools::Itertools;
 
-use super::item::base64_encode;
+use crate::value::base64;
 
 /// Provides a nicer debug view of a DynamoDB item
 /// (`HashMap<String, AttributeValue>`), owned or borrowed.
@@ -59,6 +56,8 @@ where
     }
 }
 
+/// Provides a nicer debug view of a list of DynamoDB `AttributeValue`s, owned
+/// or borrowed.
 #[derive(Clone)]
 pub struct DebugList<I, T>(pub I)
 where
@@ -170,7 +169,7 @@ where
                 .field(
                     &bs.iter()
                         // Write bytes as base64 strings
-                        .map(base64_encode)
+                        .map(base64)
                         // Use a BTreeSet to make the order stable for printing.
                         .collect::<BTreeSet<_>>(),
                 )
@@ -179,7 +178,7 @@ where
             B(b) => f
                 .debug_tuple("B")
                 // Write bytes as base64 strings
-                .field(&base64_encode(b))
+                .field(&base64(b))
                 .finish(),
             // For variants that contain more `AttributeValue`s, write those nicely, too.
             L(l) => f
@@ -211,7 +210,7 @@ mod test {
     use itertools::Itertools;
     use pretty_assertions::{assert_eq, assert_ne};
 
-    use crate::dynamodb::DebugItem;
+    use super::DebugItem;
 
     #[test]
     fn partial_eq() {
@@ -0,0 +1,303 @@
+//! Structural differences between DynamoDB items.
+
+use core::fmt;
+use std::{
+    borrow::Borrow,
+    collections::{BTreeSet, HashMap},
+    vec,
+};
+
+use aws_sdk_dynamodb::types::AttributeValue::{self, L, M};
+
+use super::DebugAttributeValue;
+use crate::{
+    path::{Element, Path},
+    Value,
+};
+
+/// The differences between two DynamoDB items, by the [`Path`] of each
+/// attribute that differs. The order of values in sets is ignored, as it is in
+/// DynamoDB.
+///
+/// ```
+/// use dynamodb_expression::{
+///     testing::{item, ItemDiff},
+///     Num, Value,
+/// };
+/// # use pretty_assertions::assert_eq;
+///
+/// let expected = item([("name", Value::from("Jane")), ("age", Num::new(21).into())]);
+/// let actual = item([("name", Value::from("Jane")), ("age", Num::new(22).into())]);
+///
+/// assert_eq!("~ age: 21 -> 22", ItemDiff::new(&expected, &actual).to_string());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ItemDiff {
+    differences: Vec<Difference>,
+}
+
+impl ItemDiff {
+    /// Compares the `actual` item to the `expected` one.
+    pub fn new<E, A>(expected: E, actual: A) -> Self
+    where
+        E: Borrow<HashMap<String, AttributeValue>>,
+        A: Borrow<HashMap<String, AttributeValue>>,
+    {
+        let mut differences = Vec::new();
+        diff_maps(None, expected.borrow(), actual.borrow(), &mut differences);
+
+        Self { differences }
+    }
+
+    /// Whether the items are equal.
+    pub fn is_empty(&self) -> bool {
+        self.differences.is_empty()
+    }
+
+    /// The differences, in order of their paths.
+    pub fn differences(&self) -> &[Difference] {
+        &self.differences
+    }
+}
+
+impl IntoIterator for ItemDiff {
+    type Item = Difference;
+    type IntoIter = vec::IntoIter<Difference>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.differences.into_iter()
+    }
+}
+
+/// Writes one [`Difference`] per line.
+impl fmt::Display for ItemDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        self.differences.iter().try_for_each(|difference| {
+            if first {
+                first = false;
+            } else {
+                f.write_str("\n")?;
+            }
+
+            difference.fmt(f)
+        })
+    }
+}
+
+/// A single difference between two DynamoDB items.
+///
+/// See also: [`ItemDiff`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Difference {
+    /// The attribute is in the expected item, but not the actual one.
+    /// Displayed as `- path: expected`.
+    Missing {
+        path: Path,
+        expected: AttributeValue,
+    },
+    /// The attribute is in the actual item, but not the expected one.
+    /// Displayed as `+ path: actual`.
+    Unexpected { path: Path, actual: AttributeValue },
+    /// The attribute is in both items, with different values. Displayed as
+    /// `~ path: expected -> actual`.
+    Changed {
+        path: Path,
+        expected: AttributeValue,
+        actual: AttributeValue,
+    },
+}
+
+impl Difference {
+    /// The path to the attribute that differs.
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Missing { path, .. }
+            | Self::Unexpected { path, .. }
+            | Self::Changed { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { path, expected } => {
+                write!(f, "- {path}: {}", DisplayValue(expected))
+            }
+            Self::Unexpected { path, actual } => write!(f, "+ {path}: {}", DisplayValue(actual)),
+            Self::Changed {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "~ {path}: {} -> {}",
+                DisplayValue(expected),
+                DisplayValue(actual)
+            ),
+        }
+    }
+}
+
+/// Writes the value the way the rest of this crate does, falling back to
+/// [`DebugAttributeValue`] for anything [`Value`] can't hold.
+struct DisplayValue<'a>(&'a AttributeValue);
+
+impl fmt::Display for DisplayValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match Value::try_from(self.0.clone()) {
+            Ok(value) => write!(f, "{value}"),
+            Err(_) => write!(f, "{:?}", DebugAttributeValue(self.0)),
+        }
+    }
+}
+
+fn diff_maps(
+    path: Option<&Path>,
+    expected: &HashMap<String, AttributeValue>,
+    actual: &HashMap<String, AttributeValue>,
+    differences: &mut Vec<Difference>,
+) {
+    let names: BTreeSet<_> = expected.keys().chain(actual.keys()).collect();
+    for name in names {
+        let path = match path {
+            Some(path) => {
+                let mut path = path.clone();
+                path.elements.push(Element::new_name(name.as_str()));
+                path
+            }
+            None => Path::new_name(name.as_str()),
+        };
+
+        diff_values(path, expected.get(name), actual.get(name), differences);
+    }
+}
+
+fn diff_values(
+    path: Path,
+    expected: Option<&AttributeValue>,
+    actual: Option<&AttributeValue>,
+    differences: &mut Vec<Difference>,
+) {
+    let (expected, actual) = match (expected, actual) {
+        (Some(expected), Some(actual)) => (expected, actual),
+        (Some(expected), None) => {
+            differences.push(Difference::Missing {
+                path,
+                expected: expected.clone(),
+            });
+            return;
+        }
+        (None, Some(actual)) => {
+            differences.push(Difference::Unexpected {
+                path,
+                actual: actual.clone(),
+            });
+            return;
+        }
+        (None, None) => return,
+    };
+
+    match (expected, actual) {
+        (M(expected), M(actual)) => diff_maps(Some(&path), expected, actual, differences),
+        (L(expected), L(actual)) => {
+            for index in 0..expected.len().max(actual.len()) {
+                diff_values(
+                    indexed(&path, index),
+                    expected.get(index),
+                    actual.get(index),
+                    differences,
+                );
+            }
+        }
+        _ if DebugAttributeValue(expected) == DebugAttributeValue(actual) => {}
+        _ => differences.push(Difference::Changed {
+            path,
+            expected: expected.clone(),
+            actual: actual.clone(),
+        }),
+    }
+}
+
+/// The path to an item in the list at `path`.
+fn indexed(path: &Path, index: usize) -> Path {
+    let mut path = path.clone();
+    let element = match path.elements.pop() {
+        Some(Element::Name(name)) => Element::new_indexed_field(name, index),
+        Some(Element::IndexedField(mut field)) => {
+            field.indexes.push(index);
+            field.into()
+        }
+        // Paths into items are only ever built from attribute names.
+        element => unreachable!("Cannot index into {element:?}"),
+    };
+    path.elements.push(element);
+
+    path
+}
+
+#[cfg(test)]
+mod test {
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use pretty_assertions::assert_eq;
+
+    use super::{Difference, ItemDiff};
+    use crate::{
+        testing::item,
+        value::{List, StringSet},
+        Map, Num, Path, Value,
+    };
+
+    #[test]
+    fn diff() {
+        let expected = item([
+            ("id", Value::from("a")),
+            ("tags", StringSet::from(["x", "y", "z"]).into()),
+            (
+                "nested",
+                Map::from([
+                    ("list", Value::from(List::from(["a", "b"]))),
+                    ("count", Num::new(1).into()),
+                ])
+                .into(),
+            ),
+            ("gone", true.into()),
+        ]);
+        let actual = item([
+            ("id", Value::from("a")),
+            ("tags", StringSet::from(["z", "y", "x"]).into()),
+            (
+                "nested",
+                Map::from([
+                    ("list", Value::from(List::from(["a", "c", "d"]))),
+                    ("count", Num::new(2).into()),
+                ])
+                .into(),
+            ),
+            ("new", "here".into()),
+        ]);
+
+        assert!(ItemDiff::new(&expected, &expected).is_empty());
+
+        let diff = ItemDiff::new(&expected, &actual);
+        assert_eq!(&Path::new_name("gone"), diff.differences()[0].path());
+        assert_eq!(
+            "- gone: true\n\
+            ~ nested.count: 1 -> 2\n\
+            ~ nested.list[1]: \"b\" -> \"c\"\n\
+            + nested.list[2]: \"d\"\n\
+            + new: \"here\"",
+            diff.to_string()
+        );
+
+        assert_eq!(
+            Difference::Changed {
+                path: "nested.list[1]".parse().unwrap(),
+                expected: AttributeValue::S("b".into()),
+                actual: AttributeValue::S("c".into()),
+            },
+            diff.into_iter().nth(2).unwrap()
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Helpers for tests that read and write DynamoDB items.
+//!
+//! Requires the `testing` feature.
+//!
+//! The AWS SDK holds sets in a `Vec`, so two items that DynamoDB considers
+//! equal may not be equal to each other once they've been through the SDK.
+//! [`DebugItem`] and [`DebugAttributeValue`] compare items the way DynamoDB
+//! does, and print them in a stable order. [`ItemDiff`] lists the attributes
+//! that differ between two items, which is what [`assert_item_eq!`] and
+//! [`assert_items_eq!`] report when they fail. [`item`] and [`items`] build
+//! items from [`Map`]s, for the expected side of those comparisons.
+//!
+//! ```
+//! use aws_sdk_dynamodb::types::AttributeValue;
+//! use dynamodb_expression::{assert_item_eq, testing::item, value::StringSet, Num, Value};
+//! use std::collections::HashMap;
+//!
+//! let got = HashMap::from([
+//!     ("name".to_string(), AttributeValue::S("Jane".into())),
+//!     ("age".to_string(), AttributeValue::N("21".into())),
+//!     (
+//!         "tags".to_string(),
+//!         AttributeValue::Ss(vec!["b".into(), "a".into()]),
+//!     ),
+//! ]);
+//!
+//! assert_item_eq!(
+//!     item([
+//!         ("name", Value::from("Jane")),
+//!         ("age", Num::new(21).into()),
+//!         ("tags", StringSet::from(["a", "b"]).into()),
+//!     ]),
+//!     got,
+//! );
+//! ```
+//!
+//! [`assert_item_eq!`]: crate::assert_item_eq
+//! [`assert_items_eq!`]: crate::assert_items_eq
+
+mod debug;
+mod diff;
+
+pub use self::{
+    debug::{DebugAttributeValue, DebugItem, DebugList},
+    diff::{Difference, ItemDiff},
+};
+
+use core::fmt::{self, Write};
+use std::{borrow::Borrow, collections::HashMap};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::{indent::Indented, Map, Value};
+
+/// Builds a DynamoDB item from a [`Map`], or anything that converts into one.
+///
+/// ```
+/// use aws_sdk_dynamodb::types::AttributeValue;
+/// use dynamodb_expression::{testing::item, Num, Value};
+/// # use pretty_assertions::assert_eq;
+/// use std::collections::HashMap;
+///
+/// assert_eq!(
+///     HashMap::from([
+///         ("name".to_string(), AttributeValue::S("Jane".into())),
+///         ("age".to_string(), AttributeValue::N("21".into())),
+///     ]),
+///     item([("name", Value::from("Jane")), ("age", Num::new(21).into())]),
+/// );
+/// ```
+pub fn item<T>(map: T) -> HashMap<String, AttributeValue>
+where
+    T: Into<Map>,
+{
+    map.into()
+        .map
+        .into_iter()
+        .map(|(name, value)| (name.name, value.into_attribute_value()))
+        .collect()
+}
+
+/// Builds DynamoDB items from [`Map`]s, or anything that converts into them.
+///
+/// See also: [`item`]
+pub fn items<I, T>(maps: I) -> Vec<HashMap<String, AttributeValue>>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<Map>,
+{
+    maps.into_iter().map(item).collect()
+}
+
+/// Builds a DynamoDB `AttributeValue` from a [`Value`], or anything that
+/// converts into one.
+///
+/// ```
+/// use aws_sdk_dynamodb::types::AttributeValue;
+/// use dynamodb_expression::{testing::attribute_value, value::NumSet};
+/// # use pretty_assertions::assert_eq;
+///
+/// assert_eq!(
+///     AttributeValue::Ns(vec!["1".into(), "2".into()]),
+///     attribute_value(NumSet::from([1, 2])),
+/// );
+/// ```
+pub fn attribute_value<T>(value: T) -> AttributeValue
+where
+    T: Into<Value>,
+{
+    value.into().into_attribute_value()
+}
+
+/// Asserts that two DynamoDB items are equal, ignoring the order of values in
+/// sets. On failure, the panic message lists the attributes that differ, as
+/// an [`ItemDiff`].
+///
+/// Either side can be an owned or borrowed `HashMap<String, AttributeValue>`.
+/// Like [`assert_eq!`], a custom message can follow.
+///
+/// ```should_panic
+/// use dynamodb_expression::{assert_item_eq, testing::item, Num, Value};
+///
+/// let expected = item([("name", Value::from("Jane")), ("age", Num::new(21).into())]);
+/// let actual = item([("name", Value::from("Jane")), ("age", Num::new(22).into())]);
+///
+/// // Panics with:
+/// // assertion `expected == actual` failed: wrong age
+/// //   ~ age: 21 -> 22
+/// assert_item_eq!(expected, actual, "wrong age");
+/// ```
+///
+/// [`ItemDiff`]: crate::testing::ItemDiff
+#[macro_export]
+macro_rules! assert_item_eq {
+    ($expected:expr, $actual:expr $(,)?) => {
+        $crate::testing::assert_item_eq_impl(&$expected, &$actual, ::core::option::Option::None)
+    };
+    ($expected:expr, $actual:expr, $($arg:tt)+) => {
+        $crate::testing::assert_item_eq_impl(
+            &$expected,
+            &$actual,
+            ::core::option::Option::Some(::core::format_args!($($arg)+)),
+        )
+    };
+}
+
+/// Asserts that two lists of DynamoDB items are equal, ignoring the order of
+/// values in sets. The items themselves must be in the same order. On
+/// failure, the panic message lists the attributes that differ, for each item
+/// that differs.
+///
+/// Either side can be anything that iterates over owned or borrowed
+/// `HashMap<String, AttributeValue>`s. Like [`assert_eq!`], a custom message
+/// can follow.
+///
+/// ```
+/// use aws_sdk_dynamodb::types::AttributeValue;
+/// use dynamodb_expression::{assert_items_eq, testing::items, Value};
+/// use std::collections::HashMap;
+///
+/// let got = vec![
+///     HashMap::from([("id".to_string(), AttributeValue::S("a".into()))]),
+///     HashMap::from([("id".to_string(), AttributeValue::S("b".into()))]),
+/// ];
+///
+/// assert_items_eq!(items([[("id", Value::from("a"))], [("id", "b".into())]]), &got);
+/// ```
+#[macro_export]
+macro_rules! assert_items_eq {
+    ($expected:expr, $actual:expr $(,)?) => {
+        $crate::testing::assert_items_eq_impl($expected, $actual, ::core::option::Option::None)
+    };
+    ($expected:expr, $actual:expr, $($arg:tt)+) => {
+        $crate::testing::assert_items_eq_impl(
+            $expected,
+            $actual,
+            ::core::option::Option::Some(::core::format_args!($($arg)+)),
+        )
+    };
+}
+
+/// Used by [`assert_item_eq!`](crate::assert_item_eq).
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_item_eq_impl<E, A>(expected: &E, actual: &A, message: Option<fmt::Arguments<'_>>)
+where
+    E: Borrow<HashMap<String, AttributeValue>> + ?Sized,
+    A: Borrow<HashMap<String, AttributeValue>> + ?Sized,
+{
+    let diff = ItemDiff::new(expected.borrow(), actual.borrow());
+    if diff.is_empty() {
+        return;
+    }
+
+    let mut failure = failure(message);
+    write!(Indented::new(&mut failure), "\n{diff}").unwrap();
+
+    panic!("{failure}");
+}
+
+/// Used by [`assert_items_eq!`](crate::assert_items_eq).
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_items_eq_impl<E, A, T, U>(expected: E, actual: A, message: Option<fmt::Arguments<'_>>)
+where
+    E: IntoIterator<Item = T>,
+    A: IntoIterator<Item = U>,
+    T: Borrow<HashMap<String, AttributeValue>>,
+    U: Borrow<HashMap<String, AttributeValue>>,
+{
+    let expected: Vec<_> = expected.into_iter().collect();
+    let actual: Vec<_> = actual.into_iter().collect();
+
+    let empty = HashMap::new();
+    let diffs: Vec<_> = (0..expected.len().max(actual.len()))
+        .map(|index| {
+            let diff = ItemDiff::new(
+                expected.get(index).map_or(&empty, Borrow::borrow),
+                actual.get(index).map_or(&empty, Borrow::borrow),
+            );
+
+            (index, diff)
+        })
+        .filter(|(_, diff)| !diff.is_empty())
+        .collect();
+    if diffs.is_empty() && expected.len() == actual.len() {
+        return;
+    }
+
+    let mut failure = failure(message);
+    {
+        let mut f = Indented::new(&mut failure);
+        if expected.len() != actual.len() {
+            write!(
+                f,
+                "\nexpected {} items, got {}",
+                expected.len(),
+                actual.len()
+            )
+            .unwrap();
+        }
+
+        for (index, diff) in diffs {
+            write!(f, "\nitem {index}:").unwrap();
+            write!(Indented::new(&mut f), "\n{diff}").unwrap();
+        }
+    }
+
+    panic!("{failure}");
+}
+
+/// The first line of a failed assertion's panic message.
+fn failure(message: Option<fmt::Arguments<'_>>) -> String {
+    match message {
+        Some(message) => format!("assertion `expected == actual` failed: {message}"),
+        None => "assertion `expected == actual` failed".into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::panic::catch_unwind;
+
+    use pretty_assertions::assert_eq;
+
+    use super::item;
+    use crate::{Num, Value};
+
+    #[test]
+    fn assertions() {
+        let jane = item([("name", Value::from("Jane")), ("age", Num::new(21).into())]);
+        let older = item([("name", Value::from("Jane")), ("age", Num::new(22).into())]);
+
+        assert_item_eq!(jane, jane.clone());
+        assert_items_eq!([&jane, &older], vec![jane.clone(), older.clone()]);
+
+        let message = |result: std::thread::Result<()>| {
+            *result
+                .unwrap_err()
+                .downcast::<String>()
+                .expect("panics with a formatted message")
+        };
+
+        assert_eq!(
+            "assertion `expected == actual` failed: jane is 21\n  \
+            ~ age: 21 -> 22",
+            message(catch_unwind(|| assert_item_eq!(
+                jane,
+                &older,
+                "jane is {}",
+                21
+            )))
+        );
+
+        assert_eq!(
+            "assertion `expected == actual` failed\n  \
+            expected 1 items, got 2\n  \
+            item 0:\n    \
+            ~ age: 21 -> 22\n  \
+            item 1:\n    \
+            + age: 21\n    \
+            + name: \"Jane\"",
+            message(catch_unwind(|| assert_items_eq!([&jane], [&older, &jane])))
+        );
+    }
+}
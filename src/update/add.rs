@@ -17,6 +17,7 @@ use crate::{
 /// [2]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.UpdateExpressions.html
 /// [`Update`]: crate::update::Update
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Add {
     pub(crate) path: Path,
     pub(crate) value: ValueOrRef,
@@ -37,6 +38,16 @@ impl Add {
             },
         }
     }
+
+    /// The path to the attribute being added to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The number or set being added.
+    pub fn value(&self) -> &ValueOrRef {
+        &self.value
+    }
 }
 
 impl fmt::Display for Add {
@@ -0,0 +1,439 @@
+//! Applying an [`Update`] to an item in memory, the way DynamoDB would.
+
+use core::fmt;
+use std::{collections::BTreeSet, error::Error};
+
+use crate::{
+    condition::{
+        eval::{self, resolve},
+        EvalError,
+    },
+    expression::OverlapError,
+    path::{Element, FieldName, IndexedField, Name, Path},
+    update::{
+        set::math::MathOp, Add, Assign, Delete, IfNotExists, ListAppend, Math, SetAction, Update,
+    },
+    value::{decimal, BinarySet, List, Map, NumSet, Scalar, Set, StringSet, Value},
+};
+
+/// An error applying an [`Update`] to an item in memory.
+///
+/// See: [`Update::apply`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApplyError {
+    /// The update uses a reference to a name or value that can't be resolved.
+    Unresolved(EvalError),
+
+    /// The update writes to overlapping paths.
+    Overlap(OverlapError),
+
+    /// A path can't be written to, such as one whose parent doesn't exist.
+    InvalidPath { path: Path },
+
+    /// A value that's read doesn't exist.
+    MissingAttribute { path: Path },
+
+    /// A value is of the wrong type for the action.
+    WrongType { path: Path },
+}
+
+impl fmt::Display for ApplyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unresolved(err) => err.fmt(f),
+            Self::Overlap(err) => err.fmt(f),
+            Self::InvalidPath { path } => write!(
+                f,
+                "the document path provided in the update expression is invalid for update: {path}"
+            ),
+            Self::MissingAttribute { path } => write!(
+                f,
+                "the provided expression refers to an attribute that does not exist in the item: {path}"
+            ),
+            Self::WrongType { path } => write!(
+                f,
+                "an operand in the update expression has an incorrect data type: {path}"
+            ),
+        }
+    }
+}
+
+impl Error for ApplyError {}
+
+impl From<EvalError> for ApplyError {
+    fn from(err: EvalError) -> Self {
+        Self::Unresolved(err)
+    }
+}
+
+impl From<OverlapError> for ApplyError {
+    fn from(err: OverlapError) -> Self {
+        Self::Overlap(err)
+    }
+}
+
+pub(super) fn apply(update: &Update, item: &mut Map) -> Result<(), ApplyError> {
+    update.check_overlaps()?;
+
+    // Every value is read from the item as it was before the update, and the
+    // item is only replaced if the whole update succeeds.
+    let original = &*item;
+    let mut updated = original.clone();
+
+    let sets = update
+        .set
+        .iter()
+        .flat_map(|set| &set.actions)
+        .map(|action| set_value(original, action))
+        .collect::<Result<Vec<_>, _>>()?;
+    for (path, value) in sets {
+        assign(&mut updated, path, value)?;
+    }
+
+    if let Some(remove) = &update.remove {
+        // Later list elements first, so the indexes refer to the list as it
+        // was.
+        let mut paths = remove.paths.iter().collect::<Vec<_>>();
+        paths.sort_unstable_by(|a, b| b.cmp(a));
+        for path in paths {
+            remove_path(&mut updated, path)?;
+        }
+    }
+
+    for Add { path, value } in &update.add {
+        let wrong_type = || ApplyError::WrongType { path: path.clone() };
+        let value = match (resolve(original, path)?, eval::value(value)?) {
+            (None, value @ (Value::Scalar(Scalar::Num(_)) | Value::Set(_))) => value.clone(),
+            (Some(Value::Scalar(Scalar::Num(a))), Value::Scalar(Scalar::Num(b))) => {
+                decimal::sum(a, b).ok_or_else(wrong_type)?.into()
+            }
+            (Some(Value::Set(a)), Value::Set(b)) => {
+                set_op(a, b, SetOp::Union).ok_or_else(wrong_type)?.into()
+            }
+            _ => return Err(wrong_type()),
+        };
+
+        assign(&mut updated, path, value)?;
+    }
+
+    for Delete { path, subset } in &update.delete {
+        let wrong_type = || ApplyError::WrongType { path: path.clone() };
+        let Value::Set(subset) = eval::value(subset)? else {
+            return Err(wrong_type());
+        };
+
+        match resolve(original, path)? {
+            None => {}
+            Some(Value::Set(set)) => {
+                let remaining = set_op(set, subset, SetOp::Difference).ok_or_else(wrong_type)?;
+                if set_len(&remaining) == 0 {
+                    remove_path(&mut updated, path)?;
+                } else {
+                    assign(&mut updated, path, remaining.into())?;
+                }
+            }
+            Some(_) => return Err(wrong_type()),
+        }
+    }
+
+    *item = updated;
+
+    Ok(())
+}
+
+/// The path a `SET` action writes to, and the value it writes.
+fn set_value<'a>(item: &Map, action: &'a SetAction) -> Result<(&'a Path, Value), ApplyError> {
+    /// The value at the source path, which must exist.
+    fn source<'a>(item: &'a Map, src: &Path) -> Result<&'a Value, ApplyError> {
+        resolve(item, src)?.ok_or_else(|| ApplyError::MissingAttribute { path: src.clone() })
+    }
+
+    Ok(match action {
+        SetAction::Assign(Assign { path, value }) => (path, eval::value(value)?.clone()),
+        SetAction::Math(Math { dst, src, op, num }) => {
+            let src = src.as_ref().unwrap_or(dst);
+            let wrong_type = || ApplyError::WrongType { path: src.clone() };
+            let (Value::Scalar(Scalar::Num(current)), Value::Scalar(Scalar::Num(num))) =
+                (source(item, src)?, eval::value(num)?)
+            else {
+                return Err(wrong_type());
+            };
+
+            let result = match op {
+                MathOp::Add => decimal::sum(current, num),
+                MathOp::Sub => decimal::sub(current, num),
+            };
+
+            (dst, result.ok_or_else(wrong_type)?.into())
+        }
+        SetAction::ListAppend(ListAppend {
+            dst,
+            src,
+            list,
+            after,
+        }) => {
+            let src = src.as_ref().unwrap_or(dst);
+            let (Value::List(current), Value::List(list)) =
+                (source(item, src)?, eval::value(list)?)
+            else {
+                return Err(ApplyError::WrongType { path: src.clone() });
+            };
+
+            let (first, second) = if *after {
+                (current, list)
+            } else {
+                (list, current)
+            };
+
+            (
+                dst,
+                first
+                    .list
+                    .iter()
+                    .chain(&second.list)
+                    .cloned()
+                    .collect::<List>()
+                    .into(),
+            )
+        }
+        SetAction::IfNotExists(IfNotExists { dst, src, value }) => {
+            let src = src.as_ref().unwrap_or(dst);
+            let value = match resolve(item, src)? {
+                Some(existing) => existing.clone(),
+                None => eval::value(value)?.clone(),
+            };
+
+            (dst, value)
+        }
+    })
+}
+
+#[derive(Clone, Copy)]
+enum SetOp {
+    Union,
+    Difference,
+}
+
+/// Combines two sets of the same type. `None` if they're different types.
+fn set_op(a: &Set, b: &Set, op: SetOp) -> Option<Set> {
+    fn combine<T>(a: &BTreeSet<T>, b: &BTreeSet<T>, op: SetOp) -> BTreeSet<T>
+    where
+        T: Ord + Clone,
+    {
+        match op {
+            SetOp::Union => a.union(b).cloned().collect(),
+            SetOp::Difference => a.difference(b).cloned().collect(),
+        }
+    }
+
+    Some(match (a, b) {
+        (Set::StringSet(a), Set::StringSet(b)) => StringSet(combine(&a.0, &b.0, op)).into(),
+        (Set::NumSet(a), Set::NumSet(b)) => NumSet(combine(&a.0, &b.0, op)).into(),
+        (Set::BinarySet(a), Set::BinarySet(b)) => BinarySet(combine(&a.0, &b.0, op)).into(),
+        _ => return None,
+    })
+}
+
+fn set_len(set: &Set) -> usize {
+    match set {
+        Set::StringSet(set) => set.0.len(),
+        Set::NumSet(set) => set.0.len(),
+        Set::BinarySet(set) => set.0.len(),
+    }
+}
+
+/// The name and list indexes of a path element.
+fn name_and_indexes(element: &Element) -> Result<(&Name, &[usize]), ApplyError> {
+    match element {
+        Element::Name(name) => Ok((name, &[])),
+        Element::IndexedField(IndexedField {
+            name: FieldName::Name(name),
+            indexes,
+        }) => Ok((name, indexes)),
+        Element::NameRef(name)
+        | Element::IndexedField(IndexedField {
+            name: FieldName::NameRef(name),
+            ..
+        }) => Err(EvalError::UnresolvedName(name.clone()).into()),
+    }
+}
+
+/// The map that holds the last element of the path, along with that
+/// element's name and list indexes. Every element before it must exist.
+fn parent<'a, 'p>(
+    item: &'a mut Map,
+    path: &'p Path,
+) -> Result<(&'a mut Map, &'p Name, &'p [usize]), ApplyError> {
+    let invalid = || ApplyError::InvalidPath { path: path.clone() };
+    let (last, parents) = path.elements.split_last().ok_or_else(invalid)?;
+
+    let mut map = item;
+    for element in parents {
+        let (name, indexes) = name_and_indexes(element)?;
+        let mut value = map.map.get_mut(name).ok_or_else(invalid)?;
+        for &index in indexes {
+            value = match value {
+                Value::List(list) => list.list.get_mut(index).ok_or_else(invalid)?,
+                _ => return Err(invalid()),
+            };
+        }
+
+        map = match value {
+            Value::Map(map) => map,
+            _ => return Err(invalid()),
+        };
+    }
+
+    let (name, indexes) = name_and_indexes(last)?;
+
+    Ok((map, name, indexes))
+}
+
+/// The list holding the element at the last index, and that index.
+fn parent_list<'a>(
+    map: &'a mut Map,
+    path: &Path,
+    name: &Name,
+    indexes: &[usize],
+) -> Result<Option<(&'a mut Vec<Value>, usize)>, ApplyError> {
+    let invalid = || ApplyError::InvalidPath { path: path.clone() };
+    let Some((&last, indexes)) = indexes.split_last() else {
+        return Ok(None);
+    };
+
+    let mut value = map.map.get_mut(name).ok_or_else(invalid)?;
+    for &index in indexes {
+        value = match value {
+            Value::List(list) => list.list.get_mut(index).ok_or_else(invalid)?,
+            _ => return Err(invalid()),
+        };
+    }
+
+    match value {
+        Value::List(list) => Ok(Some((&mut list.list, last))),
+        _ => Err(invalid()),
+    }
+}
+
+/// Writes the value at the path. Writing past the end of a list appends to
+/// it, as DynamoDB does.
+fn assign(item: &mut Map, path: &Path, value: Value) -> Result<(), ApplyError> {
+    let (map, name, indexes) = parent(item, path)?;
+    match parent_list(map, path, name, indexes)? {
+        None => {
+            map.map.insert(name.clone(), value);
+        }
+        Some((list, index)) => match list.get_mut(index) {
+            Some(element) => *element = value,
+            None => list.push(value),
+        },
+    }
+
+    Ok(())
+}
+
+/// Removes the value at the path, if there is one.
+fn remove_path(item: &mut Map, path: &Path) -> Result<(), ApplyError> {
+    let (map, name, indexes) = parent(item, path)?;
+    match parent_list(map, path, name, indexes)? {
+        None => {
+            map.map.remove(name);
+        }
+        Some((list, index)) => {
+            if index < list.len() {
+                list.remove(index);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        update::Update,
+        value::{Map, NumSet, Set, StringSet, Value},
+        Num, Path,
+    };
+
+    use super::ApplyError;
+
+    #[test]
+    fn apply() {
+        let path = |path: &str| path.parse::<Path>().unwrap();
+        let original = Map::from([
+            ("n", Value::new_num(10)),
+            ("list", Value::new_list(["a", "b", "c"])),
+            ("nums", NumSet::from([1, 2]).into()),
+            ("tags", StringSet::from(["x"]).into()),
+            ("map", Map::from([("inner", Value::new_null())]).into()),
+        ]);
+
+        let mut item = original.clone();
+        Update::from(path("n").math().sub(Num::new_lower_exp(2.5)))
+            .and(path("copy").math().src(path("n")).add(1))
+            .and(path("list[1]").assign("B"))
+            .and(path("list[9]").assign("d"))
+            .and(path("map.new").if_not_exists().assign(Num::new(0)))
+            .and(
+                path("appended")
+                    .list_append()
+                    .src(path("list"))
+                    .before()
+                    .list(["z"]),
+            )
+            .and(path("map.inner").remove())
+            .and(path("list[0]").remove())
+            .and(path("list[2]").remove())
+            .and(path("counter").add(Num::new(1)))
+            .and(path("nums").add(Set::new_num_set([3])))
+            .and(path("tags").delete(StringSet::from(["x"])))
+            .apply(&mut item)
+            .unwrap();
+        assert_eq!(
+            Map::from([
+                ("n", Value::new_num(7.5)),
+                ("copy", Value::new_num(11)),
+                ("list", Value::new_list(["B", "d"])),
+                ("map", Map::from([("new", Value::new_num(0))]).into()),
+                ("appended", Value::new_list(["z", "a", "b", "c"])),
+                ("counter", Value::new_num(1)),
+                ("nums", NumSet::from([1, 2, 3]).into()),
+            ]),
+            item
+        );
+
+        for (update, err) in [
+            (
+                Update::from(path("missing.a").assign("x")),
+                ApplyError::InvalidPath {
+                    path: path("missing.a"),
+                },
+            ),
+            (
+                path("missing").math().add(1).into(),
+                ApplyError::MissingAttribute {
+                    path: path("missing"),
+                },
+            ),
+            (
+                path("tags").add(Num::new(1)).into(),
+                ApplyError::WrongType { path: path("tags") },
+            ),
+        ] {
+            let mut item = original.clone();
+            assert_eq!(Err(err), update.apply(&mut item));
+            assert_eq!(original, item, "unchanged on error");
+        }
+
+        assert!(matches!(
+            Update::from(path("map").remove())
+                .and(path("map.inner").remove())
+                .apply(&mut original.clone()),
+            Err(ApplyError::Overlap(_))
+        ));
+    }
+}
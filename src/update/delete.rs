@@ -14,6 +14,7 @@ use crate::{
 /// [2]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.SetTypes
 /// [`Update`]: crate::update::Update
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Delete {
     pub(crate) path: Path,
     pub(crate) subset: ValueOrRef,
@@ -35,6 +36,16 @@ impl Delete {
             subset: subset.into().into(),
         }
     }
+
+    /// The path to the set being deleted from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The values being deleted from the set.
+    pub fn subset(&self) -> &ValueOrRef {
+        &self.subset
+    }
 }
 
 impl fmt::Display for Delete {
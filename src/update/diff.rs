@@ -0,0 +1,351 @@
+//! Generating an [`Update`] from the differences between two versions of an
+//! item. See [`Diff`].
+
+use core::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::{
+    path::{Name, Path},
+    update::Update,
+    value::{
+        decimal::difference, BinarySet, Map, NumSet, Scalar, Set, StringSet,
+        UnknownAttributeValueError, Value,
+    },
+};
+
+/// Generates the [`Update`] that turns one version of an item into another,
+/// touching only what changed.
+///
+/// By default, changed and new attributes are assigned with `SET`, and
+/// attributes that are gone are removed with `REMOVE`. Nested maps are compared
+/// attribute by attribute, so only the changed document paths are updated.
+/// Lists are compared and assigned as a whole.
+///
+/// Key attributes (see [`Diff::with_keys`]) are never included, since
+/// DynamoDB doesn't allow updating them.
+///
+/// ```
+/// use dynamodb_expression::{
+///     update::Diff,
+///     value::{Map, Value},
+///     Num,
+/// };
+/// # use pretty_assertions::assert_eq;
+///
+/// let old = Map::from([
+///     ("id", Value::new_string("abc")),
+///     ("name", Value::new_string("Jane")),
+///     ("visits", Value::new_num(3)),
+///     ("nickname", Value::new_string("J")),
+///     (
+///         "address",
+///         Map::from([
+///             ("city", Value::new_string("Portland")),
+///             ("zip", Value::new_string("97201")),
+///         ])
+///         .into(),
+///     ),
+/// ]);
+/// let new = Map::from([
+///     ("id", Value::new_string("xyz")),
+///     ("name", Value::new_string("Jane")),
+///     ("visits", Value::new_num(4)),
+///     (
+///         "address",
+///         Map::from([
+///             ("city", Value::new_string("Seattle")),
+///             ("zip", Value::new_string("97201")),
+///         ])
+///         .into(),
+///     ),
+/// ]);
+///
+/// let update = Diff::new().with_keys(["id"]).diff(&old, &new).unwrap();
+/// assert_eq!(
+///     r#"SET address.city = "Seattle", visits = 4 REMOVE nickname"#,
+///     update.to_string()
+/// );
+///
+/// let update = Diff::new()
+///     .with_keys(["id"])
+///     .with_math()
+///     .diff(&old, &new)
+///     .unwrap();
+/// assert_eq!(
+///     r#"SET address.city = "Seattle", visits = visits + 1 REMOVE nickname"#,
+///     update.to_string()
+/// );
+///
+/// assert_eq!(None, Diff::new().diff(&old, &old));
+/// ```
+#[must_use = "Use `.diff()` to generate the `Update`"]
+#[derive(Debug, Default, Clone)]
+pub struct Diff {
+    keys: BTreeSet<Name>,
+    set_actions: bool,
+    math: bool,
+}
+
+impl Diff {
+    /// A [`Diff`] with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The key attributes of the item, which are left out of the update.
+    pub fn with_keys<I, T>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Name>,
+    {
+        self.keys.extend(keys.into_iter().map(Into::into));
+
+        self
+    }
+
+    /// Use `ADD` and `DELETE` for the values added to and removed from a
+    /// [set][1], rather than assigning the whole set.
+    ///
+    /// DynamoDB doesn't allow both in the same update for the same attribute,
+    /// so a set that had values both added and removed is still assigned.
+    ///
+    /// ```
+    /// use dynamodb_expression::{
+    ///     update::Diff,
+    ///     value::{Map, StringSet},
+    /// };
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let old = Map::from([("tags", StringSet::from(["a", "b"]))]);
+    /// let new = Map::from([("tags", StringSet::from(["a", "b", "c"]))]);
+    ///
+    /// let update = Diff::new().with_set_actions().diff(&old, &new).unwrap();
+    /// assert_eq!(r#"ADD tags ["c"]"#, update.to_string());
+    ///
+    /// let update = Diff::new().with_set_actions().diff(&new, &old).unwrap();
+    /// assert_eq!(r#"DELETE tags ["c"]"#, update.to_string());
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.SetTypes
+    pub fn with_set_actions(mut self) -> Self {
+        self.set_actions = true;
+
+        self
+    }
+
+    /// Use a [math operation][1] for numbers that changed, rather than
+    /// assigning the new number. E.g., `SET a = a + 2` rather than `SET a = 5`.
+    ///
+    /// [1]: crate::update::Math
+    pub fn with_math(mut self) -> Self {
+        self.math = true;
+
+        self
+    }
+
+    /// The [`Update`] that turns `old` into `new`, or `None` if nothing
+    /// changed.
+    pub fn diff(&self, old: &Map, new: &Map) -> Option<Update> {
+        let mut update = None;
+        let keys = old
+            .map
+            .keys()
+            .chain(new.map.keys())
+            .filter(|name| !self.keys.contains(*name))
+            .collect::<BTreeSet<_>>();
+        for name in keys {
+            self.diff_value(
+                &mut update,
+                Path::from(name.clone()),
+                old.map.get(name),
+                new.map.get(name),
+            );
+        }
+
+        update
+    }
+
+    /// The same as [`Diff::diff`], for items from the [`aws_sdk_dynamodb`].
+    pub fn diff_items(
+        &self,
+        old: &HashMap<String, AttributeValue>,
+        new: &HashMap<String, AttributeValue>,
+    ) -> Result<Option<Update>, UnknownAttributeValueError> {
+        let into_map = |item: &HashMap<String, AttributeValue>| {
+            item.iter()
+                .map(|(name, value)| Value::try_from(value.clone()).map(|value| (name, value)))
+                .collect::<Result<Map, _>>()
+        };
+
+        Ok(self.diff(&into_map(old)?, &into_map(new)?))
+    }
+
+    fn diff_value(
+        &self,
+        update: &mut Option<Update>,
+        path: Path,
+        old: Option<&Value>,
+        new: Option<&Value>,
+    ) {
+        let action: Update = match (old, new) {
+            (Some(old), Some(new)) if old == new => return,
+            (None, None) => return,
+            (Some(_), None) => path.remove().into(),
+            (None, Some(new)) => path.assign(new.clone()).into(),
+            (Some(Value::Map(old)), Some(Value::Map(new))) => {
+                let names = old
+                    .map
+                    .keys()
+                    .chain(new.map.keys())
+                    .collect::<BTreeSet<_>>();
+                for name in names {
+                    let mut path = path.clone();
+                    path.append(name.clone().into());
+                    self.diff_value(update, path, old.map.get(name), new.map.get(name));
+                }
+
+                return;
+            }
+            (
+                Some(Value::Scalar(Scalar::Num(old_num))),
+                Some(new @ Value::Scalar(Scalar::Num(new_num))),
+            ) if self.math => match difference(new_num, old_num) {
+                Some((Ordering::Equal, _)) => return,
+                Some((Ordering::Greater, delta)) => path.math().add(delta).into(),
+                Some((Ordering::Less, delta)) => path.math().sub(delta).into(),
+                None => path.assign(new.clone()).into(),
+            },
+            (Some(Value::Set(old_set)), Some(new @ Value::Set(new_set))) if self.set_actions => {
+                match set_changes(old_set, new_set) {
+                    Some((Some(added), None)) => path.add(added).into(),
+                    Some((None, Some(removed))) => path.delete(removed).into(),
+                    _ => path.assign(new.clone()).into(),
+                }
+            }
+            (Some(_), Some(new)) => path.assign(new.clone()).into(),
+        };
+
+        *update = Some(match update.take() {
+            Some(update) => update.and(action),
+            None => action,
+        });
+    }
+}
+
+/// The values added to and removed from a set, if they're the same kind of
+/// set.
+fn set_changes(old: &Set, new: &Set) -> Option<(Option<Set>, Option<Set>)> {
+    fn changes<T, S>(old: &BTreeSet<T>, new: &BTreeSet<T>) -> (Option<Set>, Option<Set>)
+    where
+        T: Ord + Clone,
+        S: FromIterator<T> + Into<Set>,
+    {
+        let added = new.difference(old).cloned().collect::<Vec<_>>();
+        let removed = old.difference(new).cloned().collect::<Vec<_>>();
+        let into_set = |values: Vec<T>| (!values.is_empty()).then(|| S::from_iter(values).into());
+
+        (into_set(added), into_set(removed))
+    }
+
+    Some(match (old, new) {
+        (Set::StringSet(old), Set::StringSet(new)) => changes::<_, StringSet>(&old.0, &new.0),
+        (Set::NumSet(old), Set::NumSet(new)) => changes::<_, NumSet>(&old.0, &new.0),
+        (Set::BinarySet(old), Set::BinarySet(new)) => changes::<_, BinarySet>(&old.0, &new.0),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use pretty_assertions::assert_eq;
+
+    use crate::value::{List, Map, NumSet, StringSet, Value};
+
+    use super::Diff;
+
+    #[test]
+    fn diff() {
+        let old = Map::from([
+            ("pk", Value::new_string("a")),
+            ("list", List::from([Value::new_num(1)]).into()),
+            ("nums", NumSet::from([1, 2]).into()),
+            ("tags", StringSet::from(["x", "y"]).into()),
+            (
+                "nested",
+                Map::from([
+                    ("deep", Map::from([("gone", Value::new_bool(true))]).into()),
+                    ("same", Value::new_null()),
+                    ("changed", Value::new_num(1)),
+                ])
+                .into(),
+            ),
+        ]);
+        let new = Map::from([
+            ("pk", Value::new_string("b")),
+            (
+                "list",
+                List::from([Value::new_num(1), Value::new_num(2)]).into(),
+            ),
+            ("nums", NumSet::from([2, 3]).into()),
+            ("tags", StringSet::from(["x"]).into()),
+            (
+                "nested",
+                Map::from([
+                    ("deep", Map::from([("new", Value::new_bool(false))]).into()),
+                    ("same", Value::new_null()),
+                    ("changed", Value::new_string("one")),
+                ])
+                .into(),
+            ),
+        ]);
+
+        assert_eq!(
+            "SET list = [1, 2], nested.changed = \"one\", nested.deep.new = false, \
+            nums = [2, 3], pk = \"b\", tags = [\"x\"] \
+            REMOVE nested.deep.gone",
+            Diff::new().diff(&old, &new).unwrap().to_string()
+        );
+
+        let update = Diff::new()
+            .with_keys(["pk"])
+            .with_set_actions()
+            .diff(&old, &new)
+            .unwrap();
+        assert_eq!(
+            "SET list = [1, 2], nested.changed = \"one\", nested.deep.new = false, \
+            nums = [2, 3] \
+            REMOVE nested.deep.gone \
+            DELETE tags [\"y\"]",
+            update.to_string()
+        );
+        assert_eq!(Ok(()), update.check_overlaps());
+    }
+
+    #[test]
+    fn diff_items() {
+        let old = HashMap::from([
+            ("id".to_string(), AttributeValue::S("1".into())),
+            ("count".to_string(), AttributeValue::N("7.5".into())),
+        ]);
+        let new = HashMap::from([
+            ("id".to_string(), AttributeValue::S("1".into())),
+            ("count".to_string(), AttributeValue::N("2".into())),
+        ]);
+
+        assert_eq!(
+            "SET count = count - 5.5",
+            Diff::new()
+                .with_keys(["id"])
+                .with_math()
+                .diff_items(&old, &new)
+                .unwrap()
+                .unwrap()
+                .to_string()
+        );
+    }
+}
@@ -3,27 +3,54 @@
 //! [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.UpdateExpressions.html
 
 pub mod add;
+mod apply;
 pub mod delete;
+pub mod diff;
 pub mod remove;
 pub mod set;
 
-use core::fmt;
+use core::fmt::{self, Write};
+
+use crate::{
+    expression::{Dependencies, OverlapError},
+    indent::INDENT,
+    path::Path,
+    value::{Map, ValueOrRef},
+};
 
 pub use self::{
     add::Add,
+    apply::ApplyError,
     delete::Delete,
+    diff::Diff,
     remove::Remove,
-    set::{Assign, IfNotExists, ListAppend, Math, Set, SetAction},
+    set::{Assign, IfNotExists, ListAppend, Math, Set, SetAction, Upsert},
 };
 
 /// Represents a [DynamoDB update expression][1].
 ///
+/// An update expression can have one of each of the `SET`, `REMOVE`, `ADD`,
+/// and `DELETE` clauses. Use [`Update::and`] to combine them.
+///
+/// # Migrating from `0.2.0-beta.5`
+///
+/// `Update` used to be an `enum` with a variant for each clause, so it could
+/// only hold one of them. It's now a `struct`, which is a breaking change:
+///
+/// * In place of the variants, build one with [`Update::from`] or
+///   [`Update::new_set`], etc. The deprecated [`Update::Set`], [`Update::Remove`],
+///   [`Update::Add`], and [`Update::Delete`] functions take the place of the
+///   variants' constructors, so existing calls keep compiling.
+/// * In place of matching on the variants, use [`Update::set`],
+///   [`Update::remove`], [`Update::add`], and [`Update::delete`] to get each
+///   clause.
+///
 /// # Examples
 ///
 /// ```
 /// use dynamodb_expression::{
 ///     update::{Remove, Update},
-///     Path,
+///     Num, Path,
 /// };
 /// # use pretty_assertions::assert_eq;
 ///
@@ -46,15 +73,43 @@ pub use self::{
 ///     ["foo", "bar", "baz"].into_iter().map(Path::new_name),
 /// ));
 /// assert_eq!(r#"REMOVE foo, bar, baz"#, update.to_string());
+///
+/// let update = Update::from(Path::new_name("foo").assign("a value"))
+///     .and(Path::new_name("bar").remove())
+///     .and(Path::new_name("baz").add(Num::new(1)));
+/// assert_eq!(
+///     r#"SET foo = "a value" REMOVE bar ADD baz 1"#,
+///     update.to_string()
+/// );
 /// ```
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.UpdateExpressions.html
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Update {
-    Set(Set),
-    Remove(Remove),
-    Add(Add),
-    Delete(Delete),
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Update {
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub(crate) set: Option<Set>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub(crate) remove: Option<Remove>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub(crate) add: Vec<Add>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub(crate) delete: Vec<Delete>,
 }
 
 impl Update {
@@ -89,70 +144,330 @@ impl Update {
     {
         delete.into().into()
     }
+
+    /// An update expression for a [`Set`] statement. In place of the
+    /// `Update::Set` variant, from when `Update` was an `enum`.
+    #[deprecated(note = "use `Update::new_set` or `Update::from`")]
+    #[allow(non_snake_case)]
+    pub fn Set<T>(set: T) -> Self
+    where
+        T: Into<Set>,
+    {
+        Self::new_set(set)
+    }
+
+    /// An update expression for a [`Remove`] statement. In place of the
+    /// `Update::Remove` variant, from when `Update` was an `enum`.
+    #[deprecated(note = "use `Update::new_remove` or `Update::from`")]
+    #[allow(non_snake_case)]
+    pub fn Remove<T>(remove: T) -> Self
+    where
+        T: Into<Remove>,
+    {
+        Self::new_remove(remove)
+    }
+
+    /// An update expression for an [`Add`] statement. In place of the
+    /// `Update::Add` variant, from when `Update` was an `enum`.
+    #[deprecated(note = "use `Update::new_add` or `Update::from`")]
+    #[allow(non_snake_case)]
+    pub fn Add<T>(add: T) -> Self
+    where
+        T: Into<Add>,
+    {
+        Self::new_add(add)
+    }
+
+    /// An update expression for a [`Delete`] statement. In place of the
+    /// `Update::Delete` variant, from when `Update` was an `enum`.
+    #[deprecated(note = "use `Update::new_delete` or `Update::from`")]
+    #[allow(non_snake_case)]
+    pub fn Delete<T>(delete: T) -> Self
+    where
+        T: Into<Delete>,
+    {
+        Self::new_delete(delete)
+    }
+
+    /// Combines this update expression with another. Actions for the same
+    /// clause are put together, in order.
+    ///
+    /// ```
+    /// use dynamodb_expression::{update::Update, value::StringSet, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let update = Update::from(Path::new_name("a").remove())
+    ///     .and(Path::new_name("b").delete(StringSet::from(["x"])))
+    ///     .and(Path::new_name("c").assign(Num::new(1)))
+    ///     .and(Path::new_name("d").remove())
+    ///     .and(Path::new_name("e").math().sub(2));
+    /// assert_eq!(
+    ///     r#"SET c = 1, e = e - 2 REMOVE a, d DELETE b ["x"]"#,
+    ///     update.to_string()
+    /// );
+    /// ```
+    pub fn and<T>(mut self, other: T) -> Self
+    where
+        T: Into<Update>,
+    {
+        let Self {
+            set,
+            remove,
+            mut add,
+            mut delete,
+        } = other.into();
+
+        self.set = match (self.set, set) {
+            (Some(left), Some(right)) => Some(left.and(right)),
+            (left, right) => left.or(right),
+        };
+        self.remove = match (self.remove, remove) {
+            (Some(mut left), Some(mut right)) => {
+                left.paths.append(&mut right.paths);
+                Some(left)
+            }
+            (left, right) => left.or(right),
+        };
+        self.add.append(&mut add);
+        self.delete.append(&mut delete);
+
+        self
+    }
+
+    /// Checks that no two actions write to [overlapping paths][1], which
+    /// DynamoDB rejects. For example, `SET a.b = 1 REMOVE a`, or `a` appearing
+    /// in both the `SET` and `ADD` clauses.
+    ///
+    /// Paths that use an expression attribute name (e.g., `#a`) are only
+    /// compared to each other. Use [`Builder::check_overlaps`] to check them
+    /// after they've been resolved.
+    ///
+    /// ```
+    /// use dynamodb_expression::{update::Update, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let update = Update::from("a.b".parse::<Path>().unwrap().assign("x"))
+    ///     .and(Path::new_name("a").remove());
+    /// let error = update.check_overlaps().unwrap_err();
+    /// assert_eq!("two document paths overlap: `a` and `a.b`", error.to_string());
+    ///
+    /// let update = Update::from(Path::new_name("a").remove())
+    ///     .and(Path::new_name("b").add(Num::new(1)));
+    /// assert_eq!(Ok(()), update.check_overlaps());
+    /// ```
+    ///
+    /// [1]: crate::expression::Dependencies::overlaps
+    /// [`Builder::check_overlaps`]: crate::expression::Builder::check_overlaps
+    pub fn check_overlaps(&self) -> Result<(), OverlapError> {
+        Dependencies::from(self).check_overlaps()
+    }
+
+    /// Applies the update to an item in memory, the way DynamoDB would.
+    ///
+    /// Every value the update reads comes from the item as it was before the
+    /// update. If the update fails, the item is left as it was. It fails if
+    /// the update uses a [`NameRef`] or [`Ref`], since those can only be
+    /// resolved by DynamoDB.
+    ///
+    /// ```
+    /// use dynamodb_expression::{
+    ///     update::Update,
+    ///     value::{Map, Set, StringSet, Value},
+    ///     Num, Path,
+    /// };
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let mut item = Map::from([
+    ///     ("count", Value::new_num(1)),
+    ///     ("tags", StringSet::from(["a"]).into()),
+    ///     ("old", Value::new_bool(true)),
+    /// ]);
+    ///
+    /// let update = Update::from(Path::new_name("count").math().add(2))
+    ///     .and(Path::new_name("tags").add(Set::new_string_set(["b"])))
+    ///     .and(Path::new_name("old").remove());
+    /// update.apply(&mut item).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Map::from([
+    ///         ("count", Value::new_num(3)),
+    ///         ("tags", StringSet::from(["a", "b"]).into()),
+    ///     ]),
+    ///     item
+    /// );
+    /// ```
+    ///
+    /// [`NameRef`]: crate::path::NameRef
+    /// [`Ref`]: crate::value::Ref
+    pub fn apply(&self, item: &mut Map) -> Result<(), ApplyError> {
+        apply::apply(self, item)
+    }
+
+    /// The `SET` clause, if there is one.
+    pub fn set(&self) -> Option<&Set> {
+        self.set.as_ref()
+    }
+
+    /// The `REMOVE` clause, if there is one.
+    pub fn remove(&self) -> Option<&Remove> {
+        self.remove.as_ref()
+    }
+
+    /// The actions of the `ADD` clause. Empty if there isn't one.
+    pub fn add(&self) -> &[Add] {
+        &self.add
+    }
+
+    /// The actions of the `DELETE` clause. Empty if there isn't one.
+    pub fn delete(&self) -> &[Delete] {
+        &self.delete
+    }
 }
 
 impl fmt::Display for Update {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Update::Set(update) => update.fmt(f),
-            Update::Remove(update) => update.fmt(f),
-            Update::Add(update) => update.fmt(f),
-            Update::Delete(update) => update.fmt(f),
+        let mut first = true;
+        let mut separate = |f: &mut fmt::Formatter<'_>| {
+            if first {
+                first = false;
+                Ok(())
+            } else if f.alternate() {
+                f.write_char('\n')
+            } else {
+                f.write_char(' ')
+            }
+        };
+
+        if let Some(set) = &self.set {
+            separate(f)?;
+            set.fmt(f)?;
+        }
+
+        if let Some(remove) = &self.remove {
+            separate(f)?;
+            remove.fmt(f)?;
         }
+
+        if !self.add.is_empty() {
+            separate(f)?;
+            f.write_str("ADD ")?;
+            fmt_actions(f, self.add.iter().map(|add| (&add.path, &add.value)))?;
+        }
+
+        if !self.delete.is_empty() {
+            separate(f)?;
+            f.write_str("DELETE ")?;
+            fmt_actions(
+                f,
+                self.delete
+                    .iter()
+                    .map(|delete| (&delete.path, &delete.subset)),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the `path value` pairs of an `ADD` or `DELETE` clause.
+fn fmt_actions<'a, I>(f: &mut fmt::Formatter<'_>, actions: I) -> fmt::Result
+where
+    I: Iterator<Item = (&'a Path, &'a ValueOrRef)>,
+{
+    let mut first = true;
+    actions.into_iter().try_for_each(|(path, value)| {
+        if first {
+            first = false;
+        } else {
+            write_separator(f)?;
+        }
+
+        write!(f, "{path} {value}")
+    })
+}
+
+/// Writes the separator between actions of the same clause, which are on
+/// their own lines in the alternate (`{:#}`) form.
+fn write_separator(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if f.alternate() {
+        f.write_str(",\n")?;
+        f.write_str(INDENT)
+    } else {
+        f.write_str(", ")
     }
 }
 
 impl From<Set> for Update {
-    fn from(value: Set) -> Self {
-        Self::Set(value)
+    fn from(set: Set) -> Self {
+        Self {
+            set: Some(set),
+            remove: None,
+            add: Vec::new(),
+            delete: Vec::new(),
+        }
     }
 }
 
 impl From<SetAction> for Update {
     fn from(value: SetAction) -> Self {
-        Self::Set(value.into())
+        Set::from(value).into()
     }
 }
 
 impl From<Assign> for Update {
     fn from(value: Assign) -> Self {
-        Self::Set(value.into())
+        Set::from(value).into()
     }
 }
 
 impl From<Math> for Update {
     fn from(value: Math) -> Self {
-        Self::Set(value.into())
+        Set::from(value).into()
     }
 }
 
 impl From<ListAppend> for Update {
     fn from(value: ListAppend) -> Self {
-        Self::Set(value.into())
+        Set::from(value).into()
     }
 }
 
 impl From<IfNotExists> for Update {
     fn from(value: IfNotExists) -> Self {
-        Self::Set(value.into())
+        Set::from(value).into()
     }
 }
 
 impl From<Remove> for Update {
-    fn from(value: Remove) -> Self {
-        Self::Remove(value)
+    fn from(remove: Remove) -> Self {
+        Self {
+            set: None,
+            remove: Some(remove),
+            add: Vec::new(),
+            delete: Vec::new(),
+        }
     }
 }
 
 impl From<Add> for Update {
-    fn from(value: Add) -> Self {
-        Self::Add(value)
+    fn from(add: Add) -> Self {
+        Self {
+            set: None,
+            remove: None,
+            add: vec![add],
+            delete: Vec::new(),
+        }
     }
 }
 
 impl From<Delete> for Update {
-    fn from(value: Delete) -> Self {
-        Self::Delete(value)
+    fn from(delete: Delete) -> Self {
+        Self {
+            set: None,
+            remove: None,
+            add: Vec::new(),
+            delete: vec![delete],
+        }
     }
 }
 
@@ -187,4 +502,114 @@ mod test {
         ));
         assert_eq!(r#"REMOVE foo, bar, baz"#, update.to_string());
     }
+
+    #[test]
+    fn and() {
+        use crate::{
+            update::{Remove, Update},
+            value::NumSet,
+            Num, Path,
+        };
+        use pretty_assertions::assert_eq;
+
+        let update = Update::from(Path::new_name("a").delete(NumSet::from([1])))
+            .and(Path::new_name("b").add(Num::new(2)))
+            .and(Path::new_name("c").remove())
+            .and(Path::new_name("d").delete(NumSet::from([3])))
+            .and(Update::from(Path::new_name("e").assign("x")).and(Remove::new_name("f")))
+            .and(Path::new_name("g").add(Num::new(4)));
+        assert_eq!(
+            r#"SET e = "x" REMOVE c, f ADD b 2, g 4 DELETE a [1], d [3]"#,
+            update.to_string()
+        );
+        assert_eq!(
+            "SET e = \"x\"\n\
+            REMOVE c,\n  f\n\
+            ADD b 2,\n  g 4\n\
+            DELETE a [1],\n  d [3]",
+            format!("{update:#}")
+        );
+        assert_eq!(2, update.add().len());
+        assert_eq!(2, update.delete().len());
+        assert_eq!(
+            vec!["c", "f"],
+            update
+                .remove()
+                .map(|remove| remove.paths().iter().map(ToString::to_string).collect())
+                .unwrap_or_else(Vec::new)
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn enum_constructors() {
+        use crate::{
+            update::{Remove, Update},
+            value::NumSet,
+            Num, Path,
+        };
+        use pretty_assertions::assert_eq;
+
+        assert_eq!(
+            Update::from(Path::new_name("a").assign("x")),
+            Update::Set(Path::new_name("a").assign("x"))
+        );
+        assert_eq!(
+            Update::from(Remove::new_name("a")),
+            Update::Remove(Remove::new_name("a"))
+        );
+        assert_eq!(
+            Update::from(Path::new_name("a").add(Num::new(1))),
+            Update::Add(Path::new_name("a").add(Num::new(1)))
+        );
+        assert_eq!(
+            Update::from(Path::new_name("a").delete(NumSet::from([1]))),
+            Update::Delete(Path::new_name("a").delete(NumSet::from([1])))
+        );
+    }
+
+    #[test]
+    fn check_overlaps() {
+        use crate::{expression::OverlapKind, update::Update, Num, Path};
+        use pretty_assertions::assert_eq;
+
+        let update = Update::from(Path::new_name("a").assign("x"))
+            .and(Path::new_name("b").assign("y"))
+            .and("c.d".parse::<Path>().unwrap().remove())
+            .and(Path::new_name("e").add(Num::new(1)));
+        assert_eq!(Ok(()), update.check_overlaps());
+
+        // The same attribute in two clauses.
+        let error = update
+            .clone()
+            .and(Path::new_name("a").add(Num::new(1)))
+            .check_overlaps()
+            .unwrap_err();
+        assert_eq!("two document paths overlap: `a` and `a`", error.to_string());
+
+        // The same attribute twice in one clause, and a conflicting path.
+        let error = update
+            .and(Path::new_name("b").assign("z"))
+            .and("c[0]".parse::<Path>().unwrap().assign("z"))
+            .check_overlaps()
+            .unwrap_err();
+        let overlaps = error
+            .overlaps()
+            .iter()
+            .map(|overlap| {
+                (
+                    overlap.first().to_string(),
+                    overlap.second().to_string(),
+                    overlap.kind(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("b".into(), "b".into(), OverlapKind::Overlap),
+                ("c.d".into(), "c[0]".into(), OverlapKind::Conflict),
+            ],
+            overlaps
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use core::fmt;
 
-use crate::path::{Indexes, Name, Path};
+use super::write_separator;
+use crate::path::{FieldName, Indexes, Name, Path};
 
 /// For use an in an update expression to [remove attributes from an
 /// item][1], or [elements from a list][2].
@@ -30,6 +31,8 @@ use crate::path::{Indexes, Name, Path};
 /// [2]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.UpdateExpressions.html#Expressions.UpdateExpressions.REMOVE.RemovingListElements
 /// [`Update`]: crate::update::Update
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Remove {
     pub(crate) paths: Vec<Path>,
 }
@@ -59,13 +62,18 @@ impl Remove {
     /// [`IndexedField`]: crate::path::IndexedField
     pub fn new_indexed_field<N, I>(name: N, indexes: I) -> Self
     where
-        N: Into<Name>,
+        N: Into<FieldName>,
         I: Indexes,
     {
         Self {
             paths: vec![Path::new_indexed_field(name, indexes)],
         }
     }
+
+    /// The paths to the attributes being removed.
+    pub fn paths(&self) -> &[Path] {
+        &self.paths
+    }
 }
 
 impl<T> From<T> for Remove
@@ -102,7 +110,7 @@ impl fmt::Display for Remove {
             if first {
                 first = false;
             } else {
-                f.write_str(", ")?;
+                write_separator(f)?;
             }
 
             name.fmt(f)
@@ -16,6 +16,7 @@ use super::Set;
 /// [2]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.UpdateExpressions.html#Expressions.UpdateExpressions.SET.AddingListElements
 /// [3]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.UpdateExpressions.html#Expressions.UpdateExpressions.SET.AddingNestedMapAttributes
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Assign {
     pub(crate) path: Path,
     pub(crate) value: ValueOrRef,
@@ -49,6 +50,16 @@ impl Assign {
     {
         Set::from(self).and(action)
     }
+
+    /// The path to the attribute being assigned.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The value being assigned.
+    pub fn value(&self) -> &ValueOrRef {
+        &self.value
+    }
 }
 
 impl fmt::Display for Assign {
@@ -12,6 +12,7 @@ use crate::{
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.UpdateExpressions.html#Expressions.UpdateExpressions.SET.PreventingAttributeOverwrites
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IfNotExists {
     pub(crate) dst: Path,
     pub(crate) src: Option<Path>,
@@ -45,6 +46,25 @@ impl IfNotExists {
     {
         Set::from(self).and(action)
     }
+
+    /// The path to the attribute the value is assigned to.
+    pub fn dst(&self) -> &Path {
+        &self.dst
+    }
+
+    /// The path to the attribute checked for existence, if it was set.
+    ///
+    /// When it's `None`, [`dst`] is used.
+    ///
+    /// [`dst`]: Self::dst
+    pub fn src(&self) -> Option<&Path> {
+        self.src.as_ref()
+    }
+
+    /// The value assigned if the attribute doesn't exist.
+    pub fn value(&self) -> &ValueOrRef {
+        &self.value
+    }
 }
 
 impl fmt::Display for IfNotExists {
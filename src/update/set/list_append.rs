@@ -12,6 +12,7 @@ use crate::{
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.UpdateExpressions.html#Expressions.UpdateExpressions.SET.UpdatingListElements
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ListAppend {
     /// The field to set the newly combined list to
     pub(crate) dst: Path,
@@ -23,7 +24,7 @@ pub struct ListAppend {
     pub(crate) list: ValueOrRef,
 
     /// Whether to add the new values to the beginning or end of the source list
-    after: bool,
+    pub(crate) after: bool,
 }
 
 impl ListAppend {
@@ -54,6 +55,31 @@ impl ListAppend {
     {
         Set::from(self).and(action)
     }
+
+    /// The path to the attribute the combined list is assigned to.
+    pub fn dst(&self) -> &Path {
+        &self.dst
+    }
+
+    /// The path to the current list, if it was set.
+    ///
+    /// When it's `None`, [`dst`] is used.
+    ///
+    /// [`dst`]: Self::dst
+    pub fn src(&self) -> Option<&Path> {
+        self.src.as_ref()
+    }
+
+    /// The value(s) being added to the list.
+    pub fn list(&self) -> &ValueOrRef {
+        &self.list
+    }
+
+    /// Whether the new values are added to the end of the list, rather than the
+    /// beginning.
+    pub fn is_after(&self) -> bool {
+        self.after
+    }
 }
 
 impl fmt::Display for ListAppend {
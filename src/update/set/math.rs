@@ -12,10 +12,11 @@ use crate::{
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.UpdateExpressions.html#Expressions.UpdateExpressions.SET.IncrementAndDecrement
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Math {
     pub(crate) dst: Path,
     pub(crate) src: Option<Path>,
-    op: MathOp,
+    pub(crate) op: MathOp,
     pub(crate) num: ValueOrRef,
 }
 
@@ -52,6 +53,30 @@ impl Math {
     {
         Set::from(self).and(action)
     }
+
+    /// The path to the attribute the result is assigned to.
+    pub fn dst(&self) -> &Path {
+        &self.dst
+    }
+
+    /// The path to the attribute the math is done on, if it was set.
+    ///
+    /// When it's `None`, [`dst`] is used.
+    ///
+    /// [`dst`]: Self::dst
+    pub fn src(&self) -> Option<&Path> {
+        self.src.as_ref()
+    }
+
+    /// Whether the number is added or subtracted.
+    pub fn op(&self) -> MathOp {
+        self.op
+    }
+
+    /// The number being added or subtracted.
+    pub fn num(&self) -> &ValueOrRef {
+        &self.num
+    }
 }
 
 impl fmt::Display for Math {
@@ -67,8 +92,11 @@ impl fmt::Display for Math {
     }
 }
 
+/// Whether a [`Math`] action adds to or subtracts from a number.
 #[derive(Clone, Copy, PartialEq, Eq)]
-enum MathOp {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum MathOp {
     Add,
     Sub,
 }
@@ -150,7 +178,7 @@ mod test {
         Num, Path,
     };
 
-    use super::Math;
+    use super::{Math, MathOp};
 
     #[test]
     fn and() {
@@ -176,4 +204,20 @@ mod test {
             combined.to_string()
         );
     }
+
+    #[test]
+    fn getters() {
+        let math = Path::new_name("foo")
+            .math()
+            .src(Path::new_name("bar"))
+            .sub(Num::new(2));
+        assert_eq!(&Path::new_name("foo"), math.dst());
+        assert_eq!(Some(&Path::new_name("bar")), math.src());
+        assert_eq!(MathOp::Sub, math.op());
+        assert_eq!("2", math.num().to_string());
+
+        let math = Path::new_name("foo").math().add(1);
+        assert_eq!(None, math.src());
+        assert_eq!(MathOp::Add, math.op());
+    }
 }
@@ -3,15 +3,19 @@ pub mod if_not_exists;
 pub mod list_append;
 pub mod math;
 mod set_action;
+mod upsert;
 
 pub use self::assign::Assign;
 pub use self::if_not_exists::IfNotExists;
 pub use self::list_append::ListAppend;
 pub use self::math::Math;
 pub use self::set_action::SetAction;
+pub use self::upsert::Upsert;
 
 use core::fmt;
 
+use super::write_separator;
+
 /// Represents a [`SET` statement for an update expression][1].
 ///
 /// See also: [`Update`]
@@ -38,6 +42,8 @@ use core::fmt;
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.UpdateExpressions.html#Expressions.UpdateExpressions.SET
 /// [`Update`]: crate::update::Update
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Set {
     pub(crate) actions: Vec<SetAction>,
 }
@@ -63,6 +69,11 @@ impl Set {
 
         self
     }
+
+    /// The actions in this `SET` statement.
+    pub fn actions(&self) -> &[SetAction] {
+        &self.actions
+    }
 }
 
 impl fmt::Display for Set {
@@ -74,7 +85,7 @@ impl fmt::Display for Set {
             if first {
                 first = false
             } else {
-                f.write_str(", ")?;
+                write_separator(f)?;
             }
 
             action.fmt(f)
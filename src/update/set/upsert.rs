@@ -0,0 +1,182 @@
+use std::collections::{BTreeSet, HashMap};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::{
+    path::{Name, Path},
+    update::{Set, SetAction},
+    value::{Map, UnknownAttributeValueError, Value},
+};
+
+/// Builds a [`Set`] that assigns every attribute of an item, except for its
+/// keys. This makes an [`update_item`][1] act like a [`put_item`][2] that
+/// leaves alone any attributes not in the item.
+///
+/// Attributes given to [`Upsert::with_if_not_exists`] are only assigned if
+/// they aren't already there, which is useful for something like a
+/// `created_at` timestamp.
+///
+/// ```
+/// use dynamodb_expression::{
+///     update::Upsert,
+///     value::{Map, Value},
+///     Expression,
+/// };
+/// # use pretty_assertions::assert_eq;
+///
+/// let item = Map::from([
+///     ("id", Value::new_string("abc")),
+///     ("name", Value::new_string("Jane")),
+///     ("age", Value::new_num(40)),
+///     ("created_at", Value::new_string("2024-01-01")),
+/// ]);
+///
+/// let set = Upsert::new(item)
+///     .with_keys(["id"])
+///     .with_if_not_exists(["created_at"])
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     r#"SET age = 40, created_at = if_not_exists(created_at, "2024-01-01"), name = "Jane""#,
+///     set.to_string()
+/// );
+///
+/// let expression = Expression::builder().with_update(set).build();
+/// assert_eq!(
+///     Some(String::from(
+///         "SET #0 = :0, #1 = if_not_exists(#1, :1), #2 = :2"
+///     )),
+///     expression.update_expression
+/// );
+/// ```
+///
+/// [1]: crate::Expression::update_item
+/// [2]: crate::Expression::put_item
+#[must_use = "Use `.build()` to create the `Set`"]
+#[derive(Debug, Clone)]
+pub struct Upsert {
+    item: Map,
+    keys: BTreeSet<Name>,
+    if_not_exists: BTreeSet<Name>,
+}
+
+impl Upsert {
+    /// Starts an [`Upsert`] for the given item.
+    pub fn new<T>(item: T) -> Self
+    where
+        T: Into<Map>,
+    {
+        Self {
+            item: item.into(),
+            keys: BTreeSet::new(),
+            if_not_exists: BTreeSet::new(),
+        }
+    }
+
+    /// Starts an [`Upsert`] for an item from the [`aws_sdk_dynamodb`].
+    pub fn try_from_item(
+        item: HashMap<String, AttributeValue>,
+    ) -> Result<Self, UnknownAttributeValueError> {
+        item.into_iter()
+            .map(|(name, value)| Value::try_from(value).map(|value| (name, value)))
+            .collect::<Result<Map, _>>()
+            .map(Self::new)
+    }
+
+    /// The key attributes of the item. These are left out, since DynamoDB
+    /// doesn't allow updating them.
+    pub fn with_keys<I, T>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Name>,
+    {
+        self.keys.extend(keys.into_iter().map(Into::into));
+
+        self
+    }
+
+    /// Attributes that are only assigned if the item doesn't already have
+    /// them, using [`if_not_exists`][1].
+    ///
+    /// [1]: crate::update::IfNotExists
+    pub fn with_if_not_exists<I, T>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Name>,
+    {
+        self.if_not_exists.extend(names.into_iter().map(Into::into));
+
+        self
+    }
+
+    /// Builds the [`Set`], in the order of the attribute names.
+    ///
+    /// Returns `None` if the item has nothing but key attributes. An
+    /// `update_item` with no update expression still creates the item if it
+    /// doesn't exist.
+    pub fn build(self) -> Option<Set> {
+        let Self {
+            item,
+            keys,
+            if_not_exists,
+        } = self;
+
+        let actions = item
+            .map
+            .into_iter()
+            .filter(|(name, _value)| !keys.contains(name))
+            .map(|(name, value)| -> SetAction {
+                if if_not_exists.contains(&name) {
+                    Path::from(name).if_not_exists().assign(value).into()
+                } else {
+                    Path::from(name).assign(value).into()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        (!actions.is_empty()).then(|| Set::from_iter(actions))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use pretty_assertions::assert_eq;
+
+    use super::Upsert;
+
+    #[test]
+    fn from_item() {
+        let item = HashMap::from([
+            ("pk".to_string(), AttributeValue::S("a".into())),
+            ("sk".to_string(), AttributeValue::N("1".into())),
+            ("on".to_string(), AttributeValue::Bool(true)),
+            ("first_seen".to_string(), AttributeValue::N("7".into())),
+        ]);
+
+        let set = Upsert::try_from_item(item.clone())
+            .unwrap()
+            .with_keys(["pk", "sk"])
+            .with_if_not_exists(["first_seen"])
+            .build()
+            .unwrap();
+        assert_eq!(
+            "SET first_seen = if_not_exists(first_seen, 7), on = true",
+            set.to_string()
+        );
+
+        let keys_only = item
+            .into_iter()
+            .filter(|(name, _)| name.ends_with('k'))
+            .collect();
+        assert_eq!(
+            None,
+            Upsert::try_from_item(keys_only)
+                .unwrap()
+                .with_keys(["pk", "sk"])
+                .build()
+        );
+    }
+}
@@ -0,0 +1,381 @@
+use serde::{
+    de::{
+        self,
+        value::{MapDeserializer, SeqDeserializer, StringDeserializer},
+        DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any,
+};
+
+use super::{Num, Scalar, SerdeError, Set, Value};
+
+/// Deserializes a [`Value`] (or a [`Map`], [`List`], etc.) into any
+/// [`DeserializeOwned`] type. The reverse of [`to_value`].
+///
+/// DynamoDB sets can be deserialized into any sequence type.
+///
+/// Requires the `serde` feature.
+///
+/// ```
+/// use dynamodb_expression::value::{self, Map, Num, NumSet, StringSet, Value};
+/// # use pretty_assertions::assert_eq;
+///
+/// #[derive(Debug, PartialEq, serde::Deserialize)]
+/// struct Person {
+///     name: String,
+///     age: u8,
+///     nickname: Option<String>,
+///     tags: Vec<String>,
+///     lucky_numbers: Vec<u32>,
+/// }
+///
+/// let person: Person = value::from_value(Map::from([
+///     ("name", Value::from("Jane")),
+///     ("age", Num::new(42).into()),
+///     ("nickname", ().into()),
+///     ("tags", StringSet::from(["a", "b"]).into()),
+///     ("lucky_numbers", NumSet::from([3, 7]).into()),
+/// ]))
+/// .unwrap();
+///
+/// assert_eq!(
+///     Person {
+///         name: String::from("Jane"),
+///         age: 42,
+///         nickname: None,
+///         tags: vec![String::from("a"), String::from("b")],
+///         lucky_numbers: vec![3, 7],
+///     },
+///     person
+/// );
+/// ```
+///
+/// [`Map`]: super::Map
+/// [`List`]: super::List
+/// [`to_value`]: super::to_value
+pub fn from_value<T, V>(value: V) -> Result<T, SerdeError>
+where
+    T: DeserializeOwned,
+    V: Into<Value>,
+{
+    T::deserialize(value.into())
+}
+
+impl<'de> IntoDeserializer<'de, SerdeError> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+fn visit_seq<'de, I, V>(items: I, visitor: V) -> Result<V::Value, SerdeError>
+where
+    I: Iterator,
+    I::Item: IntoDeserializer<'de, SerdeError>,
+    V: Visitor<'de>,
+{
+    let mut seq = SeqDeserializer::new(items);
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+
+    Ok(value)
+}
+
+fn visit_num<'de, V>(num: Num, visitor: V) -> Result<V::Value, SerdeError>
+where
+    V: Visitor<'de>,
+{
+    let n = num.n;
+    if let Ok(n) = n.parse() {
+        visitor.visit_u64(n)
+    } else if let Ok(n) = n.parse() {
+        visitor.visit_i64(n)
+    } else if let Ok(n) = n.parse() {
+        visitor.visit_u128(n)
+    } else if let Ok(n) = n.parse() {
+        visitor.visit_i128(n)
+    } else if let Ok(n) = n.parse() {
+        visitor.visit_f64(n)
+    } else {
+        Err(de::Error::invalid_value(
+            de::Unexpected::Str(&n),
+            &"a number",
+        ))
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = SerdeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Self::Scalar(Scalar::String(s)) => visitor.visit_string(s),
+            Self::Scalar(Scalar::Num(n)) => visit_num(n, visitor),
+            Self::Scalar(Scalar::Bool(b)) => visitor.visit_bool(b),
+            Self::Scalar(Scalar::Binary(b)) => visitor.visit_byte_buf(b),
+            Self::Scalar(Scalar::Null) => visitor.visit_unit(),
+            Self::Set(Set::StringSet(set)) => visit_seq(set.0.into_iter(), visitor),
+            Self::Set(Set::NumSet(set)) => visit_seq(set.0.into_iter().map(Value::from), visitor),
+            Self::Set(Set::BinarySet(set)) => {
+                visit_seq(set.0.into_iter().map(Value::from), visitor)
+            }
+            Self::Map(map) => {
+                let mut map =
+                    MapDeserializer::new(map.map.into_iter().map(|(k, v)| (String::from(k), v)));
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+
+                Ok(value)
+            }
+            Self::List(list) => visit_seq(list.list.into_iter(), visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Self::Scalar(Scalar::Null) => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            // E.g., into a `Vec<u8>`.
+            Self::Scalar(Scalar::Binary(b)) => visit_seq(b.into_iter(), visitor),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Self::Scalar(Scalar::String(variant)) => {
+                let variant: StringDeserializer<SerdeError> = variant.into_deserializer();
+                visitor.visit_enum(variant)
+            }
+            Self::Map(map) if map.map.len() == 1 => {
+                let (variant, value) = map.map.into_iter().next().unwrap();
+                visitor.visit_enum(Variant {
+                    variant: variant.into(),
+                    value,
+                })
+            }
+            _ => Err(de::Error::custom(
+                "expected a string or a map with a single key for an enum",
+            )),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// An enum variant with data, from a map with the variant name as its only key.
+struct Variant {
+    variant: String,
+    value: Value,
+}
+
+impl<'de> EnumAccess<'de> for Variant {
+    type Error = SerdeError;
+    type Variant = Value;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Value), SerdeError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant: StringDeserializer<SerdeError> = self.variant.into_deserializer();
+
+        Ok((seed.deserialize(variant)?, self.value))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Value {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> Result<(), SerdeError> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, SerdeError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use pretty_assertions::assert_eq;
+    use serde::{Deserialize, Serialize};
+
+    use crate::value::{
+        self, from_value, to_list, to_map, to_value, BinarySet, List, Map, Num, NumSet, Scalar,
+        StringSet, Value,
+    };
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Point,
+        Circle(f64),
+        Line(i32, i32),
+        Rect { w: u16, h: u16 },
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        id: u64,
+        #[serde(rename = "Name")]
+        name: String,
+        negative: i64,
+        ratio: f32,
+        flag: bool,
+        nothing: Option<String>,
+        something: Option<char>,
+        #[serde(with = "value::binary_set")]
+        blobs: Vec<Vec<u8>>,
+        #[serde(with = "value::string_set")]
+        tags: BTreeSet<String>,
+        #[serde(with = "value::num_set")]
+        nums: Vec<u8>,
+        shapes: Vec<Shape>,
+        counts: BTreeMap<String, u32>,
+        unit: (),
+        tuple: (String, bool),
+    }
+
+    #[test]
+    fn round_trip() {
+        let item = Item {
+            id: 7,
+            name: String::from("thing"),
+            negative: -3,
+            ratio: 0.5,
+            flag: true,
+            nothing: None,
+            something: Some('x'),
+            blobs: vec![b"a".to_vec(), b"b".to_vec()],
+            tags: ["x", "y"].into_iter().map(String::from).collect(),
+            nums: vec![1, 2],
+            shapes: vec![
+                Shape::Point,
+                Shape::Circle(1.5),
+                Shape::Line(1, -1),
+                Shape::Rect { w: 2, h: 3 },
+            ],
+            counts: [(String::from("one"), 1)].into(),
+            unit: (),
+            tuple: (String::from("t"), false),
+        };
+
+        let map = to_map(&item).unwrap();
+        assert_eq!(
+            Map::from([
+                ("id", Value::from(Num::new(7))),
+                ("Name", "thing".into()),
+                ("negative", Num::new(-3).into()),
+                ("ratio", Num::new(0.5).into()),
+                ("flag", true.into()),
+                ("nothing", Scalar::Null.into()),
+                ("something", "x".into()),
+                ("blobs", BinarySet::from([b"a", b"b"]).into()),
+                ("tags", StringSet::from(["x", "y"]).into()),
+                ("nums", NumSet::from([1, 2]).into()),
+                (
+                    "shapes",
+                    List::from([
+                        Value::from("Point"),
+                        Map::from([("Circle", Num::new(1.5))]).into(),
+                        Map::from([("Line", List::from([Num::new(1), Num::new(-1)]))]).into(),
+                        Map::from([("Rect", Map::from([("w", Num::new(2)), ("h", Num::new(3))]))])
+                            .into(),
+                    ])
+                    .into()
+                ),
+                ("counts", Map::from([("one", Num::new(1))]).into()),
+                ("unit", Scalar::Null.into()),
+                ("tuple", List::from([Value::from("t"), false.into()]).into()),
+            ]),
+            map
+        );
+
+        assert_eq!(item, from_value::<Item, _>(map).unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        assert!(to_value(&f64::NAN).is_err());
+        assert!(to_map(&[1, 2]).is_err());
+        assert!(to_value(&BTreeMap::from([(1, 2)])).is_err());
+        assert_eq!(
+            List::from([Num::new(1), Num::new(2)]),
+            to_list(&[1, 2]).unwrap()
+        );
+
+        #[derive(Serialize)]
+        struct BadSet {
+            #[serde(with = "value::string_set")]
+            set: Vec<u8>,
+        }
+        assert!(to_value(&BadSet { set: vec![1] }).is_err());
+
+        assert!(from_value::<u8, _>(Num::new(256)).is_err());
+        assert!(from_value::<String, _>(Num::new(1)).is_err());
+        assert_eq!(
+            b"abc".to_vec(),
+            from_value::<Vec<u8>, _>(b"abc".to_vec()).unwrap()
+        );
+    }
+}
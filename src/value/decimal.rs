@@ -0,0 +1,327 @@
+//! Exact arithmetic on DynamoDB numbers, which can have up to 38 digits of
+//! precision and so don't fit in an `f64`.
+
+use core::{
+    cmp::Ordering,
+    fmt::{self, Write},
+};
+
+use super::Num;
+
+/// Compares two DynamoDB numbers. `None` if either isn't a valid number.
+pub(crate) fn compare(a: &Num, b: &Num) -> Option<Ordering> {
+    difference(a, b).map(|(ordering, _delta)| ordering)
+}
+
+/// `a + b`. `None` if either isn't a valid DynamoDB number.
+pub(crate) fn sum(a: &Num, b: &Num) -> Option<Num> {
+    let b = b.n.trim();
+    let b = b.strip_prefix('+').unwrap_or(b);
+    let negated = Num {
+        n: match b.strip_prefix('-') {
+            Some(b) => b.to_owned(),
+            None => format!("-{b}"),
+        },
+    };
+
+    sub(a, &negated)
+}
+
+/// `a - b`. `None` if either isn't a valid DynamoDB number.
+pub(crate) fn sub(a: &Num, b: &Num) -> Option<Num> {
+    let (ordering, Num { n }) = difference(a, b)?;
+
+    Some(Num {
+        n: match ordering {
+            Ordering::Less => format!("-{n}"),
+            Ordering::Equal | Ordering::Greater => n,
+        },
+    })
+}
+
+/// Whether the number is one DynamoDB would accept.
+#[cfg_attr(not(feature = "fake"), allow(dead_code))]
+pub(crate) fn is_valid(num: &Num) -> bool {
+    Decimal::parse(&num.n).is_some()
+}
+
+/// `new - old`, as whether it's positive, negative, or zero, and its
+/// magnitude. `None` if either isn't a valid DynamoDB number.
+///
+/// Done in decimal, so there's no loss of precision.
+pub(crate) fn difference(new: &Num, old: &Num) -> Option<(Ordering, Num)> {
+    let Decimal {
+        negative: new_negative,
+        digits: new_digits,
+        exponent: new_exponent,
+    } = Decimal::parse(&new.n)?;
+    let Decimal {
+        negative: old_negative,
+        digits: old_digits,
+        exponent: old_exponent,
+    } = Decimal::parse(&old.n)?;
+
+    // Line up the digits so they have the same exponent. DynamoDB numbers
+    // are much closer together than this, so anything else isn't worth it.
+    let exponent = new_exponent.min(old_exponent);
+    if new_exponent.max(old_exponent) - exponent > 1000 {
+        return None;
+    }
+
+    let shift = |mut digits: Vec<u8>, from: i32| {
+        digits.resize(digits.len() + (from - exponent) as usize, 0);
+        digits
+    };
+    let new_digits = shift(new_digits, new_exponent);
+    let old_digits = shift(old_digits, old_exponent);
+
+    // Adding `new` and `-old`.
+    let old_negative = !old_negative;
+    let (negative, digits) = if new_negative == old_negative {
+        (new_negative, add(&new_digits, &old_digits))
+    } else {
+        match compare_digits(&new_digits, &old_digits) {
+            Ordering::Equal => (false, Vec::new()),
+            Ordering::Greater => (new_negative, subtract(&new_digits, &old_digits)),
+            Ordering::Less => (old_negative, subtract(&old_digits, &new_digits)),
+        }
+    };
+
+    let delta = Decimal {
+        negative: false,
+        digits,
+        exponent,
+    }
+    .normalize();
+
+    let ordering = if delta.digits.is_empty() {
+        Ordering::Equal
+    } else if negative {
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    };
+
+    Some((
+        ordering,
+        Num {
+            n: delta.to_string(),
+        },
+    ))
+}
+
+/// A decimal number: `digits * 10^exponent`.
+struct Decimal {
+    negative: bool,
+    /// Most significant first. Empty for zero.
+    digits: Vec<u8>,
+    exponent: i32,
+}
+
+impl Decimal {
+    fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+
+        let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+            Some((mantissa, exponent)) => (mantissa, exponent.parse::<i32>().ok()?),
+            None => (s, 0),
+        };
+        let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return None;
+        }
+
+        let digits = integer
+            .bytes()
+            .chain(fraction.bytes())
+            .map(|digit| digit.is_ascii_digit().then(|| digit - b'0'))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(
+            Self {
+                negative,
+                digits,
+                exponent: exponent.checked_sub(i32::try_from(fraction.len()).ok()?)?,
+            }
+            .normalize(),
+        )
+    }
+
+    /// Strips leading zeros, and trailing zeros into the exponent.
+    fn normalize(mut self) -> Self {
+        let leading = self.digits.iter().take_while(|&&digit| digit == 0).count();
+        self.digits.drain(..leading);
+
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+            self.exponent += 1;
+        }
+
+        if self.digits.is_empty() {
+            self.negative = false;
+            self.exponent = 0;
+        }
+
+        self
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.digits.is_empty() {
+            return f.write_char('0');
+        }
+
+        if self.negative {
+            f.write_char('-')?;
+        }
+
+        let digits = self
+            .digits
+            .iter()
+            .map(|digit| char::from(b'0' + digit))
+            .collect::<String>();
+
+        if self.exponent >= 0 {
+            f.write_str(&digits)?;
+            (0..self.exponent).try_for_each(|_| f.write_char('0'))
+        } else {
+            let fraction_len = self.exponent.unsigned_abs() as usize;
+            if digits.len() > fraction_len {
+                let (integer, fraction) = digits.split_at(digits.len() - fraction_len);
+                write!(f, "{integer}.{fraction}")
+            } else {
+                f.write_str("0.")?;
+                (digits.len()..fraction_len).try_for_each(|_| f.write_char('0'))?;
+                f.write_str(&digits)
+            }
+        }
+    }
+}
+
+/// Compares two unsigned numbers, as digits with no leading zeros.
+fn compare_digits(a: &[u8], b: &[u8]) -> Ordering {
+    let strip = |digits: &[u8]| {
+        let leading = digits.iter().take_while(|&&digit| digit == 0).count();
+        digits[leading..].to_vec()
+    };
+    let (a, b) = (strip(a), strip(b));
+
+    a.len().cmp(&b.len()).then_with(|| a.cmp(&b))
+}
+
+fn add(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0;
+    let mut a = a.iter().rev();
+    let mut b = b.iter().rev();
+    loop {
+        let (x, y) = (a.next(), b.next());
+        if x.is_none() && y.is_none() {
+            break;
+        }
+
+        let digit = x.unwrap_or(&0) + y.unwrap_or(&0) + carry;
+        sum.push(digit % 10);
+        carry = digit / 10;
+    }
+    if carry > 0 {
+        sum.push(carry);
+    }
+
+    sum.reverse();
+    sum
+}
+
+/// `a - b`, where `a >= b`.
+fn subtract(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+    let mut b = b.iter().rev();
+    for &x in a.iter().rev() {
+        let y = b.next().copied().unwrap_or(0) + borrow;
+        if x >= y {
+            difference.push(x - y);
+            borrow = 0;
+        } else {
+            difference.push(x + 10 - y);
+            borrow = 1;
+        }
+    }
+
+    difference.reverse();
+    difference
+}
+
+#[cfg(test)]
+mod test {
+    use core::cmp::Ordering::*;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::Num;
+
+    use super::{compare, difference, is_valid, sub, sum};
+
+    #[test]
+    fn difference_is_exact() {
+        let check = |new: &str, old: &str| {
+            difference(&Num { n: new.into() }, &Num { n: old.into() })
+                .map(|(ordering, delta)| (ordering, delta.to_string()))
+        };
+
+        assert_eq!(Some((Greater, "1".into())), check("4", "3"));
+        assert_eq!(Some((Less, "1".into())), check("3", "4"));
+        assert_eq!(Some((Equal, "0".into())), check("1.0", "1"));
+        assert_eq!(Some((Greater, "0.3".into())), check("0.5", "0.2"));
+        assert_eq!(Some((Less, "10.25".into())), check("-10", "0.25"));
+        assert_eq!(Some((Greater, "15".into())), check("10", "-5"));
+        assert_eq!(Some((Less, "5".into())), check("-10", "-5"));
+        assert_eq!(Some((Greater, "1500".into())), check("2e3", "5E+2"));
+        assert_eq!(Some((Greater, "0.001".into())), check("1.001", "1"));
+        assert_eq!(
+            Some((Greater, "1".into())),
+            check(
+                "12345678901234567890123456789012345678",
+                "12345678901234567890123456789012345677"
+            )
+        );
+        assert_eq!(None, check("abc", "1"));
+        assert_eq!(None, check("1", ""));
+    }
+
+    #[test]
+    fn compare_numbers() {
+        let num = |n: &str| Num { n: n.into() };
+
+        assert_eq!(Some(Equal), compare(&num("100"), &num("1e2")));
+        assert_eq!(Some(Less), compare(&num("-3"), &num("2")));
+        assert_eq!(Some(Greater), compare(&num("0.25"), &num(".2")));
+        assert_eq!(None, compare(&num("1"), &num("one")));
+    }
+
+    #[test]
+    fn sum_numbers() {
+        let num = |n: &str| Num { n: n.into() };
+        let sum = |a, b| sum(&num(a), &num(b)).map(|Num { n }| n);
+
+        assert_eq!(Some("3.5".into()), sum("1", "2.5"));
+        assert_eq!(Some("-1.5".into()), sum("1", "-2.5"));
+        assert_eq!(Some("0".into()), sum("-1e2", "+100"));
+        assert_eq!(
+            Some("100000000000000000000000000000000000001".into()),
+            sum("1e38", "1")
+        );
+        assert_eq!(None, sum("1", "-"));
+        assert_eq!(
+            Some("-0.25".into()),
+            sub(&num("0.75"), &num("1")).map(|Num { n }| n)
+        );
+        assert!(is_valid(&num("-0.5e-3")));
+        assert!(!is_valid(&num("1.2.3")));
+    }
+}
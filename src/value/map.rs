@@ -19,8 +19,10 @@ type MapType<K, V> = std::collections::BTreeMap<K, V>;
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.Document.Map
 #[derive(Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Map {
-    map: MapType<Name, Value>,
+    pub(crate) map: MapType<Name, Value>,
 }
 
 impl Map {
@@ -1,7 +1,14 @@
+#[cfg(feature = "serde")]
+mod de;
+pub(crate) mod decimal;
 mod list;
 mod map;
 mod num;
 mod scalar;
+#[cfg(feature = "serde")]
+mod ser;
+#[cfg(feature = "serde")]
+mod serialization;
 mod set;
 mod value_or_ref;
 
@@ -10,9 +17,13 @@ pub use map::Map;
 pub use num::Num;
 pub use scalar::Scalar;
 pub use set::{BinarySet, NumSet, Set, StringSet};
-pub use value_or_ref::{Ref, StringOrRef};
+pub use value_or_ref::{Ref, StringOrRef, ValueOrRef};
 
-pub(crate) use value_or_ref::ValueOrRef;
+#[cfg(feature = "serde")]
+pub use self::{
+    de::from_value,
+    ser::{binary_set, num_set, string_set, to_list, to_map, to_value, SerdeError},
+};
 
 use core::fmt::{self, LowerExp, UpperExp};
 use std::error::Error;
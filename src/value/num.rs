@@ -41,6 +41,8 @@ use aws_sdk_dynamodb::types::AttributeValue;
 /// [`Value::new_num_lower_exp`]: crate::value::Value::new_num_lower_exp
 /// [`Value::new_num_upper_exp`]: crate::value::Value::new_num_upper_exp
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Num {
     pub(crate) n: String,
 }
@@ -0,0 +1,598 @@
+use core::fmt::{self, Display};
+use std::{collections::BTreeMap, error::Error};
+
+use serde::{ser, Serialize};
+
+use super::{List, Map, Num, Scalar, Set, Value};
+use crate::path::Name;
+
+/// Serializes any [`Serialize`] type into a [`Value`].
+///
+/// Structs and maps become a [`Map`], sequences and tuples become a [`List`],
+/// numbers become a [`Num`], and `None` and `()` become a null value. Enums
+/// are externally tagged, the same as `serde_json` does.
+///
+/// Sequences become a [`List`] unless told otherwise. Use
+/// `#[serde(with = "...")]` with [`string_set`], [`num_set`], or
+/// [`binary_set`] to get a DynamoDB set instead.
+///
+/// Requires the `serde` feature.
+///
+/// ```
+/// use dynamodb_expression::{
+///     value::{self, List, Map, Num, NumSet, StringSet, Value},
+///     Expression, Path,
+/// };
+/// # use pretty_assertions::assert_eq;
+///
+/// #[derive(serde::Serialize)]
+/// struct Address {
+///     city: String,
+///     zips: Vec<u32>,
+///     #[serde(with = "value::num_set")]
+///     zip_set: Vec<u32>,
+///     #[serde(with = "value::string_set")]
+///     tags: Vec<&'static str>,
+/// }
+///
+/// let address = Address {
+///     city: String::from("Springfield"),
+///     zips: vec![49007, 49008],
+///     zip_set: vec![49007, 49008],
+///     tags: vec!["home"],
+/// };
+///
+/// let map = value::to_map(&address).unwrap();
+/// assert_eq!(
+///     Map::from([
+///         ("city", Value::from("Springfield")),
+///         (
+///             "zips",
+///             List::from([Num::new(49007), Num::new(49008)]).into()
+///         ),
+///         ("zip_set", NumSet::from([49007, 49008]).into()),
+///         ("tags", StringSet::from(["home"]).into()),
+///     ]),
+///     map
+/// );
+///
+/// // Use it in an update expression.
+/// let expression = Expression::builder()
+///     .with_update(Path::new_name("address").assign(map))
+///     .build();
+/// assert_eq!(
+///     Some(String::from("SET #0 = :0")),
+///     expression.update_expression
+/// );
+/// ```
+pub fn to_value<T>(value: &T) -> Result<Value, SerdeError>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(Serializer)
+}
+
+/// Serializes a struct or map into a [`Map`]. Fails if it serializes to
+/// anything else.
+///
+/// See [`to_value`] for details and an example.
+///
+/// Requires the `serde` feature.
+pub fn to_map<T>(value: &T) -> Result<Map, SerdeError>
+where
+    T: Serialize + ?Sized,
+{
+    match to_value(value)? {
+        Value::Map(map) => Ok(map),
+        _ => Err(SerdeError::new("expected the value to serialize to a map")),
+    }
+}
+
+/// Serializes a sequence or tuple into a [`List`]. Fails if it serializes to
+/// anything else.
+///
+/// See [`to_value`] for details.
+///
+/// Requires the `serde` feature.
+pub fn to_list<T>(value: &T) -> Result<List, SerdeError>
+where
+    T: Serialize + ?Sized,
+{
+    match to_value(value)? {
+        Value::List(list) => Ok(list),
+        _ => Err(SerdeError::new("expected the value to serialize to a list")),
+    }
+}
+
+/// An error converting between a [`Value`] and a Rust type with [`to_value`]
+/// or [`from_value`].
+///
+/// [`from_value`]: super::from_value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerdeError {
+    message: String,
+}
+
+impl SerdeError {
+    fn new<T>(message: T) -> Self
+    where
+        T: Display,
+    {
+        Self {
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for SerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+impl Error for SerdeError {}
+
+impl ser::Error for SerdeError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: Display,
+    {
+        Self::new(msg)
+    }
+}
+
+impl serde::de::Error for SerdeError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: Display,
+    {
+        Self::new(msg)
+    }
+}
+
+// Names of the newtype structs used to signal that a sequence is a set.
+const STRING_SET: &str = "$dynamodb_expression::StringSet";
+const NUM_SET: &str = "$dynamodb_expression::NumSet";
+const BINARY_SET: &str = "$dynamodb_expression::BinarySet";
+
+macro_rules! set_hint {
+    ($(#[$attr:meta])* $module:ident, $name:ident) => {
+        $(#[$attr])*
+        pub mod $module {
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: Serialize + ?Sized,
+                S: Serializer,
+            {
+                serializer.serialize_newtype_struct(super::$name, value)
+            }
+
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+            where
+                T: Deserialize<'de>,
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer)
+            }
+        }
+    };
+}
+
+set_hint!(
+    /// Use with `#[serde(with = "dynamodb_expression::value::string_set")]` on a
+    /// sequence field so [`to_value`](super::to_value) serializes it as a
+    /// DynamoDB string set rather than a list. Other serializers see a plain
+    /// sequence.
+    ///
+    /// Requires the `serde` feature.
+    string_set,
+    STRING_SET
+);
+
+set_hint!(
+    /// Use with `#[serde(with = "dynamodb_expression::value::num_set")]` on a
+    /// sequence field so [`to_value`](super::to_value) serializes it as a
+    /// DynamoDB number set rather than a list. Other serializers see a plain
+    /// sequence.
+    ///
+    /// Requires the `serde` feature.
+    num_set,
+    NUM_SET
+);
+
+set_hint!(
+    /// Use with `#[serde(with = "dynamodb_expression::value::binary_set")]` on a
+    /// sequence field so [`to_value`](super::to_value) serializes it as a
+    /// DynamoDB binary set rather than a list. Other serializers see a plain
+    /// sequence.
+    ///
+    /// Requires the `serde` feature.
+    binary_set,
+    BINARY_SET
+);
+
+/// Turns a serialized sequence into the set named by `hint`.
+fn into_set(hint: &str, value: Value) -> Result<Value, SerdeError> {
+    let items = match value {
+        Value::List(list) => list.list,
+        Value::Set(set) => return Ok(set.into()),
+        _ => return Err(SerdeError::new("expected a sequence to serialize as a set")),
+    };
+    let items = items.into_iter();
+
+    Ok(match hint {
+        STRING_SET => items
+            .map(|item| match item {
+                Value::Scalar(Scalar::String(s)) => Ok(s),
+                _ => Err(SerdeError::new("expected strings in a string set")),
+            })
+            .collect::<Result<_, _>>()
+            .map(Set::StringSet)?,
+        NUM_SET => items
+            .map(|item| match item {
+                Value::Scalar(Scalar::Num(n)) => Ok(n),
+                _ => Err(SerdeError::new("expected numbers in a number set")),
+            })
+            .collect::<Result<_, _>>()
+            .map(Set::NumSet)?,
+        _ => items
+            .map(|item| match item {
+                Value::Scalar(Scalar::Binary(b)) => Ok(b),
+                // E.g., `Vec<u8>`, which serializes as a sequence of numbers.
+                Value::List(list) => list
+                    .list
+                    .into_iter()
+                    .map(|byte| match byte {
+                        Value::Scalar(Scalar::Num(n)) => n.n.parse::<u8>().ok(),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| SerdeError::new("expected bytes in a binary set")),
+                _ => Err(SerdeError::new("expected bytes in a binary set")),
+            })
+            .collect::<Result<_, _>>()
+            .map(Set::BinarySet)?,
+    }
+    .into())
+}
+
+struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    type SerializeSeq = SerializeList;
+    type SerializeTuple = SerializeList;
+    type SerializeTupleStruct = SerializeList;
+    type SerializeTupleVariant = SerializeVariant<SerializeList>;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeVariant<SerializeMap>;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, SerdeError> {
+        Ok(v.into())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, SerdeError> {
+        Ok(Num::new(v).into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, SerdeError> {
+        Ok(Num::new(v).into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, SerdeError> {
+        Ok(Num::new(v).into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, SerdeError> {
+        Ok(Num::new(v).into())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Value, SerdeError> {
+        Ok(Num::new(v).into())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, SerdeError> {
+        Ok(Num::new(v).into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, SerdeError> {
+        Ok(Num::new(v).into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, SerdeError> {
+        Ok(Num::new(v).into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value, SerdeError> {
+        Ok(Num::new(v).into())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Value, SerdeError> {
+        Ok(Num::new(v).into())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, SerdeError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, SerdeError> {
+        if v.is_finite() {
+            Ok(Num::new(v).into())
+        } else {
+            Err(SerdeError::new(format!(
+                "DynamoDB doesn't support the number {v}"
+            )))
+        }
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, SerdeError> {
+        Ok(String::from(v).into())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, SerdeError> {
+        Ok(v.into())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, SerdeError> {
+        Ok(v.to_vec().into())
+    }
+
+    fn serialize_none(self) -> Result<Value, SerdeError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Value, SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, SerdeError> {
+        Ok(Scalar::Null.into())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, SerdeError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value, SerdeError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Value, SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        match name {
+            STRING_SET | NUM_SET | BINARY_SET => into_set(name, value.serialize(self)?),
+            _ => value.serialize(self),
+        }
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(Map::from([(variant, value.serialize(self)?)]).into())
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeList, SerdeError> {
+        Ok(SerializeList {
+            list: Vec::with_capacity(len.unwrap_or_default()),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeList, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeList, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeList>, SerdeError> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeMap, SerdeError> {
+        Ok(SerializeMap::default())
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeMap, SerdeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeMap>, SerdeError> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+struct SerializeList {
+    list: Vec<Value>,
+}
+
+impl ser::SerializeSeq for SerializeList {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.list.push(to_value(value)?);
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(List::from(self.list).into())
+    }
+}
+
+impl ser::SerializeTuple for SerializeList {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeList {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+#[derive(Default)]
+struct SerializeMap {
+    map: BTreeMap<Name, Value>,
+    key: Option<Name>,
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(match to_value(key)? {
+            Value::Scalar(Scalar::String(key)) => key.into(),
+            _ => return Err(SerdeError::new("map keys must be strings")),
+        });
+
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| SerdeError::new("map value serialized before its key"))?;
+        self.map.insert(key, to_value(value)?);
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(Map { map: self.map }.into())
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.map.insert(key.into(), to_value(value)?);
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+/// An enum variant with data, which is serialized as a map with the variant
+/// name as its only key.
+struct SerializeVariant<T> {
+    variant: &'static str,
+    inner: T,
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeList> {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(Map::from([(self.variant, ser::SerializeSeq::end(self.inner)?)]).into())
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeMap> {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(Map::from([(self.variant, ser::SerializeMap::end(self.inner)?)]).into())
+    }
+}
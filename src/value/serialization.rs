@@ -0,0 +1,226 @@
+//! [`serde`] support for [`Value`], [`Scalar`], and [`Set`], using the same
+//! shape DynamoDB uses for its [`AttributeValue`][1] JSON. E.g., `{"S": "a"}`,
+//! `{"N": "42"}`, or `{"SS": ["a", "b"]}`.
+//!
+//! [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_AttributeValue.html
+
+use base64::{engine::general_purpose, Engine as _};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{base64, BinarySet, List, Map, Num, NumSet, Scalar, Set, StringSet, Value};
+
+#[derive(Serialize)]
+enum ValueSer<'a> {
+    #[serde(rename = "S")]
+    String(&'a str),
+    #[serde(rename = "N")]
+    Num(&'a Num),
+    #[serde(rename = "BOOL")]
+    Bool(bool),
+    #[serde(rename = "B")]
+    Binary(String),
+    #[serde(rename = "NULL")]
+    Null(bool),
+    #[serde(rename = "SS")]
+    StringSet(&'a StringSet),
+    #[serde(rename = "NS")]
+    NumSet(&'a NumSet),
+    #[serde(rename = "BS")]
+    BinarySet(&'a BinarySet),
+    #[serde(rename = "M")]
+    Map(&'a Map),
+    #[serde(rename = "L")]
+    List(&'a List),
+}
+
+#[derive(Deserialize)]
+enum ValueDe {
+    #[serde(rename = "S")]
+    String(String),
+    #[serde(rename = "N")]
+    Num(Num),
+    #[serde(rename = "BOOL")]
+    Bool(bool),
+    #[serde(rename = "B")]
+    Binary(String),
+    #[serde(rename = "NULL")]
+    Null(bool),
+    #[serde(rename = "SS")]
+    StringSet(StringSet),
+    #[serde(rename = "NS")]
+    NumSet(NumSet),
+    #[serde(rename = "BS")]
+    BinarySet(BinarySet),
+    #[serde(rename = "M")]
+    Map(Map),
+    #[serde(rename = "L")]
+    List(List),
+}
+
+impl<'a> From<&'a Scalar> for ValueSer<'a> {
+    fn from(scalar: &'a Scalar) -> Self {
+        match scalar {
+            Scalar::String(s) => Self::String(s),
+            Scalar::Num(n) => Self::Num(n),
+            Scalar::Bool(b) => Self::Bool(*b),
+            Scalar::Binary(b) => Self::Binary(base64(b)),
+            Scalar::Null => Self::Null(true),
+        }
+    }
+}
+
+impl<'a> From<&'a Set> for ValueSer<'a> {
+    fn from(set: &'a Set) -> Self {
+        match set {
+            Set::StringSet(set) => Self::StringSet(set),
+            Set::NumSet(set) => Self::NumSet(set),
+            Set::BinarySet(set) => Self::BinarySet(set),
+        }
+    }
+}
+
+impl<'a> From<&'a Value> for ValueSer<'a> {
+    fn from(value: &'a Value) -> Self {
+        match value {
+            Value::Scalar(scalar) => scalar.into(),
+            Value::Set(set) => set.into(),
+            Value::Map(map) => Self::Map(map),
+            Value::List(list) => Self::List(list),
+        }
+    }
+}
+
+impl ValueDe {
+    fn into_value<E>(self) -> Result<Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(match self {
+            Self::String(s) => Scalar::String(s).into(),
+            Self::Num(n) => Scalar::Num(n).into(),
+            Self::Bool(b) => Scalar::Bool(b).into(),
+            Self::Binary(b) => {
+                Scalar::Binary(general_purpose::STANDARD.decode(b).map_err(E::custom)?).into()
+            }
+            Self::Null(true) => Scalar::Null.into(),
+            Self::Null(false) => return Err(E::custom("`NULL` must be `true`")),
+            Self::StringSet(set) => Set::StringSet(set).into(),
+            Self::NumSet(set) => Set::NumSet(set).into(),
+            Self::BinarySet(set) => Set::BinarySet(set).into(),
+            Self::Map(map) => map.into(),
+            Self::List(list) => list.into(),
+        })
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ValueSer::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ValueDe::deserialize(deserializer)?.into_value()
+    }
+}
+
+impl Serialize for Scalar {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ValueSer::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Scalar {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Value::deserialize(deserializer)? {
+            Value::Scalar(scalar) => Ok(scalar),
+            _ => Err(D::Error::custom("expected a scalar value")),
+        }
+    }
+}
+
+impl Serialize for Set {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ValueSer::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Set {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Value::deserialize(deserializer)? {
+            Value::Set(set) => Ok(set),
+            _ => Err(D::Error::custom("expected a set value")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use crate::value::{BinarySet, List, Map, Num, NumSet, Scalar, Set, StringSet, Value};
+
+    #[test]
+    fn round_trip() {
+        let value = Value::from(Map::from([
+            ("s", Value::from("a")),
+            ("n", Num::new(42).into()),
+            ("b", Scalar::new_binary(b"abc".to_vec()).into()),
+            ("bool", true.into()),
+            ("null", Scalar::Null.into()),
+            ("ss", StringSet::from(["a", "b"]).into()),
+            ("ns", NumSet::from([1, 2]).into()),
+            ("bs", BinarySet::from([b"a", b"b"]).into()),
+            ("l", List::from(["x", "y"]).into()),
+        ]));
+
+        let json = serde_json::to_value(&value).unwrap();
+        assert_eq!(
+            json!({"M": {
+                "s": {"S": "a"},
+                "n": {"N": "42"},
+                "b": {"B": "YWJj"},
+                "bool": {"BOOL": true},
+                "null": {"NULL": true},
+                "ss": {"SS": ["a", "b"]},
+                "ns": {"NS": ["1", "2"]},
+                "bs": {"BS": ["YQ==", "Yg=="]},
+                "l": {"L": [{"S": "x"}, {"S": "y"}]},
+            }}),
+            json
+        );
+        assert_eq!(value, serde_json::from_value(json).unwrap());
+    }
+
+    #[test]
+    fn wrong_kind() {
+        assert!(serde_json::from_value::<Scalar>(json!({"SS": ["a"]})).is_err());
+        assert!(serde_json::from_value::<Set>(json!({"S": "a"})).is_err());
+        assert!(serde_json::from_value::<Value>(json!({"NULL": false})).is_err());
+        assert!(serde_json::from_value::<Value>(json!({"B": "not base64!"})).is_err());
+        assert_eq!(
+            Set::from(StringSet::from(["a"])),
+            serde_json::from_value(json!({"SS": ["a"]})).unwrap()
+        );
+    }
+}
@@ -9,7 +9,7 @@ use super::base64;
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.SetTypes
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct BinarySet(BTreeSet<Vec<u8>>);
+pub struct BinarySet(pub(crate) BTreeSet<Vec<u8>>);
 
 impl BinarySet {
     /// Creates a value to use as a [DynamoDB binary set][1].
@@ -102,6 +102,37 @@ impl fmt::Display for BinarySet {
     }
 }
 
+/// Serialized as a list of base64 strings, the way DynamoDB represents them.
+#[cfg(feature = "serde")]
+impl serde::Serialize for BinarySet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.0.iter().map(base64))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BinarySet {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use base64::{engine::general_purpose, Engine as _};
+        use serde::de::Error as _;
+
+        Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|b| {
+                general_purpose::STANDARD
+                    .decode(b)
+                    .map_err(D::Error::custom)
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
@@ -173,7 +173,7 @@ mod test {
     fn find_tricky_base64() {
         /// Visible ASCII characters
         fn charset(
-        ) -> impl Iterator<Item = char> + ExactSizeIterator + DoubleEndedIterator + FusedIterator + Clone
+        ) -> impl ExactSizeIterator<Item = char> + DoubleEndedIterator + FusedIterator + Clone
         {
             (32..127).map(char::from_u32).map(Option::unwrap)
         }
@@ -9,7 +9,8 @@ use crate::Num;
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.SetTypes
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct NumSet(BTreeSet<Num>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NumSet(pub(crate) BTreeSet<Num>);
 
 impl NumSet {
     /// Creates a value to use as a [DynamoDB number set][1].
@@ -7,7 +7,8 @@ use aws_sdk_dynamodb::types::AttributeValue;
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.SetTypes
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct StringSet(BTreeSet<String>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StringSet(pub(crate) BTreeSet<String>);
 
 impl StringSet {
     /// Creates a value to use as a [DynamoDB string set][1].
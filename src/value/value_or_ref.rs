@@ -4,7 +4,9 @@ use super::Value;
 
 /// A DynamoDB value, or a reference to one stored in the collected expression values.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub(crate) enum ValueOrRef {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ValueOrRef {
     Value(Value),
     Ref(Ref),
 }
@@ -44,6 +46,7 @@ impl From<Ref> for ValueOrRef {
 /// assert_eq!(":expression_value", value.to_string())
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ref(String);
 
 impl Ref {
@@ -53,6 +56,11 @@ impl Ref {
     {
         Self(value_ref.into())
     }
+
+    /// The name of the expression attribute value, without the leading `:`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
 }
 
 impl From<String> for Ref {
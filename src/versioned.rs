@@ -0,0 +1,200 @@
+//! A stable, versioned envelope for serializing expression types.
+//!
+//! Requires the `serde` feature.
+//!
+//! Every type used to build an expression ([`Condition`], [`Update`],
+//! [`KeyCondition`], [`Path`], [`Value`], and so on) implements
+//! [`serde::Serialize`] and [`serde::Deserialize`], and round-trips exactly.
+//! Wrap them in [`Versioned`] when storing them somewhere they may outlive the
+//! version of this crate that wrote them, so a format change is detected
+//! rather than silently misread.
+//!
+//! ```
+//! use dynamodb_expression::{condition::Condition, versioned::Versioned, Num, Path};
+//! # use pretty_assertions::assert_eq;
+//!
+//! let condition = Path::new_name("name")
+//!     .attribute_exists()
+//!     .and(Path::new_name("age").greater_than(Num::new(21)));
+//!
+//! let json = serde_json::to_string(&Versioned::new(condition.clone())).unwrap();
+//! assert_eq!(
+//!     r#"{"version":1,"data":{"and":{"left":{"attribute_exists":{"path":[{"name":"name"}]}},"right":{"comparison":{"left":{"path":[{"name":"age"}]},"cmp":"gt","right":{"scalar":{"value":{"N":"21"}}}}}}}}"#,
+//!     json
+//! );
+//!
+//! let read: Versioned<Condition> = serde_json::from_str(&json).unwrap();
+//! assert_eq!(condition, read.into_inner());
+//! ```
+//!
+//! [`Condition`]: crate::condition::Condition
+//! [`Update`]: crate::update::Update
+//! [`KeyCondition`]: crate::key::KeyCondition
+//! [`Path`]: crate::path::Path
+//! [`Value`]: crate::value::Value
+
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+
+/// The version of the serialized format written by this version of the crate.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Pairs a serialized value with the [`FORMAT_VERSION`] it was written with.
+///
+/// Deserializing fails if the version isn't one this crate can read.
+///
+/// See the [module docs](self) for an example.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Versioned<T> {
+    version: u32,
+    data: T,
+}
+
+impl<T> Versioned<T> {
+    /// Wraps the value with the current [`FORMAT_VERSION`].
+    pub fn new(data: T) -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            data,
+        }
+    }
+
+    /// The format version the value was written with.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+impl<T> From<T> for Versioned<T> {
+    fn from(data: T) -> Self {
+        Self::new(data)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Versioned<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Raw<T> {
+            version: u32,
+            data: T,
+        }
+
+        let Raw { version, data } = Raw::deserialize(deserializer)?;
+        if version != FORMAT_VERSION {
+            return Err(D::Error::custom(format!(
+                "unsupported format version {version}; expected {FORMAT_VERSION}"
+            )));
+        }
+
+        Ok(Self { version, data })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::json;
+
+    use crate::{
+        condition::{attribute_type::Type, Condition},
+        path::{Name, NameRef, Path},
+        update::{Remove, Update},
+        value::{BinarySet, List, Map, Num, NumSet, Ref, Set, StringSet},
+    };
+
+    use super::Versioned;
+
+    fn round_trip<T>(value: T)
+    where
+        T: Serialize + DeserializeOwned + PartialEq + core::fmt::Debug + Clone,
+    {
+        let json = serde_json::to_string(&Versioned::new(value.clone())).unwrap();
+        let read: Versioned<T> = serde_json::from_str(&json)
+            .unwrap_or_else(|err| panic!("Failed to deserialize {json}: {err}"));
+        assert_eq!(value, read.into_inner(), "{json}");
+    }
+
+    #[test]
+    fn conditions() {
+        let path = "foo[3].bar".parse::<Path>().unwrap();
+
+        round_trip(
+            Condition::from(path.clone().size().greater_than(Ref::new("min")))
+                .and(Path::from(NameRef::from("n")).attribute_not_exists())
+                .or(path.clone().attribute_type(Type::NumberSet).parenthesize())
+                .and(
+                    Path::new_name("s")
+                        .begins_with("pre")
+                        .or(Path::new_name("l").contains(Num::new(1)))
+                        .not(),
+                )
+                .and(path.clone().between(Num::new(1), Num::new(10)))
+                .and(path.clone().in_([Num::new(1), Num::new(2)]))
+                .and(path.not_equal(Path::new_name("other"))),
+        );
+    }
+
+    #[test]
+    fn updates() {
+        round_trip(Update::from(
+            Path::new_name("a")
+                .assign("x")
+                .and(Path::new_name("b").math().add(1))
+                .and(Path::new_name("c").math().src(Path::new_name("d")).sub(2))
+                .and(Path::new_name("e").list_append().list(["y"]))
+                .and(
+                    Path::new_name("f")
+                        .if_not_exists()
+                        .assign(Map::from([("m", List::from([NumSet::from([1, 2])]))])),
+                ),
+        ));
+        round_trip(Update::from(
+            [Path::new_name("a"), Path::new_indexed_field("b", [1])]
+                .into_iter()
+                .collect::<Remove>(),
+        ));
+        round_trip(Update::from(
+            Path::new_name("tags").add(Set::from(StringSet::from(["a"]))),
+        ));
+        round_trip(Update::from(
+            Path::new_name("bin").delete(BinarySet::from([b"a"])),
+        ));
+    }
+
+    #[test]
+    fn keys() {
+        round_trip(
+            Path::new_name("id")
+                .key()
+                .equal(Num::new(1))
+                .and(Path::new_name("sort").key().begins_with("a")),
+        );
+        round_trip(Name::from("name"));
+        round_trip(Path::new_name("id").key());
+    }
+
+    #[test]
+    fn version() {
+        assert_eq!(
+            "unsupported format version 2; expected 1",
+            serde_json::from_value::<Versioned<Name>>(json!({"version": 2, "data": "a"}))
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            json!({"version": 1, "data": "a"}),
+            serde_json::to_value(Versioned::new(Name::from("a"))).unwrap()
+        );
+    }
+}
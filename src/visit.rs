@@ -0,0 +1,302 @@
+//! Traversal of the expression types by reference, with [`Visit`].
+//!
+//! Override the methods for the parts you're interested in. Each method
+//! defaults to calling the function of the same name in this module, which
+//! visits the children of that node. Call it from your own implementation to
+//! keep walking the tree.
+//!
+//! See also: [`visit_mut`], [`fold`]
+//!
+//! ```
+//! use dynamodb_expression::{path::Name, visit::Visit, Num, Path};
+//! # use pretty_assertions::assert_eq;
+//!
+//! /// Collects the names of the attributes used.
+//! #[derive(Default)]
+//! struct Names(Vec<String>);
+//!
+//! impl Visit for Names {
+//!     fn visit_name(&mut self, name: &Name) {
+//!         self.0.push(name.to_string());
+//!     }
+//! }
+//!
+//! let condition = Path::new_name("a")
+//!     .greater_than(Num::new(1))
+//!     .and("b.c[2]".parse::<Path>().unwrap().attribute_exists());
+//!
+//! let mut names = Names::default();
+//! names.visit_condition(&condition);
+//! assert_eq!(vec!["a", "b", "c"], names.0);
+//! ```
+//!
+//! [`visit_mut`]: crate::visit_mut
+//! [`fold`]: crate::fold
+
+use crate::{
+    condition::{
+        And, AttributeExists, AttributeNotExists, AttributeType, BeginsWith, Between, Comparison,
+        Condition, Contains, In, Not, Or, Parenthetical,
+    },
+    key::KeyCondition,
+    operand::{Operand, OperandType, Size},
+    path::{Element, FieldName, Name, NameRef, Path},
+    update::{Add, Delete, SetAction, Update},
+    value::ValueOrRef,
+};
+
+/// Visits the expression types by reference. See the [module docs](self).
+pub trait Visit {
+    /// Visits a [`Condition`]. Defaults to [`visit_condition`].
+    fn visit_condition(&mut self, condition: &Condition) {
+        visit_condition(self, condition)
+    }
+
+    /// Visits a [`KeyCondition`]. Defaults to [`visit_key_condition`].
+    fn visit_key_condition(&mut self, key_condition: &KeyCondition) {
+        visit_key_condition(self, key_condition)
+    }
+
+    /// Visits an [`Operand`]. Defaults to [`visit_operand`].
+    fn visit_operand(&mut self, operand: &Operand) {
+        visit_operand(self, operand)
+    }
+
+    /// Visits an [`Update`]. Defaults to [`visit_update`].
+    fn visit_update(&mut self, update: &Update) {
+        visit_update(self, update)
+    }
+
+    /// Visits a [`SetAction`]. Defaults to [`visit_set_action`].
+    fn visit_set_action(&mut self, action: &SetAction) {
+        visit_set_action(self, action)
+    }
+
+    /// Visits a [`Path`]. Defaults to [`visit_path`].
+    fn visit_path(&mut self, path: &Path) {
+        visit_path(self, path)
+    }
+
+    /// Visits an attribute name in a [`Path`]. Does nothing by default.
+    fn visit_name(&mut self, _name: &Name) {}
+
+    /// Visits a reference to an expression attribute name in a [`Path`].
+    /// Does nothing by default.
+    fn visit_name_ref(&mut self, _name_ref: &NameRef) {}
+
+    /// Visits a value, or a reference to an expression attribute value. Does
+    /// nothing by default.
+    fn visit_value(&mut self, _value: &ValueOrRef) {}
+}
+
+/// Visits the children of a [`Condition`].
+pub fn visit_condition<V>(visitor: &mut V, condition: &Condition)
+where
+    V: Visit + ?Sized,
+{
+    match condition {
+        Condition::AttributeExists(AttributeExists { path })
+        | Condition::AttributeNotExists(AttributeNotExists { path })
+        | Condition::AttributeType(AttributeType { path, .. }) => visitor.visit_path(path),
+        Condition::BeginsWith(BeginsWith {
+            path,
+            substr: value,
+        })
+        | Condition::Contains(Contains {
+            path,
+            operand: value,
+        }) => {
+            visitor.visit_path(path);
+            visitor.visit_value(value);
+        }
+        Condition::Between(Between { op, lower, upper }) => {
+            visitor.visit_operand(op);
+            visitor.visit_operand(lower);
+            visitor.visit_operand(upper);
+        }
+        Condition::In(In { op, items }) => {
+            visitor.visit_operand(op);
+            items.iter().for_each(|item| visitor.visit_operand(item));
+        }
+        Condition::Comparison(Comparison { left, right, .. }) => {
+            visitor.visit_operand(left);
+            visitor.visit_operand(right);
+        }
+        Condition::And(And { left, right }) | Condition::Or(Or { left, right }) => {
+            visitor.visit_condition(left);
+            visitor.visit_condition(right);
+        }
+        Condition::Not(Not { condition })
+        | Condition::Parenthetical(Parenthetical { condition }) => {
+            visitor.visit_condition(condition)
+        }
+    }
+}
+
+/// Visits the children of a [`KeyCondition`].
+pub fn visit_key_condition<V>(visitor: &mut V, key_condition: &KeyCondition)
+where
+    V: Visit + ?Sized,
+{
+    visitor.visit_condition(&key_condition.condition)
+}
+
+/// Visits the children of an [`Operand`].
+pub fn visit_operand<V>(visitor: &mut V, operand: &Operand)
+where
+    V: Visit + ?Sized,
+{
+    match &operand.op {
+        OperandType::Path(path) | OperandType::Size(Size { path }) => visitor.visit_path(path),
+        OperandType::Scalar(value) => visitor.visit_value(value),
+        OperandType::Condition(condition) => visitor.visit_condition(condition),
+    }
+}
+
+/// Visits the children of an [`Update`].
+pub fn visit_update<V>(visitor: &mut V, update: &Update)
+where
+    V: Visit + ?Sized,
+{
+    let Update {
+        set,
+        remove,
+        add,
+        delete,
+    } = update;
+
+    set.iter()
+        .flat_map(|set| &set.actions)
+        .for_each(|action| visitor.visit_set_action(action));
+    remove
+        .iter()
+        .flat_map(|remove| &remove.paths)
+        .for_each(|path| visitor.visit_path(path));
+    add.iter()
+        .map(|Add { path, value }| (path, value))
+        .chain(delete.iter().map(|Delete { path, subset }| (path, subset)))
+        .for_each(|(path, value)| {
+            visitor.visit_path(path);
+            visitor.visit_value(value);
+        });
+}
+
+/// Visits the children of a [`SetAction`].
+pub fn visit_set_action<V>(visitor: &mut V, action: &SetAction)
+where
+    V: Visit + ?Sized,
+{
+    match action {
+        SetAction::Assign(action) => {
+            visitor.visit_path(&action.path);
+            visitor.visit_value(&action.value);
+        }
+        SetAction::Math(action) => {
+            visitor.visit_path(&action.dst);
+            action.src.iter().for_each(|src| visitor.visit_path(src));
+            visitor.visit_value(&action.num);
+        }
+        SetAction::ListAppend(action) => {
+            visitor.visit_path(&action.dst);
+            action.src.iter().for_each(|src| visitor.visit_path(src));
+            visitor.visit_value(&action.list);
+        }
+        SetAction::IfNotExists(action) => {
+            visitor.visit_path(&action.dst);
+            action.src.iter().for_each(|src| visitor.visit_path(src));
+            visitor.visit_value(&action.value);
+        }
+    }
+}
+
+/// Visits the parts of a [`Path`].
+pub fn visit_path<V>(visitor: &mut V, path: &Path)
+where
+    V: Visit + ?Sized,
+{
+    path.elements.iter().for_each(|element| match element {
+        Element::Name(name) => visitor.visit_name(name),
+        Element::IndexedField(field) => match &field.name {
+            FieldName::Name(name) => visitor.visit_name(name),
+            FieldName::NameRef(name_ref) => visitor.visit_name_ref(name_ref),
+        },
+        Element::NameRef(name_ref) => visitor.visit_name_ref(name_ref),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        condition::Condition,
+        path::{Name, NameRef},
+        update::Update,
+        value::{Num, Ref, ValueOrRef},
+        Path,
+    };
+
+    use super::Visit;
+
+    #[derive(Default)]
+    struct Collect {
+        names: Vec<String>,
+        values: Vec<String>,
+    }
+
+    impl Visit for Collect {
+        fn visit_name(&mut self, name: &Name) {
+            self.names.push(name.to_string());
+        }
+
+        fn visit_name_ref(&mut self, name_ref: &NameRef) {
+            self.names.push(name_ref.to_string());
+        }
+
+        fn visit_value(&mut self, value: &ValueOrRef) {
+            self.values.push(value.to_string());
+        }
+    }
+
+    #[test]
+    fn condition() {
+        let condition = Condition::from(
+            Path::new_name("a")
+                .size()
+                .between(Num::new(1), Path::new_name("b")),
+        )
+        .or(Path::new_name("c")
+            .begins_with("x")
+            .and(Path::new_name("d").in_([Ref::new("e")]))
+            .parenthesize()
+            .not())
+        .and(Path::from(NameRef::new("f")).attribute_exists());
+
+        let mut collect = Collect::default();
+        collect.visit_condition(&condition);
+        assert_eq!(vec!["a", "b", "c", "d", "#f"], collect.names);
+        assert_eq!(vec!["1", r#""x""#, ":e"], collect.values);
+    }
+
+    #[test]
+    fn update() {
+        let update = Update::from(
+            Path::new_name("a")
+                .math()
+                .src(Path::new_name("b"))
+                .add(1)
+                .and(Path::new_name("c").if_not_exists().assign("x")),
+        );
+
+        let mut collect = Collect::default();
+        collect.visit_update(&update);
+        assert_eq!(vec!["a", "b", "c"], collect.names);
+        assert_eq!(vec!["1", r#""x""#], collect.values);
+
+        let mut collect = Collect::default();
+        collect.visit_update(&Path::new_name("d").remove().into());
+        collect.visit_key_condition(&Path::new_name("e").key().equal(Num::new(2)));
+        assert_eq!(vec!["d", "e"], collect.names);
+        assert_eq!(vec!["2"], collect.values);
+    }
+}
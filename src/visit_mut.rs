@@ -0,0 +1,298 @@
+//! Traversal of the expression types by mutable reference, with [`VisitMut`],
+//! to change them in place.
+//!
+//! Override the methods for the parts you're interested in. Each method
+//! defaults to calling the function of the same name in this module, which
+//! visits the children of that node. Call it from your own implementation to
+//! keep walking the tree.
+//!
+//! See also: [`visit`], [`fold`]
+//!
+//! ```
+//! use dynamodb_expression::{path::Name, visit_mut::VisitMut, Num, Path};
+//! # use pretty_assertions::assert_eq;
+//!
+//! /// Renames an attribute everywhere it's used.
+//! struct Rename {
+//!     from: Name,
+//!     to: Name,
+//! }
+//!
+//! impl VisitMut for Rename {
+//!     fn visit_name_mut(&mut self, name: &mut Name) {
+//!         if *name == self.from {
+//!             *name = self.to.clone();
+//!         }
+//!     }
+//! }
+//!
+//! let mut condition = Path::new_name("age")
+//!     .greater_than(Num::new(21))
+//!     .and("friends[0].age".parse::<Path>().unwrap().attribute_exists());
+//!
+//! Rename {
+//!     from: Name::from("age"),
+//!     to: Name::from("years"),
+//! }
+//! .visit_condition_mut(&mut condition);
+//! assert_eq!(
+//!     "years > 21 AND attribute_exists(friends[0].years)",
+//!     condition.to_string()
+//! );
+//! ```
+//!
+//! [`visit`]: crate::visit
+//! [`fold`]: crate::fold
+
+use crate::{
+    condition::{
+        And, AttributeExists, AttributeNotExists, AttributeType, BeginsWith, Between, Comparison,
+        Condition, Contains, In, Not, Or, Parenthetical,
+    },
+    key::KeyCondition,
+    operand::{Operand, OperandType, Size},
+    path::{Element, FieldName, Name, NameRef, Path},
+    update::{Add, Delete, SetAction, Update},
+    value::ValueOrRef,
+};
+
+/// Visits the expression types by mutable reference. See the [module docs](self).
+pub trait VisitMut {
+    /// Visits a [`Condition`]. Defaults to [`visit_condition_mut`].
+    fn visit_condition_mut(&mut self, condition: &mut Condition) {
+        visit_condition_mut(self, condition)
+    }
+
+    /// Visits a [`KeyCondition`]. Defaults to [`visit_key_condition_mut`].
+    fn visit_key_condition_mut(&mut self, key_condition: &mut KeyCondition) {
+        visit_key_condition_mut(self, key_condition)
+    }
+
+    /// Visits an [`Operand`]. Defaults to [`visit_operand_mut`].
+    fn visit_operand_mut(&mut self, operand: &mut Operand) {
+        visit_operand_mut(self, operand)
+    }
+
+    /// Visits an [`Update`]. Defaults to [`visit_update_mut`].
+    fn visit_update_mut(&mut self, update: &mut Update) {
+        visit_update_mut(self, update)
+    }
+
+    /// Visits a [`SetAction`]. Defaults to [`visit_set_action_mut`].
+    fn visit_set_action_mut(&mut self, action: &mut SetAction) {
+        visit_set_action_mut(self, action)
+    }
+
+    /// Visits a [`Path`]. Defaults to [`visit_path_mut`].
+    fn visit_path_mut(&mut self, path: &mut Path) {
+        visit_path_mut(self, path)
+    }
+
+    /// Visits an attribute name in a [`Path`]. Does nothing by default.
+    fn visit_name_mut(&mut self, _name: &mut Name) {}
+
+    /// Visits a reference to an expression attribute name in a [`Path`].
+    /// Does nothing by default.
+    fn visit_name_ref_mut(&mut self, _name_ref: &mut NameRef) {}
+
+    /// Visits a value, or a reference to an expression attribute value. Does
+    /// nothing by default.
+    fn visit_value_mut(&mut self, _value: &mut ValueOrRef) {}
+}
+
+/// Visits the children of a [`Condition`].
+pub fn visit_condition_mut<V>(visitor: &mut V, condition: &mut Condition)
+where
+    V: VisitMut + ?Sized,
+{
+    match condition {
+        Condition::AttributeExists(AttributeExists { path })
+        | Condition::AttributeNotExists(AttributeNotExists { path })
+        | Condition::AttributeType(AttributeType { path, .. }) => visitor.visit_path_mut(path),
+        Condition::BeginsWith(BeginsWith {
+            path,
+            substr: value,
+        })
+        | Condition::Contains(Contains {
+            path,
+            operand: value,
+        }) => {
+            visitor.visit_path_mut(path);
+            visitor.visit_value_mut(value);
+        }
+        Condition::Between(Between { op, lower, upper }) => {
+            visitor.visit_operand_mut(op);
+            visitor.visit_operand_mut(lower);
+            visitor.visit_operand_mut(upper);
+        }
+        Condition::In(In { op, items }) => {
+            visitor.visit_operand_mut(op);
+            items
+                .iter_mut()
+                .for_each(|item| visitor.visit_operand_mut(item));
+        }
+        Condition::Comparison(Comparison { left, right, .. }) => {
+            visitor.visit_operand_mut(left);
+            visitor.visit_operand_mut(right);
+        }
+        Condition::And(And { left, right }) | Condition::Or(Or { left, right }) => {
+            visitor.visit_condition_mut(left);
+            visitor.visit_condition_mut(right);
+        }
+        Condition::Not(Not { condition })
+        | Condition::Parenthetical(Parenthetical { condition }) => {
+            visitor.visit_condition_mut(condition)
+        }
+    }
+}
+
+/// Visits the children of a [`KeyCondition`].
+pub fn visit_key_condition_mut<V>(visitor: &mut V, key_condition: &mut KeyCondition)
+where
+    V: VisitMut + ?Sized,
+{
+    visitor.visit_condition_mut(&mut key_condition.condition)
+}
+
+/// Visits the children of an [`Operand`].
+pub fn visit_operand_mut<V>(visitor: &mut V, operand: &mut Operand)
+where
+    V: VisitMut + ?Sized,
+{
+    match &mut operand.op {
+        OperandType::Path(path) | OperandType::Size(Size { path }) => visitor.visit_path_mut(path),
+        OperandType::Scalar(value) => visitor.visit_value_mut(value),
+        OperandType::Condition(condition) => visitor.visit_condition_mut(condition),
+    }
+}
+
+/// Visits the children of an [`Update`].
+pub fn visit_update_mut<V>(visitor: &mut V, update: &mut Update)
+where
+    V: VisitMut + ?Sized,
+{
+    let Update {
+        set,
+        remove,
+        add,
+        delete,
+    } = update;
+
+    set.iter_mut()
+        .flat_map(|set| &mut set.actions)
+        .for_each(|action| visitor.visit_set_action_mut(action));
+    remove
+        .iter_mut()
+        .flat_map(|remove| &mut remove.paths)
+        .for_each(|path| visitor.visit_path_mut(path));
+    add.iter_mut()
+        .map(|Add { path, value }| (path, value))
+        .chain(
+            delete
+                .iter_mut()
+                .map(|Delete { path, subset }| (path, subset)),
+        )
+        .for_each(|(path, value)| {
+            visitor.visit_path_mut(path);
+            visitor.visit_value_mut(value);
+        });
+}
+
+/// Visits the children of a [`SetAction`].
+pub fn visit_set_action_mut<V>(visitor: &mut V, action: &mut SetAction)
+where
+    V: VisitMut + ?Sized,
+{
+    match action {
+        SetAction::Assign(action) => {
+            visitor.visit_path_mut(&mut action.path);
+            visitor.visit_value_mut(&mut action.value);
+        }
+        SetAction::Math(action) => {
+            visitor.visit_path_mut(&mut action.dst);
+            action
+                .src
+                .iter_mut()
+                .for_each(|src| visitor.visit_path_mut(src));
+            visitor.visit_value_mut(&mut action.num);
+        }
+        SetAction::ListAppend(action) => {
+            visitor.visit_path_mut(&mut action.dst);
+            action
+                .src
+                .iter_mut()
+                .for_each(|src| visitor.visit_path_mut(src));
+            visitor.visit_value_mut(&mut action.list);
+        }
+        SetAction::IfNotExists(action) => {
+            visitor.visit_path_mut(&mut action.dst);
+            action
+                .src
+                .iter_mut()
+                .for_each(|src| visitor.visit_path_mut(src));
+            visitor.visit_value_mut(&mut action.value);
+        }
+    }
+}
+
+/// Visits the parts of a [`Path`].
+pub fn visit_path_mut<V>(visitor: &mut V, path: &mut Path)
+where
+    V: VisitMut + ?Sized,
+{
+    path.elements.iter_mut().for_each(|element| match element {
+        Element::Name(name) => visitor.visit_name_mut(name),
+        Element::IndexedField(field) => match &mut field.name {
+            FieldName::Name(name) => visitor.visit_name_mut(name),
+            FieldName::NameRef(name_ref) => visitor.visit_name_ref_mut(name_ref),
+        },
+        Element::NameRef(name_ref) => visitor.visit_name_ref_mut(name_ref),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        update::Update,
+        value::{Num, Ref, ValueOrRef},
+        Path,
+    };
+
+    use super::VisitMut;
+
+    /// Replaces every value with a reference to it.
+    #[derive(Default)]
+    struct Refs(usize);
+
+    impl VisitMut for Refs {
+        fn visit_value_mut(&mut self, value: &mut ValueOrRef) {
+            if let ValueOrRef::Value(_) = value {
+                *value = Ref::new(format!("v{}", self.0)).into();
+                self.0 += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn values() {
+        let mut condition = Path::new_name("a")
+            .in_([Num::new(1), Num::new(2)])
+            .and(Path::new_name("b").contains("x"))
+            .or(Path::new_name("c").equal(Ref::new("c")));
+        Refs::default().visit_condition_mut(&mut condition);
+        assert_eq!(
+            "a IN (:v0,:v1) AND contains(b, :v2) OR c = :c",
+            condition.to_string()
+        );
+
+        let mut update = Update::from(
+            Path::new_name("a")
+                .assign(Num::new(1))
+                .and(Path::new_name("b").list_append().list([Num::new(2)])),
+        );
+        Refs::default().visit_update_mut(&mut update);
+        assert_eq!("SET a = :v0, b = list_append(b, :v1)", update.to_string());
+    }
+}
@@ -19,10 +19,9 @@ use itertools::Itertools;
 use pretty_assertions::{assert_eq, assert_ne};
 
 use crate::dynamodb::{
-    debug::DebugList,
     item::{new_item, ATTR_ID, ATTR_LIST, ATTR_MAP, ATTR_NULL, ATTR_NUM, ATTR_STRING},
     setup::{clean_table, delete_table},
-    Config, DebugItem,
+    Config, DebugItem, DebugList,
 };
 
 const ITEM_ID: &str = "sanity item";
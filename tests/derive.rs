@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use dynamodb_expression::{path::typed::TypedPath, value::StringSet, DynamoPaths, Num, Path};
+use pretty_assertions::assert_eq;
+
+#[derive(DynamoPaths)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct Person {
+    first_name: String,
+    #[serde(rename = "years")]
+    age: u8,
+    address: Option<Address>,
+    previous_addresses: Vec<Address>,
+    #[serde(with = "dynamodb_expression::value::string_set")]
+    tags: Vec<String>,
+    scores: HashMap<String, f64>,
+    #[serde(skip)]
+    cache: (),
+    #[serde(flatten)]
+    audit: Audit,
+    r#type: String,
+}
+
+#[derive(DynamoPaths)]
+#[allow(dead_code)]
+struct Address {
+    city: String,
+    zips: Vec<u32>,
+}
+
+#[derive(DynamoPaths)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[allow(dead_code)]
+struct Audit {
+    created_by: String,
+}
+
+#[test]
+fn paths() {
+    let person = Person::paths();
+
+    assert_eq!(Path::new_name("firstName"), person.first_name().into());
+    assert_eq!(Path::new_name("years"), person.age().into());
+    assert_eq!(Path::new_name("type"), person.r#type().into());
+    assert_eq!(
+        "address.city".parse::<Path>().unwrap(),
+        person.address().city().into()
+    );
+    assert_eq!(
+        "previousAddresses[2].zips[0]".parse::<Path>().unwrap(),
+        person.previous_addresses().index(2).zips().index(0).into()
+    );
+    assert_eq!(
+        "scores.math".parse::<Path>().unwrap(),
+        person.scores().get("math").into()
+    );
+    assert_eq!(
+        Path::new_name("CREATED_BY"),
+        person.audit().created_by().into()
+    );
+}
+
+#[test]
+fn operations() {
+    let person = Person::paths();
+
+    assert_eq!(
+        r#"begins_with(firstName, "J")"#,
+        person.first_name().begins_with("J").to_string()
+    );
+    assert_eq!(
+        "years BETWEEN 18 AND 65",
+        person.age().between(Num::new(18), Num::new(65)).to_string()
+    );
+    assert_eq!("ADD years 1", person.age().add(1).to_string());
+    assert_eq!(
+        r#"ADD tags ["a"]"#,
+        person.tags().add(StringSet::from(["a"])).to_string()
+    );
+    assert_eq!(
+        "size(previousAddresses) > 1",
+        person
+            .previous_addresses()
+            .size()
+            .greater_than(Num::new(1))
+            .to_string()
+    );
+    assert_eq!(
+        "attribute_exists(address)",
+        person.address().attribute_exists().to_string()
+    );
+    assert_eq!(
+        "scores.math < 50",
+        person
+            .scores()
+            .get("math")
+            .less_than(Num::new(50))
+            .to_string()
+    );
+}
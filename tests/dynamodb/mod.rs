@@ -1,12 +1,10 @@
 //! Helpers for running tests against DynamoDB.
 
 pub mod config;
-pub mod debug;
 pub mod item;
 pub mod setup;
 
 #[allow(unused_imports)]
-pub use self::{
-    config::Config,
-    debug::{DebugAttributeValue, DebugItem},
-};
+pub use self::config::Config;
+#[allow(unused_imports)]
+pub use dynamodb_expression::testing::{DebugAttributeValue, DebugItem, DebugList};
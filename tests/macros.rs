@@ -0,0 +1,129 @@
+use dynamodb_expression::{
+    condition,
+    condition::attribute_type::Type,
+    update,
+    update::{Remove, Update},
+    value::{List, StringSet},
+    Num, Path,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn conditions() {
+    let min_age = Num::new(21);
+    let prefix = "J";
+
+    assert_eq!(
+        Path::new_name("age")
+            .greater_than_or_equal(Num::new(21))
+            .and(Path::new_name("name").begins_with("J"))
+            .and(Path::new_name("email").attribute_exists()),
+        condition!(age >= {min_age} AND begins_with(name, {prefix}) AND attribute_exists(email))
+    );
+
+    assert_eq!(
+        r#"a = 1 OR b <> "x" AND NOT c < d"#,
+        condition!(a = 1 or b != "x" AND NOT c < d).to_string()
+    );
+    assert_eq!(
+        "(a <= -1.5 OR b > c) AND d >= e",
+        condition!((a <= -1.5 OR b > c) AND d >= e).to_string()
+    );
+    assert_eq!(
+        "foo[3].bar BETWEEN 1 AND 10",
+        condition!(foo[3].bar BETWEEN 1 AND 10).to_string()
+    );
+    assert_eq!(
+        r#"size(tags) IN (1,2,"three",true)"#,
+        condition!(size(tags) in (1, 2, "three", true)).to_string()
+    );
+    assert_eq!(
+        r#"attribute_not_exists(a) AND attribute_type(b, SS) AND contains(c, "x")"#,
+        condition!(attribute_not_exists(a) AND attribute_type(b, SS) AND contains(c, "x"))
+            .to_string()
+    );
+    assert_eq!(
+        "attribute_type(type, M) OR attribute_type(b, NULL)",
+        condition!(attribute_type(type, "M") OR attribute_type(b, { Type::Null })).to_string()
+    );
+
+    let path = Path::new_indexed_field("x", [1, 2]);
+    let existing = Path::new_name("y").attribute_exists();
+    assert_eq!(
+        "x[1][2] = y AND attribute_exists(y)",
+        condition!({ path.clone() } == y AND { existing.clone() }).to_string()
+    );
+    assert_eq!(
+        "attribute_exists(x[1][2])",
+        condition!(attribute_exists({ path })).to_string()
+    );
+
+    // Interpolated `AND` and `OR` conditions stay grouped.
+    let x_and_y = condition!(attribute_exists(x) AND attribute_exists(y));
+    assert_eq!(
+        "NOT (attribute_exists(x) AND attribute_exists(y))",
+        condition!(NOT { x_and_y }).to_string()
+    );
+    let x_or_y = condition!(attribute_exists(x) OR attribute_exists(y));
+    assert_eq!(
+        "(attribute_exists(x) OR attribute_exists(y)) AND z = 1",
+        condition!({ x_or_y } AND z = 1).to_string()
+    );
+}
+
+#[test]
+fn updates() {
+    assert_eq!(
+        Update::from(
+            Path::new_name("visits")
+                .math()
+                .src(Path::new_name("visits"))
+                .add(1)
+                .and(Path::new_name("name").assign("Jane"))
+                .and(
+                    Path::new_name("nickname")
+                        .if_not_exists()
+                        .src(Path::new_name("nickname"))
+                        .assign("J")
+                )
+        ),
+        update!(
+            SET visits = visits + 1, name = "Jane", nickname = if_not_exists(nickname, "J")
+        )
+    );
+
+    let list = List::from([Num::new(1), Num::new(2)]);
+    assert_eq!(
+        "SET a = list_append(a, [1, 2]), b = list_append([1, 2], c), d = e - 3",
+        update!(
+            SET a = list_append(a, { list.clone() }),
+                b = list_append({ list }, c),
+                d = e - 3
+        )
+        .to_string()
+    );
+
+    assert_eq!(
+        Update::from(Remove::from_iter([
+            Path::new_name("a"),
+            "b[0].c".parse().unwrap(),
+        ])),
+        update!(remove a, b[0].c)
+    );
+
+    assert_eq!("ADD count 5", update!(ADD count 5).to_string());
+    assert_eq!(
+        r#"SET a = 1 REMOVE b, c ADD d 2, e 3 DELETE f ["x"]"#,
+        update!(
+            DELETE f { StringSet::from(["x"]) }
+            set a = 1
+            ADD d 2, e 3
+            REMOVE b, c
+        )
+        .to_string()
+    );
+    assert_eq!(
+        r#"DELETE colors ["red"]"#,
+        update!(DELETE colors { StringSet::from(["red"]) }).to_string()
+    );
+}
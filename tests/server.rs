@@ -0,0 +1,254 @@
+//! Runs the AWS SDK's client against the local DynamoDB stand-in.
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use aws_sdk_dynamodb::{
+    config::{BehaviorVersion, Credentials, Region},
+    types::{
+        AttributeDefinition, AttributeValue, KeySchemaElement, KeyType, Put, ReturnValue,
+        ScalarAttributeType, TransactWriteItem,
+    },
+    Client,
+};
+use dynamodb_expression::{fake::server::Server, Expression, Num, Path};
+use pretty_assertions::assert_eq;
+
+const TABLE: &str = "server-test";
+
+fn client() -> Client {
+    let server = Server::bind("127.0.0.1:0").unwrap();
+    let endpoint_url = server.endpoint_url().unwrap();
+    server.spawn();
+
+    Client::from_conf(
+        aws_sdk_dynamodb::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .endpoint_url(endpoint_url)
+            .region(Region::new("us-local-1"))
+            .credentials_provider(Credentials::new("local", "local", None, None, "test"))
+            .build(),
+    )
+}
+
+fn key(id: &str, n: i32) -> HashMap<String, AttributeValue> {
+    HashMap::from([
+        ("id".to_string(), AttributeValue::S(id.into())),
+        ("n".to_string(), AttributeValue::N(n.to_string())),
+    ])
+}
+
+#[tokio::test]
+async fn sdk_client() {
+    let client = client();
+
+    client
+        .create_table()
+        .table_name(TABLE)
+        .key_schema(
+            KeySchemaElement::builder()
+                .attribute_name("id")
+                .key_type(KeyType::Hash)
+                .build()
+                .unwrap(),
+        )
+        .key_schema(
+            KeySchemaElement::builder()
+                .attribute_name("n")
+                .key_type(KeyType::Range)
+                .build()
+                .unwrap(),
+        )
+        .attribute_definitions(
+            AttributeDefinition::builder()
+                .attribute_name("id")
+                .attribute_type(ScalarAttributeType::S)
+                .build()
+                .unwrap(),
+        )
+        .attribute_definitions(
+            AttributeDefinition::builder()
+                .attribute_name("n")
+                .attribute_type(ScalarAttributeType::N)
+                .build()
+                .unwrap(),
+        )
+        .send()
+        .await
+        .unwrap();
+
+    for n in 1..=3 {
+        Expression::builder()
+            .with_condition(Path::new_name("id").attribute_not_exists())
+            .build()
+            .put_item(&client)
+            .table_name(TABLE)
+            .set_item(Some(key("a", n)))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let err = Expression::builder()
+        .with_condition(Path::new_name("id").attribute_not_exists())
+        .build()
+        .put_item(&client)
+        .table_name(TABLE)
+        .set_item(Some(key("a", 1)))
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err
+        .into_service_error()
+        .is_conditional_check_failed_exception());
+
+    let updated = Expression::builder()
+        .with_update(Path::new_name("count").add(Num::new(2)))
+        .build()
+        .update_item(&client)
+        .table_name(TABLE)
+        .set_key(Some(key("a", 2)))
+        .return_values(ReturnValue::AllNew)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        Some(&AttributeValue::N("2".into())),
+        updated.attributes.unwrap().get("count")
+    );
+
+    let page = Expression::builder()
+        .with_key_condition(
+            Path::new_name("id")
+                .key()
+                .equal("a")
+                .and(Path::new_name("n").key().greater_than(Num::new(1))),
+        )
+        .build()
+        .query(&client)
+        .table_name(TABLE)
+        .scan_index_forward(false)
+        .limit(1)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(vec![key("a", 3)], page.items.unwrap());
+    assert_eq!(Some(key("a", 3)), page.last_evaluated_key);
+
+    let got = Expression::builder()
+        .with_projection(["count"])
+        .build()
+        .get_item(&client)
+        .table_name(TABLE)
+        .set_key(Some(key("a", 2)))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        Some(HashMap::from([(
+            "count".to_string(),
+            AttributeValue::N("2".into())
+        )])),
+        got.item
+    );
+
+    let err = client
+        .transact_write_items()
+        .transact_items(
+            TransactWriteItem::builder()
+                .put(
+                    Put::builder()
+                        .table_name(TABLE)
+                        .set_item(Some(key("b", 1)))
+                        .build()
+                        .unwrap(),
+                )
+                .build(),
+        )
+        .transact_items(
+            TransactWriteItem::builder()
+                .put(
+                    Put::builder()
+                        .table_name(TABLE)
+                        .set_item(Some(key("a", 1)))
+                        .condition_expression("attribute_not_exists(id)")
+                        .build()
+                        .unwrap(),
+                )
+                .build(),
+        )
+        .send()
+        .await
+        .unwrap_err()
+        .into_service_error();
+    assert!(err.is_transaction_canceled_exception());
+
+    let scanned = client.scan().table_name(TABLE).send().await.unwrap();
+    assert_eq!(3, scanned.count);
+
+    let deleted = client
+        .delete_item()
+        .table_name(TABLE)
+        .set_key(Some(key("a", 1)))
+        .return_values(ReturnValue::AllOld)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(Some(key("a", 1)), deleted.attributes);
+}
+
+#[test]
+fn chunked_requests() {
+    let server = Server::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    server.spawn();
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+    // Two requests on the same connection, so a chunked body that isn't read
+    // to its end holds up the one after it.
+    for _ in 0..2 {
+        stream
+            .write_all(
+                b"POST / HTTP/1.1\r\n\
+                X-Amz-Target: DynamoDB_20120810.ListTables\r\n\
+                Transfer-Encoding: chunked\r\n\
+                \r\n\
+                1\r\n{\r\n\
+                1\r\n}\r\n\
+                0\r\n\
+                \r\n",
+            )
+            .unwrap();
+
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!("HTTP/1.1 200 OK", line.trim_end());
+
+        let mut length = 0;
+        loop {
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+
+            if let Some(value) = header.strip_prefix("Content-Length: ") {
+                length = value.parse().unwrap();
+            }
+        }
+
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        assert_eq!(b"{\"TableNames\":[]}", body.as_slice());
+    }
+}